@@ -36,6 +36,6 @@ fn main() {
 
     match Dbc::try_from(data.as_ref()) {
         Ok(dbc_content) => println!("{dbc_content:#?}"),
-        Err(e) => eprintln!("Error parsing DBC file '{path}': {e}"),
+        Err(e) => eprint!("{}", e.report(path)),
     }
 }
@@ -0,0 +1,60 @@
+use std::convert::TryFrom;
+use std::fs;
+use std::process::ExitCode;
+
+use can_dbc::{merge, Dbc};
+use clap::Parser;
+
+/// Three-way merge of DBC files, usable as a git merge driver:
+/// `driver = merge_driver %O %A %B`
+///
+/// On conflicts, the written file contains our version of each conflicting part and no conflict markers.
+/// The conflicts are only printed, so resolve them by editing the file with the printed versions at hand,
+/// or rerun the merge with `git checkout --merge <file>` to see them again.
+#[derive(Parser)]
+#[command(about, version)]
+struct Args {
+    /// Common ancestor of both versions
+    base: String,
+
+    /// Our version, overwritten with the merge result
+    ours: String,
+
+    /// Their version
+    theirs: String,
+}
+
+fn read(path: &str) -> String {
+    fs::read_to_string(path).expect("Unable to read input file")
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let (base, ours, theirs) = (read(&args.base), read(&args.ours), read(&args.theirs));
+
+    let mut versions = Vec::new();
+    for (path, data) in [
+        (&args.base, &base),
+        (&args.ours, &ours),
+        (&args.theirs, &theirs),
+    ] {
+        match Dbc::try_from(data.as_str()) {
+            Ok(dbc) => versions.push(dbc),
+            Err(e) => {
+                eprint!("{}", e.report(path));
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let merged = merge(&versions[0], &versions[1], &versions[2]);
+    fs::write(&args.ours, merged.dbc.to_string()).expect("Unable to write output file");
+    for conflict in &merged.conflicts {
+        eprint!("{conflict}");
+    }
+    if merged.is_clean() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
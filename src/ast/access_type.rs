@@ -1,6 +1,11 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
+#[cfg(feature = "std")]
 use crate::parser::{parse_uint, single_inner, validated};
+#[cfg(feature = "std")]
 use crate::DbcError;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -12,6 +17,7 @@ pub enum AccessType {
     DummyNodeVector3,
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<Pair<'_, Rule>> for AccessType {
     type Error = DbcError;
 
@@ -29,3 +35,14 @@ impl TryFrom<Pair<'_, Rule>> for AccessType {
         })
     }
 }
+
+impl fmt::Display for AccessType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::DummyNodeVector0 => "DUMMY_NODE_VECTOR0",
+            Self::DummyNodeVector1 => "DUMMY_NODE_VECTOR1",
+            Self::DummyNodeVector2 => "DUMMY_NODE_VECTOR2",
+            Self::DummyNodeVector3 => "DUMMY_NODE_VECTOR3",
+        })
+    }
+}
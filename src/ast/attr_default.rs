@@ -1,9 +1,10 @@
 use crate::ast::AttributeValue;
+use alloc::borrow::Cow;
 
 // FIXME: not used!
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct AttrDefault {
-    pub name: String,
-    pub value: AttributeValue,
+pub struct AttrDefault<'a> {
+    pub name: Cow<'a, str>,
+    pub value: AttributeValue<'a>,
 }
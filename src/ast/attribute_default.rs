@@ -1,19 +1,28 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
 use crate::ast::AttributeValue;
-use crate::parser::{expect_empty, next, parse_next_inner_str, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{expect_empty, next, parse_next_inner_str};
+use crate::writer::Quoted;
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct AttributeDefault {
-    pub name: String,
-    pub value: AttributeValue,
+pub struct AttributeDefault<'a> {
+    pub name: Cow<'a, str>,
+    pub value: AttributeValue<'a>,
 }
 
-impl TryFrom<Pair<'_, Rule>> for AttributeDefault {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for AttributeDefault<'a> {
     type Error = DbcError;
 
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let rule = value.as_rule();
         if !matches!(rule, Rule::ba_def_def | Rule::ba_def_def_rel) {
             return Err(DbcError::ExpectedRule(Rule::ba_def_def, rule));
@@ -28,7 +37,26 @@ impl TryFrom<Pair<'_, Rule>> for AttributeDefault {
     }
 }
 
-#[cfg(test)]
+impl AttributeDefault<'_> {
+    /// Write the default either as `BA_DEF_DEF_` or, for relation attributes, as `BA_DEF_DEF_REL_`
+    pub(crate) fn write(&self, f: &mut fmt::Formatter<'_>, relation: bool) -> fmt::Result {
+        let keyword = if relation {
+            "BA_DEF_DEF_REL_"
+        } else {
+            "BA_DEF_DEF_"
+        };
+        write!(f, "{keyword} {} {};", Quoted(&self.name), self.value)
+    }
+}
+
+impl fmt::Display for AttributeDefault<'_> {
+    /// Write attribute default: `BA_DEF_DEF_ attribute_name default_value;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, false)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::ast::AttributeValue;
@@ -40,8 +68,8 @@ mod tests {
 BA_DEF_DEF_  "ZUV" "OAL";
 "#;
         let exp = AttributeDefault {
-            name: "ZUV".to_string(),
-            value: AttributeValue::String("OAL".to_string()),
+            name: "ZUV".into(),
+            value: AttributeValue::String("OAL".into()),
         };
         let val = test_into::<AttributeDefault>(def.trim_start(), Rule::ba_def_def);
         assert_eq!(val, exp);
@@ -1,23 +1,38 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::parser::{expect_empty, inner_str, next, next_optional_rule, next_rule, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{expect_empty, inner_str, next, next_optional_rule, next_rule};
+use crate::writer::Quoted;
 use crate::AttributeValueType;
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum AttributeDefinition {
-    Message(String, AttributeValueType),
-    Node(String, AttributeValueType),
-    Signal(String, AttributeValueType),
-    EnvironmentVariable(String, AttributeValueType),
-    Plain(String, AttributeValueType),
+pub enum AttributeDefinition<'a> {
+    Message(Cow<'a, str>, AttributeValueType<'a>),
+    Node(Cow<'a, str>, AttributeValueType<'a>),
+    Signal(Cow<'a, str>, AttributeValueType<'a>),
+    EnvironmentVariable(Cow<'a, str>, AttributeValueType<'a>),
+    Plain(Cow<'a, str>, AttributeValueType<'a>),
+    /// `BA_DEF_REL_ BU_SG_REL_` relation of a node to a signal
+    NodeToSignal(Cow<'a, str>, AttributeValueType<'a>),
+    /// `BA_DEF_REL_ BU_BO_REL_` relation of a node to a message
+    NodeToMessage(Cow<'a, str>, AttributeValueType<'a>),
+    /// `BA_DEF_REL_ BU_EV_REL_` relation of a node to an environment variable
+    NodeToEnvironmentVariable(Cow<'a, str>, AttributeValueType<'a>),
 }
 
-impl TryFrom<Pair<'_, Rule>> for AttributeDefinition {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for AttributeDefinition<'a> {
     type Error = DbcError;
 
     /// Parse attribute definition: `BA_DEF_ [object_type] attribute_name attribute_type [min max];`
-    fn try_from(pair: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(pair: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let rule = pair.as_rule();
         let expected = match rule {
             Rule::attr_def => Rule::object_type,
@@ -39,16 +54,94 @@ impl TryFrom<Pair<'_, Rule>> for AttributeDefinition {
         expect_empty(&pairs)?;
 
         Ok(match object_type.as_str() {
-            "SG_" | "BU_SG_REL_" => Self::Signal(name, value),
-            "BO_" | "BU_BO_REL_" => Self::Message(name, value),
+            "SG_" => Self::Signal(name, value),
+            "BO_" => Self::Message(name, value),
             "BU_" => Self::Node(name, value),
-            "EV_" | "BU_EV_REL_" => Self::EnvironmentVariable(name, value),
+            "EV_" => Self::EnvironmentVariable(name, value),
+            "BU_SG_REL_" => Self::NodeToSignal(name, value),
+            "BU_BO_REL_" => Self::NodeToMessage(name, value),
+            "BU_EV_REL_" => Self::NodeToEnvironmentVariable(name, value),
             _ => Self::Plain(name, value),
         })
     }
 }
 
-#[cfg(test)]
+impl<'a> AttributeDefinition<'a> {
+    /// Name of the defined attribute
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Message(name, _)
+            | Self::Node(name, _)
+            | Self::Signal(name, _)
+            | Self::EnvironmentVariable(name, _)
+            | Self::Plain(name, _)
+            | Self::NodeToSignal(name, _)
+            | Self::NodeToMessage(name, _)
+            | Self::NodeToEnvironmentVariable(name, _) => name,
+        }
+    }
+
+    /// Type of the attribute's values
+    #[must_use]
+    pub fn value_type(&self) -> &AttributeValueType<'a> {
+        match self {
+            Self::Message(_, value_type)
+            | Self::Node(_, value_type)
+            | Self::Signal(_, value_type)
+            | Self::EnvironmentVariable(_, value_type)
+            | Self::Plain(_, value_type)
+            | Self::NodeToSignal(_, value_type)
+            | Self::NodeToMessage(_, value_type)
+            | Self::NodeToEnvironmentVariable(_, value_type) => value_type,
+        }
+    }
+
+    /// Object type the attribute is defined for, e.g. `BO_` or `BU_SG_REL_`, empty for database attributes
+    #[must_use]
+    pub fn object_type(&self) -> &'static str {
+        match self {
+            Self::Message(..) => "BO_",
+            Self::Node(..) => "BU_",
+            Self::Signal(..) => "SG_",
+            Self::EnvironmentVariable(..) => "EV_",
+            Self::Plain(..) => "",
+            Self::NodeToSignal(..) => "BU_SG_REL_",
+            Self::NodeToMessage(..) => "BU_BO_REL_",
+            Self::NodeToEnvironmentVariable(..) => "BU_EV_REL_",
+        }
+    }
+
+    /// Whether this is a relation attribute definition (`BA_DEF_REL_`)
+    #[must_use]
+    pub fn is_relation(&self) -> bool {
+        matches!(
+            self,
+            Self::NodeToSignal(..) | Self::NodeToMessage(..) | Self::NodeToEnvironmentVariable(..)
+        )
+    }
+}
+
+impl fmt::Display for AttributeDefinition<'_> {
+    /// Write attribute definition: `BA_DEF_ [object_type] attribute_name attribute_type [min max];`,
+    /// or `BA_DEF_REL_ rel_object_type attribute_name attribute_type [min max];` for relations
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keyword = if self.is_relation() {
+            "BA_DEF_REL_"
+        } else {
+            "BA_DEF_"
+        };
+        let object_type = self.object_type();
+        write!(
+            f,
+            "{keyword} {object_type} {} {};",
+            Quoted(self.name()),
+            self.value_type()
+        )
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::ast::{AttributeValueType, NumericValue};
@@ -60,7 +153,7 @@ mod tests {
 BA_DEF_ BO_ "BaDef1BO" INT 0 1000000;
 "#;
         let exp = AttributeDefinition::Message(
-            "BaDef1BO".to_string(),
+            "BaDef1BO".into(),
             AttributeValueType::Int(NumericValue::Uint(0), NumericValue::Uint(1_000_000)),
         );
         let val = test_into::<AttributeDefinition>(def.trim_start(), Rule::attr_def);
@@ -72,8 +165,7 @@ BA_DEF_ BO_ "BaDef1BO" INT 0 1000000;
         let def = r#"
 BA_DEF_ BO_  "GenMsgSendType" STRING ;
 "#;
-        let exp =
-            AttributeDefinition::Message("GenMsgSendType".to_string(), AttributeValueType::String);
+        let exp = AttributeDefinition::Message("GenMsgSendType".into(), AttributeValueType::String);
         let val = test_into::<AttributeDefinition>(def.trim_start(), Rule::attr_def);
         assert_eq!(val, exp);
     }
@@ -84,7 +176,7 @@ BA_DEF_ BO_  "GenMsgSendType" STRING ;
 BA_DEF_ BU_ "BuDef1BO" INT 0 1000000;
 "#;
         let exp = AttributeDefinition::Node(
-            "BuDef1BO".to_string(),
+            "BuDef1BO".into(),
             AttributeValueType::Int(NumericValue::Uint(0), NumericValue::Uint(1_000_000)),
         );
         let val = test_into::<AttributeDefinition>(def.trim_start(), Rule::attr_def);
@@ -97,7 +189,7 @@ BA_DEF_ BU_ "BuDef1BO" INT 0 1000000;
 BA_DEF_ SG_ "SgDef1BO" INT 0 1000000;
 "#;
         let exp = AttributeDefinition::Signal(
-            "SgDef1BO".to_string(),
+            "SgDef1BO".into(),
             AttributeValueType::Int(NumericValue::Uint(0), NumericValue::Uint(1_000_000)),
         );
         let val = test_into::<AttributeDefinition>(def.trim_start(), Rule::attr_def);
@@ -110,10 +202,45 @@ BA_DEF_ SG_ "SgDef1BO" INT 0 1000000;
 BA_DEF_ EV_ "EvDef1BO" INT 0 1000000;
 "#;
         let exp = AttributeDefinition::EnvironmentVariable(
-            "EvDef1BO".to_string(),
+            "EvDef1BO".into(),
             AttributeValueType::Int(NumericValue::Uint(0), NumericValue::Uint(1_000_000)),
         );
         let val = test_into::<AttributeDefinition>(def.trim_start(), Rule::attr_def);
         assert_eq!(val, exp);
     }
+
+    #[test]
+    fn attribute_definition_write_test() {
+        for def in [
+            r#"BA_DEF_ BO_ "BaDef1BO" INT 0 1000000;"#,
+            r#"BA_DEF_ SG_ "SgDef" FLOAT -3.5 100.0;"#,
+            r#"BA_DEF_ BU_ "BuDef" HEX 0 255;"#,
+            r#"BA_DEF_ EV_ "EvDef" STRING;"#,
+            r#"BA_DEF_  "Plain" ENUM "A","B","C";"#,
+        ] {
+            let val = test_into::<AttributeDefinition>(def, Rule::attr_def);
+            assert_eq!(val.to_string(), def);
+        }
+    }
+
+    #[test]
+    fn relation_attribute_definition_test() {
+        for (def, kind) in [
+            (r#"BA_DEF_REL_ BU_SG_REL_ "SgRel" INT 0 10;"#, "BU_SG_REL_"),
+            (r#"BA_DEF_REL_ BU_BO_REL_ "BoRel" STRING;"#, "BU_BO_REL_"),
+            (r#"BA_DEF_REL_ BU_EV_REL_ "EvRel" HEX 0 255;"#, "BU_EV_REL_"),
+        ] {
+            let val = test_into::<AttributeDefinition>(def, Rule::ba_def_rel);
+            assert!(val.is_relation(), "{kind}");
+            assert_eq!(val.to_string(), def);
+        }
+        let val = test_into::<AttributeDefinition>(
+            r#"BA_DEF_REL_ BU_SG_REL_ "SgRel" STRING;"#,
+            Rule::ba_def_rel,
+        );
+        assert_eq!(
+            val,
+            AttributeDefinition::NodeToSignal("SgRel".into(), AttributeValueType::String)
+        );
+    }
 }
@@ -1,21 +1,30 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::parser::{inner_str, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::inner_str;
+use crate::writer::Quoted;
 use crate::NumericValue;
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum AttributeValue {
+pub enum AttributeValue<'a> {
     Uint(u64),
     Int(i64),
     Double(f64),
-    String(String),
+    String(Cow<'a, str>),
 }
 
-impl TryFrom<Pair<'_, Rule>> for AttributeValue {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for AttributeValue<'a> {
     type Error = DbcError;
 
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         match value.as_rule() {
             Rule::quoted_str => Ok(Self::String(inner_str(value))),
             Rule::number => Ok(match value.as_str().parse()? {
@@ -27,3 +36,14 @@ impl TryFrom<Pair<'_, Rule>> for AttributeValue {
         }
     }
 }
+
+impl fmt::Display for AttributeValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uint(v) => write!(f, "{v}"),
+            Self::Int(v) => write!(f, "{v}"),
+            Self::Double(v) => write!(f, "{}", NumericValue::Double(*v)),
+            Self::String(v) => write!(f, "{}", Quoted(v)),
+        }
+    }
+}
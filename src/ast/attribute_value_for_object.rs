@@ -1,20 +1,22 @@
 use can_dbc_pest::{Pair, Rule};
+use std::borrow::Cow;
 
 use crate::ast::AttributeValueForObjectType;
-use crate::parser::{parse_next_inner_str, validated_inner, DbcError};
+use crate::parser::{parse_next_inner_str, validated_inner};
+use crate::DbcError;
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub(crate) struct AttributeValueForObject {
-    pub name: String,
-    pub value: AttributeValueForObjectType,
+pub(crate) struct AttributeValueForObject<'a> {
+    pub name: Cow<'a, str>,
+    pub value: AttributeValueForObjectType<'a>,
 }
 
-impl TryFrom<Pair<'_, Rule>> for AttributeValueForObject {
+impl<'a> TryFrom<Pair<'a, Rule>> for AttributeValueForObject<'a> {
     type Error = DbcError;
 
     /// Parse attribute value: `BA_ attribute_name [object_type] object_name value;`
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::attr_value)?;
 
         Ok(Self {
@@ -36,9 +38,9 @@ mod tests {
 BA_ "AttrName" BU_ NodeName 12;
 "#;
         let exp = AttributeValueForObject {
-            name: "AttrName".to_string(),
+            name: "AttrName".into(),
             value: AttributeValueForObjectType::NetworkNode(
-                "NodeName".to_string(),
+                "NodeName".into(),
                 AttributeValue::Uint(12),
             ),
         };
@@ -53,9 +55,9 @@ BA_ "AttrName" BU_ NodeName 12;
 BA_ "Attribute" BU_ NodeName 12;
 "#;
         let exp = AttributeValueForObject {
-            name: "Attribute".to_string(),
+            name: "Attribute".into(),
             value: AttributeValueForObjectType::NetworkNode(
-                "NodeName".to_string(),
+                "NodeName".into(),
                 AttributeValue::Uint(12),
             ),
         };
@@ -67,9 +69,9 @@ BA_ "Attribute" BU_ NodeName 12;
 BA_ "Attribute" BU_ NodeName -12;
 "#;
         let exp = AttributeValueForObject {
-            name: "Attribute".to_string(),
+            name: "Attribute".into(),
             value: AttributeValueForObjectType::NetworkNode(
-                "NodeName".to_string(),
+                "NodeName".into(),
                 AttributeValue::Int(-12),
             ),
         };
@@ -81,9 +83,9 @@ BA_ "Attribute" BU_ NodeName -12;
 BA_ "Attribute" BU_ NodeName 12;
 "#;
         let exp = AttributeValueForObject {
-            name: "Attribute".to_string(),
+            name: "Attribute".into(),
             value: AttributeValueForObjectType::NetworkNode(
-                "NodeName".to_string(),
+                "NodeName".into(),
                 AttributeValue::Uint(12),
             ),
         };
@@ -95,9 +97,9 @@ BA_ "Attribute" BU_ NodeName 12;
 BA_ "Attribute" BU_ NodeName 12.1;
 "#;
         let exp = AttributeValueForObject {
-            name: "Attribute".to_string(),
+            name: "Attribute".into(),
             value: AttributeValueForObjectType::NetworkNode(
-                "NodeName".to_string(),
+                "NodeName".into(),
                 AttributeValue::Double(12.1),
             ),
         };
@@ -111,7 +113,7 @@ BA_ "Attribute" BU_ NodeName 12.1;
 BA_ "AttrName" BO_ 298 13;
 "#;
         let exp = AttributeValueForObject {
-            name: "AttrName".to_string(),
+            name: "AttrName".into(),
             value: AttributeValueForObjectType::MessageDefinition(
                 MessageId::Standard(298),
                 Some(AttributeValue::Uint(13)),
@@ -127,10 +129,10 @@ BA_ "AttrName" BO_ 298 13;
 BA_ "AttrName" SG_ 198 SGName 13;
 "#;
         let exp = AttributeValueForObject {
-            name: "AttrName".to_string(),
+            name: "AttrName".into(),
             value: AttributeValueForObjectType::Signal(
                 MessageId::Standard(198),
-                "SGName".to_string(),
+                "SGName".into(),
                 AttributeValue::Uint(13),
             ),
         };
@@ -144,10 +146,10 @@ BA_ "AttrName" SG_ 198 SGName 13;
 BA_ "AttrName" EV_ EvName "CharStr";
 "#;
         let exp = AttributeValueForObject {
-            name: "AttrName".to_string(),
+            name: "AttrName".into(),
             value: AttributeValueForObjectType::EnvVariable(
-                "EvName".to_string(),
-                AttributeValue::String("CharStr".to_string()),
+                "EvName".into(),
+                AttributeValue::String("CharStr".into()),
             ),
         };
         let val = test_into::<AttributeValueForObject>(def.trim_start(), Rule::attr_value);
@@ -160,8 +162,8 @@ BA_ "AttrName" EV_ EvName "CharStr";
 BA_ "AttrName" "RAW";
 "#;
         let exp = AttributeValueForObject {
-            name: "AttrName".to_string(),
-            value: AttributeValueForObjectType::Raw(AttributeValue::String("RAW".to_string())),
+            name: "AttrName".into(),
+            value: AttributeValueForObjectType::Raw(AttributeValue::String("RAW".into())),
         };
         let val = test_into::<AttributeValueForObject>(def.trim_start(), Rule::attr_value);
         assert_eq!(val, exp);
@@ -1,4 +1,5 @@
 use can_dbc_pest::{Pairs, Rule};
+use std::borrow::Cow;
 
 use crate::ast::{AttributeValue, MessageId};
 use crate::parser::{expect_empty, next, next_rule, next_string};
@@ -6,18 +7,18 @@ use crate::DbcError;
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub(crate) enum AttributeValueForObjectType {
-    Raw(AttributeValue),
-    NetworkNode(String, AttributeValue),
-    MessageDefinition(MessageId, Option<AttributeValue>),
-    Signal(MessageId, String, AttributeValue),
-    EnvVariable(String, AttributeValue),
+pub(crate) enum AttributeValueForObjectType<'a> {
+    Raw(AttributeValue<'a>),
+    NetworkNode(Cow<'a, str>, AttributeValue<'a>),
+    MessageDefinition(MessageId, Option<AttributeValue<'a>>),
+    Signal(MessageId, Cow<'a, str>, AttributeValue<'a>),
+    EnvVariable(Cow<'a, str>, AttributeValue<'a>),
 }
 
-impl TryFrom<Pairs<'_, Rule>> for AttributeValueForObjectType {
+impl<'a> TryFrom<Pairs<'a, Rule>> for AttributeValueForObjectType<'a> {
     type Error = DbcError;
 
-    fn try_from(mut value: Pairs<Rule>) -> Result<Self, Self::Error> {
+    fn try_from(mut value: Pairs<'a, Rule>) -> Result<Self, Self::Error> {
         // Expect exactly one remaining pair (the object-specific value)
         let pair = value.next().ok_or(DbcError::NoMoreRules)?;
         if let Some(more) = value.next() {
@@ -1,21 +1,30 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::parser::{next, parse_next_inner_str, validated_inner, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{next, parse_next_inner_str, validated_inner};
+use crate::writer::Quoted;
 use crate::AttributeValueForRelationType;
 
-/// An attribute attached to the relation between a node and a signal
+/// An attribute attached to the relation between a node and a signal, message or environment variable
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct AttributeValueForRelation {
-    pub name: String,
-    pub details: AttributeValueForRelationType,
+pub struct AttributeValueForRelation<'a> {
+    pub name: Cow<'a, str>,
+    pub details: AttributeValueForRelationType<'a>,
 }
 
-impl TryFrom<Pair<'_, Rule>> for AttributeValueForRelation {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for AttributeValueForRelation<'a> {
     type Error = DbcError;
 
     /// Parse attribute value for relation: `BA_REL_ attribute_name rel_object_type rel_object_data;`
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::ba_rel)?;
 
         let name = parse_next_inner_str(&mut pairs, Rule::attribute_name)?;
@@ -28,3 +37,31 @@ impl TryFrom<Pair<'_, Rule>> for AttributeValueForRelation {
         })
     }
 }
+
+impl fmt::Display for AttributeValueForRelation<'_> {
+    /// Write attribute value for relation: `BA_REL_ attribute_name rel_object_type rel_object_data;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BA_REL_ {} ", Quoted(&self.name))?;
+        match &self.details {
+            AttributeValueForRelationType::NodeToSignal {
+                node_name,
+                message_id,
+                signal_name,
+                value,
+            } => write!(
+                f,
+                "BU_SG_REL_ {node_name} SG_ {message_id} {signal_name} {value};"
+            ),
+            AttributeValueForRelationType::NodeToMessage {
+                node_name,
+                message_id,
+                value,
+            } => write!(f, "BU_BO_REL_ {node_name} {message_id} {value};"),
+            AttributeValueForRelationType::NodeToEnvironmentVariable {
+                node_name,
+                variable_name,
+                value,
+            } => write!(f, "BU_EV_REL_ {node_name} EV_ {variable_name} {value};"),
+        }
+    }
+}
@@ -1,29 +1,92 @@
+use alloc::borrow::Cow;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::parser::{expect_empty, next, next_rule, next_string, DbcError};
-use crate::{AttributeValue, MessageId};
+use crate::ast::ObjectKey;
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{expect_empty, next, next_rule, next_string};
+use crate::{AttributeValue, MessageId, ObjectRef};
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum AttributeValueForRelationType {
+#[expect(clippy::enum_variant_names)]
+pub enum AttributeValueForRelationType<'a> {
     NodeToSignal {
-        node_name: String,
+        node_name: Cow<'a, str>,
         message_id: MessageId,
-        signal_name: String,
-        value: AttributeValue,
+        signal_name: Cow<'a, str>,
+        value: AttributeValue<'a>,
     },
     NodeToMessage {
-        node_name: String,
+        node_name: Cow<'a, str>,
         message_id: MessageId,
-        value: AttributeValue,
+        value: AttributeValue<'a>,
+    },
+    NodeToEnvironmentVariable {
+        node_name: Cow<'a, str>,
+        variable_name: Cow<'a, str>,
+        value: AttributeValue<'a>,
     },
 }
 
-impl TryFrom<Pair<'_, Rule>> for AttributeValueForRelationType {
+impl<'a> AttributeValueForRelationType<'a> {
+    /// Name of the node the relation starts at
+    #[must_use]
+    pub fn node_name(&self) -> &str {
+        match self {
+            Self::NodeToSignal { node_name, .. }
+            | Self::NodeToMessage { node_name, .. }
+            | Self::NodeToEnvironmentVariable { node_name, .. } => node_name,
+        }
+    }
+
+    /// The relation the value is assigned to
+    #[must_use]
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        match self {
+            Self::NodeToSignal {
+                node_name,
+                message_id,
+                signal_name,
+                ..
+            } => ObjectKey::NodeToSignal(node_name, *message_id, signal_name),
+            Self::NodeToMessage {
+                node_name,
+                message_id,
+                ..
+            } => ObjectKey::NodeToMessage(node_name, *message_id),
+            Self::NodeToEnvironmentVariable {
+                node_name,
+                variable_name,
+                ..
+            } => ObjectKey::NodeToEnvironmentVariable(node_name, variable_name),
+        }
+    }
+
+    /// Value assigned to the relation
+    #[must_use]
+    pub fn value(&self) -> &AttributeValue<'a> {
+        match self {
+            Self::NodeToSignal { value, .. }
+            | Self::NodeToMessage { value, .. }
+            | Self::NodeToEnvironmentVariable { value, .. } => value,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for AttributeValueForRelationType<'a> {
     type Error = DbcError;
 
     /// Parse `rel_object_data`: `ident ~ signal_var` | `ident ~ env_var_val` | `ident ~ message_id ~ num_str_value`
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         if value.as_rule() != Rule::rel_object_data {
             return Err(DbcError::ExpectedRule(
                 Rule::rel_object_data,
@@ -55,9 +118,16 @@ impl TryFrom<Pair<'_, Rule>> for AttributeValueForRelationType {
             }
             Rule::env_var_val => {
                 // BU_EV_REL_: ident ~ env_var_val
-                // TODO: This case is not yet defined in AttributeValueForRelationType
-                // For now, return an error or add a new variant
-                Err(DbcError::NotImplemented("BU_EV_REL_ not yet implemented"))
+                let mut env_pairs = next_pair.into_inner();
+                let variable_name = next_string(&mut env_pairs, Rule::env_var_name)?;
+                let value = next(&mut env_pairs)?.try_into()?;
+                expect_empty(&env_pairs)?;
+
+                Ok(Self::NodeToEnvironmentVariable {
+                    node_name,
+                    variable_name,
+                    value,
+                })
             }
             Rule::message_id => {
                 // BU_BO_REL_: ident ~ message_id ~ num_str_value
@@ -75,3 +145,30 @@ impl TryFrom<Pair<'_, Rule>> for AttributeValueForRelationType {
         }
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use crate::AttributeValueForRelation;
+
+    #[test]
+    fn node_to_environment_variable_test() {
+        let def = r#"
+BA_REL_ "GsmAttr" BU_EV_REL_ Gateway EV_ Ignition 5;
+"#;
+        let exp = AttributeValueForRelation {
+            name: "GsmAttr".into(),
+            details: AttributeValueForRelationType::NodeToEnvironmentVariable {
+                node_name: "Gateway".into(),
+                variable_name: "Ignition".into(),
+                value: AttributeValue::Uint(5),
+            },
+        };
+        let val = test_into::<AttributeValueForRelation>(def.trim_start(), Rule::ba_rel);
+        assert_eq!(val, exp);
+        assert_eq!(val.to_string(), def.trim());
+        assert_eq!(val.details.node_name(), "Gateway");
+        assert_eq!(val.details.value(), &AttributeValue::Uint(5));
+    }
+}
@@ -1,22 +1,32 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
+#[cfg(feature = "std")]
 use crate::parser::{expect_empty, inner_str, next_rule};
-use crate::{DbcError, DbcResult, NumericValue};
+use crate::writer::Quoted;
+use crate::NumericValue;
+#[cfg(feature = "std")]
+use crate::{DbcError, DbcResult};
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum AttributeValueType {
+pub enum AttributeValueType<'a> {
     Int(NumericValue, NumericValue),
     Hex(NumericValue, NumericValue),
     Float(NumericValue, NumericValue),
     String,
-    Enum(Vec<String>),
+    Enum(Vec<Cow<'a, str>>),
 }
 
-impl TryFrom<Pair<'_, Rule>> for AttributeValueType {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for AttributeValueType<'a> {
     type Error = DbcError;
 
-    fn try_from(pair: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(pair: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let rule = pair.as_rule();
         Ok(match rule {
             Rule::attribute_type_int | Rule::attribute_type_hex | Rule::attribute_type_float => {
@@ -49,3 +59,24 @@ impl TryFrom<Pair<'_, Rule>> for AttributeValueType {
         })
     }
 }
+
+impl fmt::Display for AttributeValueType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(min, max) => write!(f, "INT {min} {max}"),
+            Self::Hex(min, max) => write!(f, "HEX {min} {max}"),
+            Self::Float(min, max) => write!(f, "FLOAT {min} {max}"),
+            Self::String => f.write_str("STRING"),
+            Self::Enum(values) => {
+                f.write_str("ENUM ")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", Quoted(value))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
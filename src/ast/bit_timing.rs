@@ -0,0 +1,77 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
+use can_dbc_pest::{Pair, Rule};
+
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{expect_empty, parse_next_uint, validated_inner};
+
+/// Bit timing of the network, defined by `BS_: baudrate : BTR1 , BTR2`.
+/// The section is obsolete and no longer used by most tools, but still emitted by some legacy tools.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitTiming {
+    /// Baudrate of network in KBit/s
+    pub baudrate: u64,
+    /// Value of the bit timing register 1
+    pub btr1: u64,
+    /// Value of the bit timing register 2
+    pub btr2: u64,
+}
+
+/// Former name of [`BitTiming`], which only held the baudrate
+#[deprecated(note = "use `BitTiming`, the baudrate is `BitTiming::baudrate`")]
+pub type Baudrate = BitTiming;
+
+#[cfg(feature = "std")]
+impl TryFrom<Pair<'_, Rule>> for BitTiming {
+    type Error = DbcError;
+
+    /// Parse bit timing: `BS_: [baud_rate : BTR1 , BTR2 ]`
+    ///
+    /// The optional values must be present, an empty `BS_:` section has no `BitTiming`.
+    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+        let mut pairs = validated_inner(value, Rule::bit_timing)?;
+        let value = Self {
+            baudrate: parse_next_uint(&mut pairs, Rule::uint)?,
+            btr1: parse_next_uint(&mut pairs, Rule::uint)?,
+            btr2: parse_next_uint(&mut pairs, Rule::uint)?,
+        };
+        expect_empty(&pairs)?;
+        Ok(value)
+    }
+}
+
+impl fmt::Display for BitTiming {
+    /// Write the values of the bit timing section: `baudrate : BTR1,BTR2`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} : {},{}", self.baudrate, self.btr1, self.btr2)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+
+    #[test]
+    fn bit_timing_test() {
+        let def = "BS_: 500 : 12,34\n";
+        let exp = BitTiming {
+            baudrate: 500,
+            btr1: 12,
+            btr2: 34,
+        };
+        let val = test_into::<BitTiming>(def, Rule::bit_timing);
+        assert_eq!(val, exp);
+        assert_eq!(format!("BS_: {val}\n"), def);
+    }
+
+    #[test]
+    fn empty_bit_timing_test() {
+        let pair = parse("BS_:\n", Rule::bit_timing).unwrap();
+        assert!(BitTiming::try_from(pair).is_err());
+    }
+}
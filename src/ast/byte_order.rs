@@ -1,5 +1,9 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
+#[cfg(feature = "std")]
 use crate::DbcError;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -9,6 +13,7 @@ pub enum ByteOrder {
     BigEndian,
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<Pair<'_, Rule>> for ByteOrder {
     type Error = DbcError;
 
@@ -21,7 +26,17 @@ impl TryFrom<Pair<'_, Rule>> for ByteOrder {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for ByteOrder {
+    /// Write the byte order as used in a signal definition: `0` for big endian, `1` for little endian
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::LittleEndian => "1",
+            Self::BigEndian => "0",
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
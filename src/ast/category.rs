@@ -0,0 +1,56 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+use crate::ast::{MessageId, ObjectKey, ObjectRef};
+
+/// Object a category is assigned to
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CategoryObject<'a> {
+    Node(Cow<'a, str>),
+    Message(MessageId),
+    EnvironmentVariable(Cow<'a, str>),
+}
+
+impl CategoryObject<'_> {
+    /// The object the category is assigned to
+    #[must_use]
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        match self {
+            Self::Node(name) => ObjectKey::Node(name),
+            Self::Message(id) => ObjectKey::Message(*id),
+            Self::EnvironmentVariable(name) => ObjectKey::EnvironmentVariable(name),
+        }
+    }
+}
+
+impl fmt::Display for CategoryObject<'_> {
+    /// Write the object as `BU_ node_name`, `BO_ message_id` or `EV_ env_var_name`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Node(name) => write!(f, "BU_ {name}"),
+            Self::Message(id) => write!(f, "BO_ {id}"),
+            Self::EnvironmentVariable(name) => write!(f, "EV_ {name}"),
+        }
+    }
+}
+
+/// Assignment of a category to an object (`CAT_`), obsolete
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Category<'a> {
+    pub object: CategoryObject<'a>,
+    /// Id of the [`CategoryDefinition`](crate::CategoryDefinition)
+    pub category_id: u64,
+}
+
+impl fmt::Display for Category<'_> {
+    /// Write category: `CAT_ object_type object_name category_id;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CAT_ {} {};", self.object, self.category_id)
+    }
+}
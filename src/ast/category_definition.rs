@@ -0,0 +1,18 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+/// Category definition (`CAT_DEF_`), obsolete
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CategoryDefinition<'a> {
+    pub id: u64,
+    pub name: Cow<'a, str>,
+    pub value: u64,
+}
+
+impl fmt::Display for CategoryDefinition<'_> {
+    /// Write category definition: `CAT_DEF_ category_id category_name value;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CAT_DEF_ {} {} {};", self.id, self.name, self.value)
+    }
+}
@@ -1,42 +1,51 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Pairs, Rule};
 
-use crate::ast::MessageId;
+use crate::ast::{MessageId, ObjectKey, ObjectRef};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
 use crate::parser::{
     inner_str, next, next_optional_rule, next_rule, next_string, parse_next_inner_str,
-    single_inner, validated_inner, DbcError,
+    single_inner, validated_inner,
 };
+use crate::writer::Quoted;
 
 /// Object comments
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum Comment {
+pub enum Comment<'a> {
     Node {
-        name: String,
-        comment: String,
+        name: Cow<'a, str>,
+        comment: Cow<'a, str>,
     },
     Message {
         id: MessageId,
-        comment: String,
+        comment: Cow<'a, str>,
     },
     Signal {
         message_id: MessageId,
-        name: String,
-        comment: String,
+        name: Cow<'a, str>,
+        comment: Cow<'a, str>,
     },
     EnvVar {
-        name: String,
-        comment: String,
+        name: Cow<'a, str>,
+        comment: Cow<'a, str>,
     },
     Plain {
-        comment: String,
+        comment: Cow<'a, str>,
     },
 }
 
-impl TryFrom<Pair<'_, Rule>> for Comment {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for Comment<'a> {
     type Error = DbcError;
 
     /// Parse comment: `CM_ [BU_|BO_|SG_|EV_] object_name "comment_text";`
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut inner_pairs = validated_inner(value, Rule::comment)?;
 
         let pair = next(&mut inner_pairs)?;
@@ -80,13 +89,14 @@ impl TryFrom<Pair<'_, Rule>> for Comment {
 
 /// Parse signal comment: `SG_ <message_id> [<signal_name>] "comment"`
 /// If `signal_name` is omitted, this is treated as a message comment.
+#[cfg(feature = "std")]
 fn parse_signal_comment(mut pairs: Pairs<Rule>) -> Result<Comment, DbcError> {
     let message_id = next_rule(&mut pairs, Rule::message_id)?.try_into()?;
     if let Some(name) = next_optional_rule(&mut pairs, Rule::signal_name) {
         // This is a proper signal comment with signal name
         Ok(Comment::Signal {
             message_id,
-            name: name.as_str().to_string(),
+            name: name.as_str().into(),
             comment: parse_next_inner_str(&mut pairs, Rule::quoted_str)?,
         })
     } else {
@@ -98,7 +108,56 @@ fn parse_signal_comment(mut pairs: Pairs<Rule>) -> Result<Comment, DbcError> {
     }
 }
 
-#[cfg(test)]
+impl Comment<'_> {
+    /// The object the comment is attached to, [`ObjectRef::Database`] for plain comments
+    #[must_use]
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        match self {
+            Self::Node { name, .. } => ObjectKey::Node(name),
+            Self::Message { id, .. } => ObjectKey::Message(*id),
+            Self::Signal {
+                message_id, name, ..
+            } => ObjectKey::Signal(*message_id, name),
+            Self::EnvVar { name, .. } => ObjectKey::EnvironmentVariable(name),
+            Self::Plain { .. } => ObjectKey::Database,
+        }
+    }
+
+    /// The comment text
+    #[must_use]
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Node { comment, .. }
+            | Self::Message { comment, .. }
+            | Self::Signal { comment, .. }
+            | Self::EnvVar { comment, .. }
+            | Self::Plain { comment } => comment,
+        }
+    }
+}
+
+impl fmt::Display for Comment<'_> {
+    /// Write comment: `CM_ [BU_|BO_|SG_|EV_] object_name "comment_text";`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Node { name, comment } => write!(f, "CM_ BU_ {name} {};", Quoted(comment)),
+            Self::Message { id, comment } => write!(f, "CM_ BO_ {id} {};", Quoted(comment)),
+            Self::Signal {
+                message_id,
+                name,
+                comment,
+            } => write!(f, "CM_ SG_ {message_id} {name} {};", Quoted(comment)),
+            Self::EnvVar { name, comment } => write!(f, "CM_ EV_ {name} {};", Quoted(comment)),
+            Self::Plain { comment } => write!(f, "CM_ {};", Quoted(comment)),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -110,8 +169,8 @@ CM_ SG_ 193 KLU_R_X "This is a signal comment test";
 "#;
         let exp = Comment::Signal {
             message_id: MessageId::Standard(193),
-            name: "KLU_R_X".to_string(),
-            comment: "This is a signal comment test".to_string(),
+            name: "KLU_R_X".into(),
+            comment: "This is a signal comment test".into(),
         };
         let val = test_into::<Comment>(def.trim_start(), Rule::comment);
         assert_eq!(val, exp);
@@ -124,7 +183,7 @@ CM_ BO_ 34544 "Some Message comment";
 "#;
         let exp = Comment::Message {
             id: MessageId::Standard(34544),
-            comment: "Some Message comment".to_string(),
+            comment: "Some Message comment".into(),
         };
         let val = test_into::<Comment>(def.trim_start(), Rule::comment);
         assert_eq!(val, exp);
@@ -136,8 +195,8 @@ CM_ BO_ 34544 "Some Message comment";
 CM_ BU_ network_node "Some network node comment";
 "#;
         let exp = Comment::Node {
-            name: "network_node".to_string(),
-            comment: "Some network node comment".to_string(),
+            name: "network_node".into(),
+            comment: "Some network node comment".into(),
         };
         let val = test_into::<Comment>(def.trim_start(), Rule::comment);
         assert_eq!(val, exp);
@@ -149,8 +208,8 @@ CM_ BU_ network_node "Some network node comment";
 CM_ EV_ ENVXYZ "Some env var name comment";
 "#;
         let exp = Comment::EnvVar {
-            name: "ENVXYZ".to_string(),
-            comment: "Some env var name comment".to_string(),
+            name: "ENVXYZ".into(),
+            comment: "Some env var name comment".into(),
         };
         let val = test_into::<Comment>(def.trim_start(), Rule::comment);
         assert_eq!(val, exp);
@@ -163,13 +222,25 @@ CM_ SG_ 2147548912 FooBar "Foo\\ \n \"Bar\"";
 "#;
         let exp = Comment::Signal {
             message_id: MessageId::Extended(65264),
-            name: "FooBar".to_string(),
-            comment: r#"Foo\\ \n \"Bar\""#.to_string(),
+            name: "FooBar".into(),
+            comment: r#"Foo\ \n "Bar""#.into(),
         };
         let val = test_into::<Comment>(def.trim_start(), Rule::comment);
         assert_eq!(val, exp);
     }
 
+    #[test]
+    fn trailing_backslash_test() {
+        for text in [r"C:\path\", r"\", r"a\\", r#"a\"b\"#] {
+            let comment = Comment::Plain {
+                comment: text.into(),
+            };
+            let written = comment.to_string();
+            let val = test_into::<Comment>(&written, Rule::comment);
+            assert_eq!(val, comment, "{written}");
+        }
+    }
+
     #[test]
     fn empty_signal_comment_test() {
         let def = r#"
@@ -177,10 +248,36 @@ CM_ SG_ 2147548912 FooBar "";
 "#;
         let exp = Comment::Signal {
             message_id: MessageId::Extended(65264),
-            name: "FooBar".to_string(),
-            comment: String::new(),
+            name: "FooBar".into(),
+            comment: Cow::Borrowed(""),
         };
         let val = test_into::<Comment>(def.trim_start(), Rule::comment);
         assert_eq!(val, exp);
     }
+
+    #[test]
+    fn comment_write_test() {
+        for def in [
+            r#"CM_ SG_ 193 KLU_R_X "This is a signal comment test";"#,
+            r#"CM_ BO_ 34544 "Some Message comment";"#,
+            r#"CM_ BU_ network_node "Some network node comment";"#,
+            r#"CM_ EV_ ENVXYZ "Some env var name comment";"#,
+            "CM_ \"Multi\r\nline\ncomment\";",
+            r#"CM_ BO_ 1 "Path C:\\dbc\\ \"quoted\"";"#,
+        ] {
+            let val = test_into::<Comment>(def, Rule::comment);
+            assert_eq!(val.to_string(), def);
+        }
+    }
+
+    #[test]
+    fn multi_line_comment_test() {
+        let val = test_into::<Comment>("CM_ \"Multi\r\nline\ncomment\";", Rule::comment);
+        assert_eq!(
+            val,
+            Comment::Plain {
+                comment: "Multi\r\nline\ncomment".into(),
+            }
+        );
+    }
 }
@@ -1,132 +1,260 @@
-use std::str;
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::{fmt, str};
 
-use can_dbc_pest::{DbcParser, Parser as _, Rule};
+#[cfg(feature = "std")]
+use can_dbc_pest::{DbcParser, Pair, Pairs, Parser as _, Rule};
 
 use crate::ast::{
-    AttributeDefault, AttributeDefinition, AttributeValueForObject, Baudrate, Comment,
-    EnvironmentVariable, EnvironmentVariableData, ExtendedMultiplex, Message, MessageId,
-    MessageTransmitter, MultiplexIndicator, Node, Signal, SignalExtendedValueType,
-    SignalExtendedValueTypeList, SignalGroups, SignalType, SignalTypeRef, Symbol, ValDescription,
-    ValueDescription, ValueTable, Version,
+    AttributeDefault, AttributeDefinition, AttributeValue, AttributeValueForRelation,
+    AttributeValueForRelationType, BitTiming, Category, CategoryDefinition, Comment,
+    EnvironmentVariable, EnvironmentVariableData, ExtendedMultiplex, Filter, Message, MessageId,
+    MessageTransmitter, MultiplexIndicator, Node, ObjectKey, ObjectRef, Signal,
+    SignalExtendedValueType, SignalExtendedValueTypeList, SignalGroups, SignalType, SignalTypeRef,
+    Symbol, ValDescription, ValueDescription, ValueTable, Version,
 };
-use crate::parser::{collect_all, DbcError, DbcResult};
-use crate::{AttributeValue, AttributeValueForObjectType, AttributeValueForRelation};
+#[cfg(feature = "std")]
+use crate::ast::{AttributeValueForObject, AttributeValueForObjectType, MapStrings};
+use crate::codec::{CodecError, CodecResult, DecodedMessage, DecodedSignal};
+#[cfg(feature = "std")]
+use crate::error::SourceLocation;
+use crate::error::{DbcError, DbcResult};
+#[cfg(feature = "std")]
+use crate::parser::{collect_all, skip_error_line, ParseOptions, ParseWarning};
+#[cfg(feature = "std")]
+use crate::statements::extract_statements;
+use crate::writer::{write_section, Quoted};
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Dbc {
+pub struct Dbc<'a> {
     /// Version generated by DB editor
-    pub version: Version,
+    pub version: Version<'a>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub new_symbols: Vec<Symbol>,
-    /// Baud rate of network
-    pub bit_timing: Option<Vec<Baudrate>>,
+    pub new_symbols: Vec<Symbol<'a>>,
+    /// Bit timing section (`BS_`), `None` if the section is missing.
+    /// The section usually is empty, it holds at most one `BitTiming`.
+    pub bit_timing: Option<Vec<BitTiming>>,
     /// CAN network nodes
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub nodes: Vec<Node>,
+    pub nodes: Vec<Node<'a>>,
     /// Global value table
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub value_tables: Vec<ValueTable>,
+    pub value_tables: Vec<ValueTable<'a>>,
     /// CAN message (frame) details including signal details
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub messages: Vec<Message>,
+    pub messages: Vec<Message<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub message_transmitters: Vec<MessageTransmitter>,
+    pub message_transmitters: Vec<MessageTransmitter<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub environment_variables: Vec<EnvironmentVariable>,
+    pub environment_variables: Vec<EnvironmentVariable<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub environment_variable_data: Vec<EnvironmentVariableData>,
+    pub environment_variable_data: Vec<EnvironmentVariableData<'a>>,
+    /// Signal types (`SGTYPE_`)
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub signal_types: Vec<SignalType>,
+    pub signal_types: Vec<SignalType<'a>>,
     /// Object comments
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub comments: Vec<Comment>,
+    pub comments: Vec<Comment<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub attribute_definitions: Vec<AttributeDefinition>,
+    pub attribute_definitions: Vec<AttributeDefinition<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub relation_attribute_definitions: Vec<AttributeDefinition>,
+    pub relation_attribute_definitions: Vec<AttributeDefinition<'a>>,
     // undefined
     // sigtype_attr_list: SigtypeAttrList,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub attribute_defaults: Vec<AttributeDefault>,
+    pub attribute_defaults: Vec<AttributeDefault<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub relation_attribute_defaults: Vec<AttributeDefault>,
+    pub relation_attribute_defaults: Vec<AttributeDefault<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub relation_attribute_values: Vec<AttributeValueForRelation>,
+    pub relation_attribute_values: Vec<AttributeValueForRelation<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub attribute_values_database: Vec<AttributeValueForDatabase>,
+    pub attribute_values_database: Vec<AttributeValueForDatabase<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub attribute_values_node: Vec<AttributeValueForNode>,
+    pub attribute_values_node: Vec<AttributeValueForNode<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub attribute_values_message: Vec<AttributeValueForMessage>,
+    pub attribute_values_message: Vec<AttributeValueForMessage<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub attribute_values_signal: Vec<AttributeValueForSignal>,
+    pub attribute_values_signal: Vec<AttributeValueForSignal<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub attribute_values_env: Vec<AttributeValueForEnvVariable>,
+    pub attribute_values_env: Vec<AttributeValueForEnvVariable<'a>>,
     /// Encoding for signal raw values
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub value_descriptions: Vec<ValueDescription>,
-    // obsolete + undefined
-    // category_definitions: Vec<CategoryDefinition>,
-    // obsolete + undefined
-    //categories: Vec<Category>,
-    // obsolete + undefined
-    //filter: Vec<Filter>,
+    pub value_descriptions: Vec<ValueDescription<'a>>,
+    /// Category definitions (`CAT_DEF_`), obsolete
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub category_definitions: Vec<CategoryDefinition<'a>>,
+    /// Categories assigned to objects (`CAT_`), obsolete
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub categories: Vec<Category<'a>>,
+    /// Filters (`FILTER`), obsolete
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub filter: Vec<Filter<'a>>,
+    /// Signal type references (`SIG_TYPE_REF_`), assigning a signal type to a signal
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub signal_type_refs: Vec<SignalTypeRef>,
+    pub signal_type_refs: Vec<SignalTypeRef<'a>>,
     /// Signal groups define a group of signals within a message
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub signal_groups: Vec<SignalGroups>,
+    pub signal_groups: Vec<SignalGroups<'a>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub signal_extended_value_type_list: Vec<SignalExtendedValueTypeList>,
+    pub signal_extended_value_type_list: Vec<SignalExtendedValueTypeList<'a>>,
     /// Extended multiplex attributes
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub extended_multiplex: Vec<ExtendedMultiplex>,
+    pub extended_multiplex: Vec<ExtendedMultiplex<'a>>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct AttributeValueForDatabase {
-    pub name: String,
-    pub value: AttributeValue,
+pub struct AttributeValueForDatabase<'a> {
+    pub name: Cow<'a, str>,
+    pub value: AttributeValue<'a>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct AttributeValueForNode {
-    pub name: String,
-    pub node_name: String,
-    pub value: AttributeValue,
+pub struct AttributeValueForNode<'a> {
+    pub name: Cow<'a, str>,
+    pub node_name: Cow<'a, str>,
+    pub value: AttributeValue<'a>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct AttributeValueForMessage {
-    pub name: String,
+pub struct AttributeValueForMessage<'a> {
+    pub name: Cow<'a, str>,
     pub message_id: MessageId,
-    pub value: AttributeValue,
+    pub value: AttributeValue<'a>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct AttributeValueForSignal {
-    pub name: String,
+pub struct AttributeValueForSignal<'a> {
+    pub name: Cow<'a, str>,
     pub message_id: MessageId,
-    pub signal_name: String,
-    pub value: AttributeValue,
+    pub signal_name: Cow<'a, str>,
+    pub value: AttributeValue<'a>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct AttributeValueForEnvVariable {
-    pub name: String,
-    pub variable_name: String,
-    pub value: AttributeValue,
+pub struct AttributeValueForEnvVariable<'a> {
+    pub name: Cow<'a, str>,
+    pub variable_name: Cow<'a, str>,
+    pub value: AttributeValue<'a>,
 }
 
-impl Dbc {
+impl AttributeValueForNode<'_> {
+    /// The node the value is assigned to
     #[must_use]
-    pub fn signal_by_name(&self, message_id: MessageId, signal_name: &str) -> Option<&Signal> {
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        ObjectKey::Node(&self.node_name)
+    }
+}
+
+impl AttributeValueForMessage<'_> {
+    /// The message the value is assigned to
+    #[must_use]
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        ObjectKey::Message(self.message_id)
+    }
+}
+
+impl AttributeValueForSignal<'_> {
+    /// The signal the value is assigned to
+    #[must_use]
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        ObjectKey::Signal(self.message_id, &self.signal_name)
+    }
+}
+
+impl AttributeValueForEnvVariable<'_> {
+    /// The environment variable the value is assigned to
+    #[must_use]
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        ObjectKey::EnvironmentVariable(&self.variable_name)
+    }
+}
+
+impl fmt::Display for AttributeValueForDatabase<'_> {
+    /// Write attribute value: `BA_ attribute_name value;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BA_ {} {};", Quoted(&self.name), self.value)
+    }
+}
+
+impl fmt::Display for AttributeValueForNode<'_> {
+    /// Write attribute value: `BA_ attribute_name BU_ node_name value;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BA_ {} BU_ {} {};",
+            Quoted(&self.name),
+            self.node_name,
+            self.value
+        )
+    }
+}
+
+impl fmt::Display for AttributeValueForMessage<'_> {
+    /// Write attribute value: `BA_ attribute_name BO_ message_id value;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BA_ {} BO_ {} {};",
+            Quoted(&self.name),
+            self.message_id,
+            self.value
+        )
+    }
+}
+
+impl fmt::Display for AttributeValueForSignal<'_> {
+    /// Write attribute value: `BA_ attribute_name SG_ message_id signal_name value;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BA_ {} SG_ {} {} {};",
+            Quoted(&self.name),
+            self.message_id,
+            self.signal_name,
+            self.value
+        )
+    }
+}
+
+impl fmt::Display for AttributeValueForEnvVariable<'_> {
+    /// Write attribute value: `BA_ attribute_name EV_ variable_name value;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BA_ {} EV_ {} {};",
+            Quoted(&self.name),
+            self.variable_name,
+            self.value
+        )
+    }
+}
+
+impl<'a> Dbc<'a> {
+    #[must_use]
+    pub fn signal_by_name(&self, message_id: MessageId, signal_name: &str) -> Option<&Signal<'_>> {
         let message = self
             .messages
             .iter()
@@ -150,7 +278,7 @@ impl Dbc {
                 ref comment,
             } => {
                 if *x_message_id == message_id {
-                    Some(comment.as_str())
+                    Some(comment.as_ref())
                 } else {
                     None
                 }
@@ -169,7 +297,7 @@ impl Dbc {
                 comment,
             } => {
                 if *x_message_id == message_id && x_signal_name == signal_name {
-                    Some(comment.as_str())
+                    Some(comment.as_ref())
                 } else {
                     None
                 }
@@ -184,7 +312,7 @@ impl Dbc {
         &self,
         message_id: MessageId,
         signal_name: &str,
-    ) -> Option<&[ValDescription]> {
+    ) -> Option<&[ValDescription<'_>]> {
         self.value_descriptions.iter().find_map(|x| match x {
             ValueDescription::Signal {
                 message_id: ref x_message_id,
@@ -222,9 +350,141 @@ impl Dbc {
         })
     }
 
+    /// Lookup the signal type (`SGTYPE_`) a signal refers to with `SIG_TYPE_REF_`
+    #[must_use]
+    pub fn signal_type_for_signal(
+        &self,
+        message_id: MessageId,
+        signal_name: &str,
+    ) -> Option<&SignalType<'_>> {
+        let reference = self
+            .signal_type_refs
+            .iter()
+            .find(|r| r.message_id == message_id && r.signal_name == signal_name)?;
+        self.signal_types
+            .iter()
+            .find(|t| t.name == reference.signal_type_name)
+    }
+
+    /// Apply the signal types referenced by `SIG_TYPE_REF_` to their signals.
+    ///
+    /// Each referencing signal takes size, byte order, value type, factor, offset, limits and unit
+    /// from its [`SignalType`]. If the type names a value table and the signal has no value
+    /// descriptions (`VAL_`) of its own, the descriptions of the table are added for the signal.
+    /// References to unknown signals or signal types are ignored, see [`Dbc::validate`].
+    pub fn resolve_signal_types(&mut self) {
+        for reference in &self.signal_type_refs {
+            let Some(signal_type) = self
+                .signal_types
+                .iter()
+                .find(|t| t.name == reference.signal_type_name)
+            else {
+                continue;
+            };
+            let Some(signal) = self
+                .messages
+                .iter_mut()
+                .filter(|m| m.id == reference.message_id)
+                .flat_map(|m| m.signals.iter_mut())
+                .find(|s| s.name == reference.signal_name)
+            else {
+                continue;
+            };
+            signal_type.apply_to(signal);
+
+            let has_descriptions = self.value_descriptions.iter().any(|v| {
+                matches!(v, ValueDescription::Signal { message_id, name, .. }
+                    if *message_id == reference.message_id && *name == reference.signal_name)
+            });
+            if has_descriptions {
+                continue;
+            }
+            if let Some(table) = self
+                .value_tables
+                .iter()
+                .find(|t| t.name == signal_type.value_table)
+            {
+                self.value_descriptions.push(ValueDescription::Signal {
+                    message_id: reference.message_id,
+                    name: reference.signal_name.clone(),
+                    value_descriptions: table.descriptions.clone(),
+                });
+            }
+        }
+    }
+
+    /// Lookup the extended value type of a signal, defaulting to an integer if no `SIG_VALTYPE_` is defined
+    fn signal_value_type(
+        &self,
+        message_id: MessageId,
+        signal_name: &str,
+    ) -> SignalExtendedValueType {
+        self.extended_value_type_for_signal(message_id, signal_name)
+            .copied()
+            .unwrap_or(SignalExtendedValueType::SignedOrUnsignedInteger)
+    }
+
+    /// Decode all signals of a message from a CAN frame payload.
+    ///
+    /// Signals declared as IEEE floating point numbers by `SIG_VALTYPE_` are read as `f32`/`f64`,
+    /// all others as integers. Multiplexed signals are decoded regardless of the multiplexor value.
+    pub fn decode_message(
+        &self,
+        message_id: MessageId,
+        data: &[u8],
+    ) -> CodecResult<Vec<DecodedSignal<'_>>> {
+        self.message_by_id(message_id)?.decode_with(data, |signal| {
+            self.signal_value_type(message_id, &signal.name)
+        })
+    }
+
+    /// Encode physical signal values, given as `(signal_name, value)` pairs, into a payload of the message.
+    ///
+    /// Signals declared as IEEE floating point numbers by `SIG_VALTYPE_` are written as `f32`/`f64`,
+    /// all others as integers. Bits of signals without a value are left as zero.
+    pub fn encode_message<I, S>(&self, message_id: MessageId, values: I) -> CodecResult<Vec<u8>>
+    where
+        I: IntoIterator<Item = (S, f64)>,
+        S: AsRef<str>,
+    {
+        self.message_by_id(message_id)?
+            .encode_with(values, |signal| {
+                self.signal_value_type(message_id, &signal.name)
+            })
+    }
+
+    /// Decode the active signals of a multiplexed message from a CAN frame payload.
+    ///
+    /// Multiplexed signals are only decoded if their multiplexor is active and has a matching value.
+    /// Extended multiplexing (`SG_MUL_VAL_`) is supported, including value ranges and nested
+    /// multiplexors (`mxM`). Signals without an `SG_MUL_VAL_` entry are selected by the single
+    /// multiplexor switch (`M`) of the message. The multiplexor values are reported in the branch of the result.
+    pub fn decode_multiplexed(
+        &self,
+        message_id: MessageId,
+        data: &[u8],
+    ) -> CodecResult<DecodedMessage<'_>> {
+        self.message_by_id(message_id)?.decode_multiplexed_with(
+            data,
+            &self.extended_multiplex,
+            |signal| self.signal_value_type(message_id, &signal.name),
+        )
+    }
+
+    fn message_by_id(&self, message_id: MessageId) -> CodecResult<&Message<'_>> {
+        self.messages
+            .iter()
+            .find(|message| message.id == message_id)
+            .ok_or(CodecError::UnknownMessage(message_id))
+    }
+
     /// Lookup the message multiplexer switch signal for a given message
     /// This does not work for extended multiplexed messages, if multiple multiplexors are defined for a message an Error is returned.
-    pub fn message_multiplexor_switch(&self, message_id: MessageId) -> DbcResult<Option<&Signal>> {
+    /// Use [`Dbc::decode_multiplexed`] to decode extended multiplexed messages.
+    pub fn message_multiplexor_switch(
+        &self,
+        message_id: MessageId,
+    ) -> DbcResult<Option<&Signal<'_>>> {
         let message = self
             .messages
             .iter()
@@ -250,7 +510,11 @@ impl Dbc {
 
     /// Lookup an assigned message-level (`BO_`) attribute value.
     #[must_use]
-    pub fn message_attribute(&self, message_id: MessageId, name: &str) -> Option<&AttributeValue> {
+    pub fn message_attribute(
+        &self,
+        message_id: MessageId,
+        name: &str,
+    ) -> Option<&AttributeValue<'_>> {
         self.attribute_values_message
             .iter()
             .find(|a| a.message_id == message_id && a.name == name)
@@ -264,49 +528,226 @@ impl Dbc {
         message_id: MessageId,
         signal_name: &str,
         name: &str,
-    ) -> Option<&AttributeValue> {
+    ) -> Option<&AttributeValue<'_>> {
         self.attribute_values_signal
             .iter()
             .find(|a| a.message_id == message_id && a.signal_name == signal_name && a.name == name)
             .map(|a| &a.value)
     }
 
-    /// Lookup an attribute's default value (`BA_DEF_DEF_`).
-    #[must_use]
-    pub fn attribute_default(&self, name: &str) -> Option<&AttributeValue> {
-        self.attribute_defaults
-            .iter()
-            .find(|d| d.name == name)
-            .map(|d| &d.value)
-    }
-
     /// Lookup a message-level attribute value.
-    /// Falls back to the default `BA_DEF_DEF_` if a value is not defined.
+    /// Falls back to the default `BA_DEF_DEF_` if a value is not defined, see [`Dbc::resolved_attribute`].
     #[must_use]
     pub fn resolved_message_attribute(
         &self,
         message_id: MessageId,
         name: &str,
-    ) -> Option<&AttributeValue> {
-        self.message_attribute(message_id, name)
-            .or_else(|| self.attribute_default(name))
+    ) -> Option<&AttributeValue<'_>> {
+        self.resolved_attribute(&ObjectRef::Message(message_id), name)
     }
 
     /// Lookup a signal-level attribute value.
-    /// Falls back to the default `BA_DEF_DEF_` if a value is not defined.
+    /// Falls back to the default `BA_DEF_DEF_` if a value is not defined, see [`Dbc::resolved_attribute`].
     #[must_use]
     pub fn resolved_signal_attribute(
         &self,
         message_id: MessageId,
         signal_name: &str,
         name: &str,
-    ) -> Option<&AttributeValue> {
-        self.signal_attribute(message_id, signal_name, name)
-            .or_else(|| self.attribute_default(name))
+    ) -> Option<&AttributeValue<'_>> {
+        let object = ObjectRef::Signal {
+            message_id,
+            signal_name: signal_name.to_string(),
+        };
+        self.resolved_attribute(&object, name)
+    }
+
+    /// Lookup an assigned node-to-signal relation (`BU_SG_REL_`) attribute value.
+    #[must_use]
+    pub fn node_signal_attribute(
+        &self,
+        node_name: &str,
+        message_id: MessageId,
+        signal_name: &str,
+        name: &str,
+    ) -> Option<&AttributeValue<'_>> {
+        self.find_relation_attribute(name, |details| match details {
+            AttributeValueForRelationType::NodeToSignal {
+                node_name: n,
+                message_id: m,
+                signal_name: s,
+                value,
+            } if n == node_name && *m == message_id && s == signal_name => Some(value),
+            _ => None,
+        })
+    }
+
+    /// Lookup an assigned node-to-message relation (`BU_BO_REL_`) attribute value.
+    #[must_use]
+    pub fn node_message_attribute(
+        &self,
+        node_name: &str,
+        message_id: MessageId,
+        name: &str,
+    ) -> Option<&AttributeValue<'_>> {
+        self.find_relation_attribute(name, |details| match details {
+            AttributeValueForRelationType::NodeToMessage {
+                node_name: n,
+                message_id: m,
+                value,
+            } if n == node_name && *m == message_id => Some(value),
+            _ => None,
+        })
+    }
+
+    /// Lookup an assigned node-to-environment-variable relation (`BU_EV_REL_`) attribute value.
+    #[must_use]
+    pub fn node_environment_variable_attribute(
+        &self,
+        node_name: &str,
+        variable_name: &str,
+        name: &str,
+    ) -> Option<&AttributeValue<'_>> {
+        self.find_relation_attribute(name, |details| match details {
+            AttributeValueForRelationType::NodeToEnvironmentVariable {
+                node_name: n,
+                variable_name: v,
+                value,
+            } if n == node_name && v == variable_name => Some(value),
+            _ => None,
+        })
+    }
+
+    /// Lookup a node-to-signal relation attribute value.
+    /// Falls back to the default `BA_DEF_DEF_REL_` if a value is not defined, see [`Dbc::resolved_attribute`].
+    #[must_use]
+    pub fn resolved_node_signal_attribute(
+        &self,
+        node_name: &str,
+        message_id: MessageId,
+        signal_name: &str,
+        name: &str,
+    ) -> Option<&AttributeValue<'_>> {
+        let object = ObjectRef::NodeToSignal {
+            node_name: node_name.to_string(),
+            message_id,
+            signal_name: signal_name.to_string(),
+        };
+        self.resolved_attribute(&object, name)
+    }
+
+    /// Lookup a node-to-message relation attribute value.
+    /// Falls back to the default `BA_DEF_DEF_REL_` if a value is not defined, see [`Dbc::resolved_attribute`].
+    #[must_use]
+    pub fn resolved_node_message_attribute(
+        &self,
+        node_name: &str,
+        message_id: MessageId,
+        name: &str,
+    ) -> Option<&AttributeValue<'_>> {
+        let object = ObjectRef::NodeToMessage {
+            node_name: node_name.to_string(),
+            message_id,
+        };
+        self.resolved_attribute(&object, name)
+    }
+
+    /// Lookup a node-to-environment-variable relation attribute value.
+    /// Falls back to the default `BA_DEF_DEF_REL_` if a value is not defined, see [`Dbc::resolved_attribute`].
+    #[must_use]
+    pub fn resolved_node_environment_variable_attribute(
+        &self,
+        node_name: &str,
+        variable_name: &str,
+        name: &str,
+    ) -> Option<&AttributeValue<'_>> {
+        let object = ObjectRef::NodeToEnvironmentVariable {
+            node_name: node_name.to_string(),
+            variable_name: variable_name.to_string(),
+        };
+        self.resolved_attribute(&object, name)
+    }
+
+    /// Find the relation attribute value (`BA_REL_`) of the attribute `name` selected by `select`
+    fn find_relation_attribute<'b>(
+        &'b self,
+        name: &str,
+        select: impl Fn(&'b AttributeValueForRelationType<'a>) -> Option<&'b AttributeValue<'a>>,
+    ) -> Option<&'b AttributeValue<'a>> {
+        self.relation_attribute_values
+            .iter()
+            .filter(|a| a.name == name)
+            .find_map(|a| select(&a.details))
+    }
+}
+
+impl fmt::Display for Dbc<'_> {
+    /// Write the database as DBC text, with all sections in the order defined by the DBC specification.
+    /// Parsing the written text results in an equal `Dbc`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.version)?;
+        writeln!(f)?;
+
+        writeln!(f, "NS_ :")?;
+        for symbol in &self.new_symbols {
+            writeln!(f, "\t{symbol}")?;
+        }
+        writeln!(f)?;
+
+        if let Some(bit_timing) = &self.bit_timing {
+            f.write_str("BS_:")?;
+            for timing in bit_timing {
+                write!(f, " {timing}")?;
+            }
+            writeln!(f)?;
+            writeln!(f)?;
+        }
+
+        f.write_str("BU_:")?;
+        for node in &self.nodes {
+            write!(f, " {node}")?;
+        }
+        writeln!(f)?;
+        writeln!(f)?;
+
+        write_section(f, &self.value_tables)?;
+        for message in &self.messages {
+            writeln!(f, "{message}")?;
+            writeln!(f)?;
+        }
+        write_section(f, &self.message_transmitters)?;
+        write_section(f, &self.environment_variables)?;
+        write_section(f, &self.environment_variable_data)?;
+        write_section(f, &self.signal_types)?;
+        write_section(f, &self.comments)?;
+        write_section(f, &self.attribute_definitions)?;
+        for definition in &self.relation_attribute_definitions {
+            writeln!(f, "{definition}")?;
+        }
+        write_section(f, &self.attribute_defaults)?;
+        for default in &self.relation_attribute_defaults {
+            default.write(f, true)?;
+            writeln!(f)?;
+        }
+        write_section(f, &self.attribute_values_database)?;
+        write_section(f, &self.attribute_values_node)?;
+        write_section(f, &self.attribute_values_message)?;
+        write_section(f, &self.attribute_values_signal)?;
+        write_section(f, &self.attribute_values_env)?;
+        write_section(f, &self.relation_attribute_values)?;
+        write_section(f, &self.value_descriptions)?;
+        write_section(f, &self.category_definitions)?;
+        write_section(f, &self.categories)?;
+        write_section(f, &self.filter)?;
+        write_section(f, &self.signal_type_refs)?;
+        write_section(f, &self.signal_groups)?;
+        write_section(f, &self.signal_extended_value_type_list)?;
+        write_section(f, &self.extended_multiplex)
     }
 }
 
-impl<'a> TryFrom<&'a str> for Dbc {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<&'a str> for Dbc<'a> {
     type Error = DbcError;
 
     fn try_from(dbc_in: &'a str) -> Result<Self, Self::Error> {
@@ -314,155 +755,203 @@ impl<'a> TryFrom<&'a str> for Dbc {
     }
 }
 
-#[expect(clippy::too_many_lines)] // FIXME: refactor
-pub(crate) fn dbc(buffer: &str) -> DbcResult<Dbc> {
-    let mut version = Version::default();
-    let mut new_symbols = vec![];
-    let mut bit_timing = None;
-    let mut nodes = vec![];
-    let mut value_tables = vec![];
-    let mut messages: Vec<Message> = vec![];
-    let mut message_transmitters = vec![];
-    let mut environment_variables = vec![];
-    let mut environment_variable_data = vec![];
-    let mut comments = vec![];
-    let mut attribute_definitions = vec![];
-    let mut relation_attribute_definitions = vec![];
-    let mut attribute_defaults = vec![];
-    let mut relation_attribute_defaults = vec![];
-    let mut attribute_values_database = vec![];
-    let mut attribute_values_node = vec![];
-    let mut attribute_values_message = vec![];
-    let mut attribute_values_signal = vec![];
-    let mut attribute_values_env = vec![];
-    let mut relation_attribute_values = vec![];
-    let mut value_descriptions = vec![];
-    let mut signal_groups = vec![];
-    let mut signal_extended_value_type_list = vec![];
-    let mut extended_multiplex = vec![];
-
-    for pair in DbcParser::parse(Rule::file, buffer)? {
-        if !matches!(pair.as_rule(), Rule::file) {
-            return Err(DbcError::ExpectedRule(Rule::file, pair.as_rule()));
+#[cfg(feature = "std")]
+pub(crate) fn dbc(buffer: &str) -> DbcResult<Dbc<'_>> {
+    Ok(Dbc::parse_with_options(buffer, &ParseOptions::default())?.0)
+}
+
+/// Borrow `s` from `buffer` instead of `text`, a copy of `buffer` with the same byte offsets
+/// where only unrelated statements were replaced by whitespace
+#[cfg(feature = "std")]
+fn rebase<'b>(buffer: &'b str, text: &str, s: Cow<'_, str>) -> Cow<'b, str> {
+    if let Cow::Borrowed(s) = s {
+        let original = (s.as_ptr() as usize)
+            .checked_sub(text.as_ptr() as usize)
+            .and_then(|start| buffer.get(start..start.checked_add(s.len())?));
+        if let Some(original) = original.filter(|original| *original == s) {
+            return Cow::Borrowed(original);
         }
-        for pairs in pair.into_inner() {
-            match pairs.as_rule() {
-                Rule::version => version = pairs.try_into()?,
-                Rule::new_symbols => {
-                    let symbols: Vec<Symbol> = collect_all::<Symbol>(&mut pairs.into_inner())?;
-                    new_symbols.extend(symbols);
+    }
+    Cow::Owned(s.into_owned())
+}
+
+#[cfg(feature = "std")]
+impl<'a> Dbc<'a> {
+    /// Parse a DBC file, returning the database and a warning for every statement that was skipped.
+    ///
+    /// By default parsing fails on the first error, in lenient mode (see [`ParseOptions::lenient`])
+    /// statements that can't be parsed are skipped instead.
+    ///
+    /// Strings of the database borrow from `buffer`, only strings containing escape sequences are copied.
+    /// This also holds if the input has to be rewritten before parsing, e.g. to skip invalid lines
+    /// or to remove statements the grammar doesn't cover.
+    pub fn parse_with_options(
+        buffer: &'a str,
+        options: &ParseOptions,
+    ) -> DbcResult<(Self, Vec<ParseWarning>)> {
+        let mut warnings = Vec::new();
+        // statements not covered by the grammar are parsed separately
+        let (mut input, extracted) = extract_statements(buffer);
+        let mut statements = Vec::new();
+        for (location, statement) in extracted {
+            match statement {
+                Ok(statement) => statements.push(statement),
+                Err(reason) if options.is_lenient() => {
+                    warnings.push(ParseWarning { location, reason });
                 }
-                Rule::bit_timing => {
-                    let _inner_pairs = pairs.into_inner();
-                    // For now, just return empty vec since bit-timing parsing is not implemented
-                    bit_timing = Some(vec![]);
+                Err(error) => return Err(error),
+            }
+        }
+        loop {
+            let parsed = match &input {
+                Cow::Borrowed(text) => DbcParser::parse(Rule::file, text)
+                    .map(|pairs| Self::from_pairs(pairs, options, &mut warnings)),
+                Cow::Owned(text) => DbcParser::parse(Rule::file, text).map(|pairs| {
+                    Dbc::from_pairs(pairs, options, &mut warnings)
+                        .map(|dbc| dbc.map_strings(&mut |s| rebase(buffer, text, s)))
+                }),
+            };
+            match parsed {
+                Ok(dbc) => {
+                    let mut dbc = dbc?;
+                    for statement in statements {
+                        statement.apply(&mut dbc);
+                    }
+                    warnings.sort_by_key(|w| w.location.line);
+                    return Ok((dbc, warnings));
                 }
-                Rule::nodes => nodes = collect_all::<Node>(&mut pairs.into_inner())?,
-                Rule::message => messages.push(pairs.try_into()?),
-                Rule::comment => comments.push(pairs.try_into()?),
-                Rule::attr_def => attribute_definitions.push(pairs.try_into()?),
-                Rule::ba_def_rel => relation_attribute_definitions.push(pairs.try_into()?),
-                Rule::ba_rel => relation_attribute_values.push(pairs.try_into()?),
-                Rule::attr_value => {
-                    let attr_value: AttributeValueForObject = pairs.try_into()?;
-                    match attr_value.value {
-                        AttributeValueForObjectType::Raw(attribute_value) => {
-                            attribute_values_database.push(AttributeValueForDatabase {
-                                name: attr_value.name,
-                                value: attribute_value,
-                            });
-                        }
-                        AttributeValueForObjectType::NetworkNode(node_name, attribute_value) => {
-                            attribute_values_node.push(AttributeValueForNode {
+                Err(error) if options.is_lenient() => {
+                    let Some((location, skipped)) = skip_error_line(&input, &error) else {
+                        return Err(error.into());
+                    };
+                    warnings.push(ParseWarning {
+                        location,
+                        reason: error.into(),
+                    });
+                    input = Cow::Owned(skipped);
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
+
+    fn from_pairs(
+        pairs: Pairs<'a, Rule>,
+        options: &ParseOptions,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> DbcResult<Self> {
+        let mut dbc = Self::default();
+        for pair in pairs {
+            if !matches!(pair.as_rule(), Rule::file) {
+                return Err(DbcError::ExpectedRule(Rule::file, pair.as_rule()).at(&pair));
+            }
+            for statement in pair.into_inner() {
+                let location = statement.clone();
+                if let Err(error) = dbc.parse_statement(statement) {
+                    let error = error.at(&location);
+                    if !options.is_lenient() {
+                        return Err(error);
+                    }
+                    warnings.push(ParseWarning {
+                        location: SourceLocation::of(&location),
+                        reason: error,
+                    });
+                }
+            }
+        }
+        Ok(dbc)
+    }
+
+    /// Parse a single top-level statement and add it to the database
+    fn parse_statement(&mut self, pairs: Pair<'a, Rule>) -> DbcResult<()> {
+        match pairs.as_rule() {
+            Rule::version => self.version = pairs.try_into()?,
+            Rule::new_symbols => {
+                let symbols: Vec<Symbol> = collect_all::<Symbol>(&mut pairs.into_inner())?;
+                self.new_symbols.extend(symbols);
+            }
+            Rule::bit_timing => {
+                // an empty `BS_:` section is common, the values are optional
+                self.bit_timing = Some(if pairs.clone().into_inner().next().is_some() {
+                    vec![pairs.try_into()?]
+                } else {
+                    vec![]
+                });
+            }
+            Rule::nodes => self.nodes = collect_all::<Node>(&mut pairs.into_inner())?,
+            Rule::message => self.messages.push(pairs.try_into()?),
+            Rule::comment => self.comments.push(pairs.try_into()?),
+            Rule::attr_def => self.attribute_definitions.push(pairs.try_into()?),
+            Rule::ba_def_rel => self.relation_attribute_definitions.push(pairs.try_into()?),
+            Rule::ba_rel => self.relation_attribute_values.push(pairs.try_into()?),
+            Rule::attr_value => {
+                let attr_value: AttributeValueForObject = pairs.try_into()?;
+                match attr_value.value {
+                    AttributeValueForObjectType::Raw(attribute_value) => {
+                        self.attribute_values_database
+                            .push(AttributeValueForDatabase {
                                 name: attr_value.name,
-                                node_name,
                                 value: attribute_value,
                             });
-                        }
-                        AttributeValueForObjectType::MessageDefinition(
-                            message_id,
-                            attribute_value,
-                        ) => {
-                            if let Some(value) = attribute_value {
-                                attribute_values_message.push(AttributeValueForMessage {
+                    }
+                    AttributeValueForObjectType::NetworkNode(node_name, attribute_value) => {
+                        self.attribute_values_node.push(AttributeValueForNode {
+                            name: attr_value.name,
+                            node_name,
+                            value: attribute_value,
+                        });
+                    }
+                    AttributeValueForObjectType::MessageDefinition(message_id, attribute_value) => {
+                        if let Some(value) = attribute_value {
+                            self.attribute_values_message
+                                .push(AttributeValueForMessage {
                                     name: attr_value.name,
                                     message_id,
                                     value,
                                 });
-                            }
                         }
-                        AttributeValueForObjectType::Signal(
+                    }
+                    AttributeValueForObjectType::Signal(
+                        message_id,
+                        signal_name,
+                        attribute_value,
+                    ) => {
+                        self.attribute_values_signal.push(AttributeValueForSignal {
+                            name: attr_value.name,
                             message_id,
                             signal_name,
-                            attribute_value,
-                        ) => {
-                            attribute_values_signal.push(AttributeValueForSignal {
-                                name: attr_value.name,
-                                message_id,
-                                signal_name,
-                                value: attribute_value,
-                            });
-                        }
-                        AttributeValueForObjectType::EnvVariable(
-                            variable_name,
-                            attribute_value,
-                        ) => {
-                            attribute_values_env.push(AttributeValueForEnvVariable {
+                            value: attribute_value,
+                        });
+                    }
+                    AttributeValueForObjectType::EnvVariable(variable_name, attribute_value) => {
+                        self.attribute_values_env
+                            .push(AttributeValueForEnvVariable {
                                 name: attr_value.name,
                                 variable_name,
                                 value: attribute_value,
                             });
-                        }
                     }
                 }
-                Rule::value_table => value_tables.push(pairs.try_into()?),
-                Rule::value_table_def => value_descriptions.push(pairs.try_into()?),
-                Rule::signal_group => signal_groups.push(pairs.try_into()?),
-                Rule::signal_value_type => {
-                    signal_extended_value_type_list.push(pairs.try_into()?);
-                }
-                Rule::message_transmitter => message_transmitters.push(pairs.try_into()?),
-                Rule::ba_def_def => attribute_defaults.push(pairs.try_into()?),
-                Rule::ba_def_def_rel => relation_attribute_defaults.push(pairs.try_into()?),
-                Rule::sg_mul_val => extended_multiplex.push(pairs.try_into()?),
-                Rule::environment_variable => {
-                    environment_variables.push(pairs.try_into()?);
-                }
-                Rule::env_var_data => environment_variable_data.push(pairs.try_into()?),
-                Rule::EOI => {
-                    // ignore
-                }
-                other => Err(DbcError::UnknownRule(other))?,
             }
+            Rule::value_table => self.value_tables.push(pairs.try_into()?),
+            Rule::value_table_def => self.value_descriptions.push(pairs.try_into()?),
+            Rule::signal_group => self.signal_groups.push(pairs.try_into()?),
+            Rule::signal_value_type => {
+                self.signal_extended_value_type_list.push(pairs.try_into()?);
+            }
+            Rule::message_transmitter => self.message_transmitters.push(pairs.try_into()?),
+            Rule::ba_def_def => self.attribute_defaults.push(pairs.try_into()?),
+            Rule::ba_def_def_rel => self.relation_attribute_defaults.push(pairs.try_into()?),
+            Rule::sg_mul_val => self.extended_multiplex.push(pairs.try_into()?),
+            Rule::environment_variable => {
+                self.environment_variables.push(pairs.try_into()?);
+            }
+            Rule::env_var_data => self.environment_variable_data.push(pairs.try_into()?),
+            Rule::EOI => {
+                // ignore
+            }
+            other => Err(DbcError::UnknownRule(other))?,
         }
+        Ok(())
     }
-
-    Ok(Dbc {
-        version,
-        new_symbols,
-        bit_timing,
-        nodes,
-        value_tables,
-        messages,
-        message_transmitters,
-        environment_variables,
-        environment_variable_data,
-        signal_types: vec![], // TODO
-        comments,
-        attribute_definitions,
-        relation_attribute_definitions,
-        attribute_defaults,
-        relation_attribute_defaults,
-        relation_attribute_values,
-        attribute_values_database,
-        attribute_values_node,
-        attribute_values_message,
-        attribute_values_signal,
-        attribute_values_env,
-        value_descriptions,
-        signal_type_refs: vec![], // TODO
-        signal_groups,
-        signal_extended_value_type_list,
-        extended_multiplex,
-    })
 }
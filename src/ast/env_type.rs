@@ -1,5 +1,9 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::Rule;
 
+#[cfg(feature = "std")]
 use crate::DbcError;
 
 /// `env_var_type = '0' | '1' | '2' ; (* 0=integer, 1=float, 2=string *)`
@@ -11,6 +15,7 @@ pub enum EnvType {
     String,
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<Rule> for EnvType {
     type Error = DbcError;
 
@@ -23,3 +28,13 @@ impl TryFrom<Rule> for EnvType {
         }
     }
 }
+
+impl fmt::Display for EnvType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Integer => "0",
+            Self::Float => "1",
+            Self::String => "2",
+        })
+    }
+}
@@ -1,31 +1,41 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
 use crate::ast::{AccessType, EnvType};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
 use crate::parser::{
     collect_node_names, expect_empty, inner_str, next, next_optional_rule, next_rule, parse_int,
-    parse_min_max_int, single_inner_str, validated_inner, DbcError,
+    parse_min_max_int, single_inner_str, validated_inner,
 };
+use crate::writer::{write_node_names, Quoted};
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct EnvironmentVariable {
-    pub name: String,
+pub struct EnvironmentVariable<'a> {
+    pub name: Cow<'a, str>,
     pub typ: EnvType,
     pub min: i64,
     pub max: i64,
-    pub unit: String,
+    pub unit: Cow<'a, str>,
     pub initial_value: i64,
     pub ev_id: i64,
     pub access_type: AccessType,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub access_nodes: Vec<String>,
+    pub access_nodes: Vec<Cow<'a, str>>,
 }
 
-impl TryFrom<Pair<'_, Rule>> for EnvironmentVariable {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for EnvironmentVariable<'a> {
     type Error = DbcError;
 
     /// Parse environment variable: `EV_ variable_name : type [min|max] "unit" access_type access_node node_name1 node_name2;`
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         // 1) Validate wrapper and get iterator
         let mut pairs = validated_inner(value, Rule::environment_variable)?;
 
@@ -42,7 +52,7 @@ impl TryFrom<Pair<'_, Rule>> for EnvironmentVariable {
         }
 
         // 5) Optional: unit
-        let mut unit = String::new();
+        let mut unit = Cow::Borrowed("");
         if let Some(unit_pair) = next_optional_rule(&mut pairs, Rule::unit) {
             unit = inner_str(unit_pair);
         }
@@ -81,7 +91,27 @@ impl TryFrom<Pair<'_, Rule>> for EnvironmentVariable {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for EnvironmentVariable<'_> {
+    /// Write environment variable: `EV_ variable_name : type [min|max] "unit" initial_value ev_id access_type access_node1,access_node2;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "EV_ {}: {} [{}|{}] {} {} {} {} ",
+            self.name,
+            self.typ,
+            self.min,
+            self.max,
+            Quoted(&self.unit),
+            self.initial_value,
+            self.ev_id,
+            self.access_type,
+        )?;
+        write_node_names(f, &self.access_nodes, ",")?;
+        f.write_str(";")
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -96,15 +126,15 @@ mod tests {
 EV_ IUV: 0 [-22|20] "mm" 3 7 DUMMY_NODE_VECTOR0 VECTOR_XXX;
 "#;
         let exp = EnvironmentVariable {
-            name: "IUV".to_string(),
+            name: "IUV".into(),
             typ: EnvType::Integer,
             min: -22,
             max: 20,
-            unit: "mm".to_string(),
+            unit: "mm".into(),
             initial_value: 3,
             ev_id: 7,
             access_type: AccessType::DummyNodeVector0,
-            access_nodes: vec!["VECTOR_XXX".to_string()],
+            access_nodes: vec!["VECTOR_XXX".into()],
         };
         let val = test_into::<EnvironmentVariable>(def.trim_start(), Rule::environment_variable);
         assert_eq!(val, exp);
@@ -1,18 +1,26 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::parser::{expect_empty, next_string, parse_next_uint, validated_inner, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{expect_empty, next_string, parse_next_uint, validated_inner};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct EnvironmentVariableData {
-    pub env_var_name: String,
+pub struct EnvironmentVariableData<'a> {
+    pub env_var_name: Cow<'a, str>,
     pub data_size: u64,
 }
 
-impl TryFrom<Pair<'_, Rule>> for EnvironmentVariableData {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for EnvironmentVariableData<'a> {
     type Error = DbcError;
 
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::env_var_data)?;
 
         let env_var_name = next_string(&mut pairs, Rule::env_var_name)?;
@@ -26,7 +34,14 @@ impl TryFrom<Pair<'_, Rule>> for EnvironmentVariableData {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for EnvironmentVariableData<'_> {
+    /// Write environment variable data: `ENVVAR_DATA_ env_var_name : data_size;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ENVVAR_DATA_ {}: {};", self.env_var_name, self.data_size)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -37,7 +52,7 @@ mod tests {
 ENVVAR_DATA_ SomeEnvVarData: 399;
 ";
         let exp = EnvironmentVariableData {
-            env_var_name: "SomeEnvVarData".to_string(),
+            env_var_name: "SomeEnvVarData".into(),
             data_size: 399,
         };
         let val = test_into::<EnvironmentVariableData>(def.trim_start(), Rule::env_var_data);
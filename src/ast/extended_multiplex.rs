@@ -1,23 +1,44 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::ast::{ExtendedMultiplexMapping, MessageId};
-use crate::parser::{collect_all, next_rule, next_string, validated_inner, DbcError};
+use crate::ast::{ExtendedMultiplexMapping, MessageId, ObjectKey, ObjectRef};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{collect_all, next_rule, next_string, validated_inner};
 
 /// Mapping between multiplexors and multiplexed signals
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct ExtendedMultiplex {
+pub struct ExtendedMultiplex<'a> {
     pub message_id: MessageId,
-    pub signal_name: String,
-    pub multiplexor_signal_name: String,
+    pub signal_name: Cow<'a, str>,
+    pub multiplexor_signal_name: Cow<'a, str>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
     pub mappings: Vec<ExtendedMultiplexMapping>,
 }
 
-impl TryFrom<Pair<'_, Rule>> for ExtendedMultiplex {
+impl ExtendedMultiplex<'_> {
+    /// The multiplexed signal
+    #[must_use]
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        ObjectKey::Signal(self.message_id, &self.signal_name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for ExtendedMultiplex<'a> {
     type Error = DbcError;
 
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::sg_mul_val)?;
 
         let message_id = next_rule(&mut pairs, Rule::message_id)?.try_into()?;
@@ -37,7 +58,25 @@ impl TryFrom<Pair<'_, Rule>> for ExtendedMultiplex {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for ExtendedMultiplex<'_> {
+    /// Write extended multiplexing: `SG_MUL_VAL_ message_id signal_name multiplexer_name min-max, min-max ... ;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SG_MUL_VAL_ {} {} {} ",
+            self.message_id, self.signal_name, self.multiplexor_signal_name
+        )?;
+        for (idx, mapping) in self.mappings.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{mapping}")?;
+        }
+        f.write_str(";")
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -50,8 +89,8 @@ SG_MUL_VAL_ 2147483650 muxed_A_1 MUX_A 1-1;
 ";
         let exp = ExtendedMultiplex {
             message_id: MessageId::Extended(2),
-            signal_name: "muxed_A_1".to_string(),
-            multiplexor_signal_name: "MUX_A".to_string(),
+            signal_name: "muxed_A_1".into(),
+            multiplexor_signal_name: "MUX_A".into(),
             mappings: vec![ExtendedMultiplexMapping {
                 min_value: 1,
                 max_value: 1,
@@ -69,8 +108,8 @@ SG_MUL_VAL_ 2147483650 muxed_A_1 MUX_A 1568-2568;
 ";
         let exp = ExtendedMultiplex {
             message_id: MessageId::Extended(2),
-            signal_name: "muxed_A_1".to_string(),
-            multiplexor_signal_name: "MUX_A".to_string(),
+            signal_name: "muxed_A_1".into(),
+            multiplexor_signal_name: "MUX_A".into(),
             mappings: vec![ExtendedMultiplexMapping {
                 min_value: 1568,
                 max_value: 2568,
@@ -88,8 +127,8 @@ SG_MUL_VAL_ 2147483650 muxed_B_5 MUX_B 5-5, 16-24;
 ";
         let exp = ExtendedMultiplex {
             message_id: MessageId::Extended(2),
-            signal_name: "muxed_B_5".to_string(),
-            multiplexor_signal_name: "MUX_B".to_string(),
+            signal_name: "muxed_B_5".into(),
+            multiplexor_signal_name: "MUX_B".into(),
             mappings: vec![
                 ExtendedMultiplexMapping {
                     min_value: 5,
@@ -1,6 +1,12 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::parser::{expect_empty, parse_next_uint, validated_inner, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{expect_empty, parse_next_uint, validated_inner};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -9,6 +15,7 @@ pub struct ExtendedMultiplexMapping {
     pub max_value: u64,
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<Pair<'_, Rule>> for ExtendedMultiplexMapping {
     type Error = DbcError;
 
@@ -22,3 +29,9 @@ impl TryFrom<Pair<'_, Rule>> for ExtendedMultiplexMapping {
         Ok(value)
     }
 }
+
+impl fmt::Display for ExtendedMultiplexMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.min_value, self.max_value)
+    }
+}
@@ -0,0 +1,18 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+/// Filter (`FILTER`), obsolete.
+/// The format of the statement is undocumented, its content is preserved verbatim.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Filter<'a> {
+    /// Text between the `FILTER` keyword and the terminating `;`
+    pub definition: Cow<'a, str>,
+}
+
+impl fmt::Display for Filter<'_> {
+    /// Write filter: `FILTER definition;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FILTER {};", self.definition)
+    }
+}
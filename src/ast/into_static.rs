@@ -0,0 +1,675 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::ast::{
+    AttrDefault, AttributeDefault, AttributeDefinition, AttributeValue, AttributeValueForDatabase,
+    AttributeValueForEnvVariable, AttributeValueForMessage, AttributeValueForNode,
+    AttributeValueForRelation, AttributeValueForRelationType, AttributeValueForSignal,
+    AttributeValueType, Category, CategoryDefinition, CategoryObject, Comment, Dbc,
+    EnvironmentVariable, EnvironmentVariableData, ExtendedMultiplex, Filter, Message,
+    MessageTransmitter, Node, Signal, SignalAttributeValue, SignalExtendedValueTypeList,
+    SignalGroups, SignalType, SignalTypeRef, Symbol, ValDescription, ValueDescription, ValueTable,
+    Version,
+};
+
+/// Conversion of a value borrowing from the parsed input into one that owns all its strings.
+///
+/// Parsing borrows strings from the input buffer. Use this to keep a database around
+/// after the buffer is dropped.
+pub trait IntoStatic {
+    /// The owned type, usually `Self` with a `'static` lifetime
+    type Static: 'static;
+
+    /// Convert into the owned type, copying borrowed strings
+    #[must_use]
+    fn into_static(self) -> Self::Static;
+}
+
+impl IntoStatic for Cow<'_, str> {
+    type Static = Cow<'static, str>;
+
+    fn into_static(self) -> Self::Static {
+        Cow::Owned(self.into_owned())
+    }
+}
+
+impl<T: IntoStatic> IntoStatic for Vec<T> {
+    type Static = Vec<T::Static>;
+
+    fn into_static(self) -> Self::Static {
+        self.into_iter().map(IntoStatic::into_static).collect()
+    }
+}
+
+impl<T: IntoStatic> IntoStatic for Option<T> {
+    type Static = Option<T::Static>;
+
+    fn into_static(self) -> Self::Static {
+        self.map(IntoStatic::into_static)
+    }
+}
+
+/// Maps every string of a value, e.g. to copy it or to borrow it from another buffer
+pub(crate) type StrMapper<'m, 'b> = dyn for<'s> FnMut(Cow<'s, str>) -> Cow<'b, str> + 'm;
+
+/// Conversion of a value into the same type with all strings replaced by a [`StrMapper`]
+pub(crate) trait MapStrings<'b> {
+    type Output;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output;
+}
+
+impl<'b> MapStrings<'b> for Cow<'_, str> {
+    type Output = Cow<'b, str>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        f(self)
+    }
+}
+
+impl<'b, T: MapStrings<'b>> MapStrings<'b> for Vec<T> {
+    type Output = Vec<T::Output>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        self.into_iter().map(|v| v.map_strings(f)).collect()
+    }
+}
+
+impl<'b, T: MapStrings<'b>> MapStrings<'b> for Option<T> {
+    type Output = Option<T::Output>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        self.map(|v| v.map_strings(f))
+    }
+}
+
+macro_rules! into_static {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl IntoStatic for $name<'_> {
+                type Static = $name<'static>;
+
+                fn into_static(self) -> Self::Static {
+                    self.map_strings(&mut |s| Cow::Owned(s.into_owned()))
+                }
+            }
+        )*
+    };
+}
+
+into_static!(
+    Dbc,
+    AttrDefault,
+    AttributeDefault,
+    AttributeDefinition,
+    AttributeValue,
+    AttributeValueType,
+    AttributeValueForRelation,
+    AttributeValueForRelationType,
+    AttributeValueForDatabase,
+    AttributeValueForNode,
+    AttributeValueForMessage,
+    AttributeValueForSignal,
+    AttributeValueForEnvVariable,
+    CategoryObject,
+    Category,
+    CategoryDefinition,
+    Comment,
+    EnvironmentVariable,
+    EnvironmentVariableData,
+    ExtendedMultiplex,
+    Filter,
+    Message,
+    MessageTransmitter,
+    Node,
+    Signal,
+    SignalAttributeValue,
+    SignalExtendedValueTypeList,
+    SignalGroups,
+    SignalType,
+    SignalTypeRef,
+    Symbol,
+    ValDescription,
+    ValueDescription,
+    ValueTable,
+    Version,
+);
+
+impl<'b> MapStrings<'b> for Dbc<'_> {
+    type Output = Dbc<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        Dbc {
+            version: self.version.map_strings(f),
+            new_symbols: self.new_symbols.map_strings(f),
+            bit_timing: self.bit_timing,
+            nodes: self.nodes.map_strings(f),
+            value_tables: self.value_tables.map_strings(f),
+            messages: self.messages.map_strings(f),
+            message_transmitters: self.message_transmitters.map_strings(f),
+            environment_variables: self.environment_variables.map_strings(f),
+            environment_variable_data: self.environment_variable_data.map_strings(f),
+            signal_types: self.signal_types.map_strings(f),
+            comments: self.comments.map_strings(f),
+            attribute_definitions: self.attribute_definitions.map_strings(f),
+            relation_attribute_definitions: self.relation_attribute_definitions.map_strings(f),
+            attribute_defaults: self.attribute_defaults.map_strings(f),
+            relation_attribute_defaults: self.relation_attribute_defaults.map_strings(f),
+            relation_attribute_values: self.relation_attribute_values.map_strings(f),
+            attribute_values_database: self.attribute_values_database.map_strings(f),
+            attribute_values_node: self.attribute_values_node.map_strings(f),
+            attribute_values_message: self.attribute_values_message.map_strings(f),
+            attribute_values_signal: self.attribute_values_signal.map_strings(f),
+            attribute_values_env: self.attribute_values_env.map_strings(f),
+            value_descriptions: self.value_descriptions.map_strings(f),
+            category_definitions: self.category_definitions.map_strings(f),
+            categories: self.categories.map_strings(f),
+            filter: self.filter.map_strings(f),
+            signal_type_refs: self.signal_type_refs.map_strings(f),
+            signal_groups: self.signal_groups.map_strings(f),
+            signal_extended_value_type_list: self.signal_extended_value_type_list.map_strings(f),
+            extended_multiplex: self.extended_multiplex.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttrDefault<'_> {
+    type Output = AttrDefault<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        AttrDefault {
+            name: self.name.map_strings(f),
+            value: self.value.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeDefault<'_> {
+    type Output = AttributeDefault<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        AttributeDefault {
+            name: self.name.map_strings(f),
+            value: self.value.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeDefinition<'_> {
+    type Output = AttributeDefinition<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        match self {
+            Self::Message(name, typ) => {
+                AttributeDefinition::Message(name.map_strings(f), typ.map_strings(f))
+            }
+            Self::Node(name, typ) => {
+                AttributeDefinition::Node(name.map_strings(f), typ.map_strings(f))
+            }
+            Self::Signal(name, typ) => {
+                AttributeDefinition::Signal(name.map_strings(f), typ.map_strings(f))
+            }
+            Self::EnvironmentVariable(name, typ) => {
+                AttributeDefinition::EnvironmentVariable(name.map_strings(f), typ.map_strings(f))
+            }
+            Self::Plain(name, typ) => {
+                AttributeDefinition::Plain(name.map_strings(f), typ.map_strings(f))
+            }
+            Self::NodeToSignal(name, typ) => {
+                AttributeDefinition::NodeToSignal(name.map_strings(f), typ.map_strings(f))
+            }
+            Self::NodeToMessage(name, typ) => {
+                AttributeDefinition::NodeToMessage(name.map_strings(f), typ.map_strings(f))
+            }
+            Self::NodeToEnvironmentVariable(name, typ) => {
+                AttributeDefinition::NodeToEnvironmentVariable(
+                    name.map_strings(f),
+                    typ.map_strings(f),
+                )
+            }
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeValue<'_> {
+    type Output = AttributeValue<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        match self {
+            Self::Uint(v) => AttributeValue::Uint(v),
+            Self::Int(v) => AttributeValue::Int(v),
+            Self::Double(v) => AttributeValue::Double(v),
+            Self::String(v) => AttributeValue::String(v.map_strings(f)),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeValueType<'_> {
+    type Output = AttributeValueType<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        match self {
+            Self::Int(min, max) => AttributeValueType::Int(min, max),
+            Self::Hex(min, max) => AttributeValueType::Hex(min, max),
+            Self::Float(min, max) => AttributeValueType::Float(min, max),
+            Self::String => AttributeValueType::String,
+            Self::Enum(values) => AttributeValueType::Enum(values.map_strings(f)),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeValueForRelation<'_> {
+    type Output = AttributeValueForRelation<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        AttributeValueForRelation {
+            name: self.name.map_strings(f),
+            details: self.details.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeValueForRelationType<'_> {
+    type Output = AttributeValueForRelationType<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        match self {
+            Self::NodeToSignal {
+                node_name,
+                message_id,
+                signal_name,
+                value,
+            } => AttributeValueForRelationType::NodeToSignal {
+                node_name: node_name.map_strings(f),
+                message_id,
+                signal_name: signal_name.map_strings(f),
+                value: value.map_strings(f),
+            },
+            Self::NodeToMessage {
+                node_name,
+                message_id,
+                value,
+            } => AttributeValueForRelationType::NodeToMessage {
+                node_name: node_name.map_strings(f),
+                message_id,
+                value: value.map_strings(f),
+            },
+            Self::NodeToEnvironmentVariable {
+                node_name,
+                variable_name,
+                value,
+            } => AttributeValueForRelationType::NodeToEnvironmentVariable {
+                node_name: node_name.map_strings(f),
+                variable_name: variable_name.map_strings(f),
+                value: value.map_strings(f),
+            },
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeValueForDatabase<'_> {
+    type Output = AttributeValueForDatabase<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        AttributeValueForDatabase {
+            name: self.name.map_strings(f),
+            value: self.value.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeValueForNode<'_> {
+    type Output = AttributeValueForNode<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        AttributeValueForNode {
+            name: self.name.map_strings(f),
+            node_name: self.node_name.map_strings(f),
+            value: self.value.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeValueForMessage<'_> {
+    type Output = AttributeValueForMessage<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        AttributeValueForMessage {
+            name: self.name.map_strings(f),
+            message_id: self.message_id,
+            value: self.value.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeValueForSignal<'_> {
+    type Output = AttributeValueForSignal<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        AttributeValueForSignal {
+            name: self.name.map_strings(f),
+            message_id: self.message_id,
+            signal_name: self.signal_name.map_strings(f),
+            value: self.value.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for AttributeValueForEnvVariable<'_> {
+    type Output = AttributeValueForEnvVariable<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        AttributeValueForEnvVariable {
+            name: self.name.map_strings(f),
+            variable_name: self.variable_name.map_strings(f),
+            value: self.value.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for CategoryObject<'_> {
+    type Output = CategoryObject<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        match self {
+            Self::Node(name) => CategoryObject::Node(name.map_strings(f)),
+            Self::Message(id) => CategoryObject::Message(id),
+            Self::EnvironmentVariable(name) => {
+                CategoryObject::EnvironmentVariable(name.map_strings(f))
+            }
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for Category<'_> {
+    type Output = Category<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        Category {
+            object: self.object.map_strings(f),
+            category_id: self.category_id,
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for CategoryDefinition<'_> {
+    type Output = CategoryDefinition<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        CategoryDefinition {
+            id: self.id,
+            name: self.name.map_strings(f),
+            value: self.value,
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for Comment<'_> {
+    type Output = Comment<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        match self {
+            Self::Node { name, comment } => Comment::Node {
+                name: name.map_strings(f),
+                comment: comment.map_strings(f),
+            },
+            Self::Message { id, comment } => Comment::Message {
+                id,
+                comment: comment.map_strings(f),
+            },
+            Self::Signal {
+                message_id,
+                name,
+                comment,
+            } => Comment::Signal {
+                message_id,
+                name: name.map_strings(f),
+                comment: comment.map_strings(f),
+            },
+            Self::EnvVar { name, comment } => Comment::EnvVar {
+                name: name.map_strings(f),
+                comment: comment.map_strings(f),
+            },
+            Self::Plain { comment } => Comment::Plain {
+                comment: comment.map_strings(f),
+            },
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for EnvironmentVariable<'_> {
+    type Output = EnvironmentVariable<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        EnvironmentVariable {
+            name: self.name.map_strings(f),
+            typ: self.typ,
+            min: self.min,
+            max: self.max,
+            unit: self.unit.map_strings(f),
+            initial_value: self.initial_value,
+            ev_id: self.ev_id,
+            access_type: self.access_type,
+            access_nodes: self.access_nodes.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for EnvironmentVariableData<'_> {
+    type Output = EnvironmentVariableData<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        EnvironmentVariableData {
+            env_var_name: self.env_var_name.map_strings(f),
+            data_size: self.data_size,
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for ExtendedMultiplex<'_> {
+    type Output = ExtendedMultiplex<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        ExtendedMultiplex {
+            message_id: self.message_id,
+            signal_name: self.signal_name.map_strings(f),
+            multiplexor_signal_name: self.multiplexor_signal_name.map_strings(f),
+            mappings: self.mappings,
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for Filter<'_> {
+    type Output = Filter<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        Filter {
+            definition: self.definition.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for Message<'_> {
+    type Output = Message<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        Message {
+            id: self.id,
+            name: self.name.map_strings(f),
+            size: self.size,
+            transmitter: self.transmitter.map_strings(f),
+            signals: self.signals.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for MessageTransmitter<'_> {
+    type Output = MessageTransmitter<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        MessageTransmitter {
+            message_id: self.message_id,
+            transmitter: self.transmitter.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for Node<'_> {
+    type Output = Node<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        Node(self.0.map_strings(f))
+    }
+}
+
+impl<'b> MapStrings<'b> for Signal<'_> {
+    type Output = Signal<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        Signal {
+            name: self.name.map_strings(f),
+            multiplexer_indicator: self.multiplexer_indicator,
+            start_bit: self.start_bit,
+            size: self.size,
+            byte_order: self.byte_order,
+            value_type: self.value_type,
+            factor: self.factor,
+            offset: self.offset,
+            min: self.min,
+            max: self.max,
+            unit: self.unit.map_strings(f),
+            receivers: self.receivers.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for SignalAttributeValue<'_> {
+    type Output = SignalAttributeValue<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        match self {
+            Self::Text(text) => SignalAttributeValue::Text(text.map_strings(f)),
+            Self::Int(value) => SignalAttributeValue::Int(value),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for SignalExtendedValueTypeList<'_> {
+    type Output = SignalExtendedValueTypeList<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        SignalExtendedValueTypeList {
+            message_id: self.message_id,
+            signal_name: self.signal_name.map_strings(f),
+            signal_extended_value_type: self.signal_extended_value_type,
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for SignalGroups<'_> {
+    type Output = SignalGroups<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        SignalGroups {
+            message_id: self.message_id,
+            name: self.name.map_strings(f),
+            repetitions: self.repetitions,
+            signal_names: self.signal_names.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for SignalType<'_> {
+    type Output = SignalType<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        SignalType {
+            name: self.name.map_strings(f),
+            signal_size: self.signal_size,
+            byte_order: self.byte_order,
+            value_type: self.value_type,
+            factor: self.factor,
+            offset: self.offset,
+            min: self.min,
+            max: self.max,
+            unit: self.unit.map_strings(f),
+            default_value: self.default_value,
+            value_table: self.value_table.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for SignalTypeRef<'_> {
+    type Output = SignalTypeRef<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        SignalTypeRef {
+            message_id: self.message_id,
+            signal_name: self.signal_name.map_strings(f),
+            signal_type_name: self.signal_type_name.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for Symbol<'_> {
+    type Output = Symbol<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        Symbol(self.0.map_strings(f))
+    }
+}
+
+impl<'b> MapStrings<'b> for ValDescription<'_> {
+    type Output = ValDescription<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        ValDescription {
+            id: self.id,
+            description: self.description.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for ValueDescription<'_> {
+    type Output = ValueDescription<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        match self {
+            Self::Signal {
+                message_id,
+                name,
+                value_descriptions,
+            } => ValueDescription::Signal {
+                message_id,
+                name: name.map_strings(f),
+                value_descriptions: value_descriptions.map_strings(f),
+            },
+            Self::EnvironmentVariable {
+                name,
+                value_descriptions,
+            } => ValueDescription::EnvironmentVariable {
+                name: name.map_strings(f),
+                value_descriptions: value_descriptions.map_strings(f),
+            },
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for ValueTable<'_> {
+    type Output = ValueTable<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        ValueTable {
+            name: self.name.map_strings(f),
+            descriptions: self.descriptions.map_strings(f),
+        }
+    }
+}
+
+impl<'b> MapStrings<'b> for Version<'_> {
+    type Output = Version<'b>;
+
+    fn map_strings(self, f: &mut StrMapper<'_, 'b>) -> Self::Output {
+        Version(self.0.map_strings(f))
+    }
+}
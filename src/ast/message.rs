@@ -1,31 +1,344 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::ast::{MessageId, Signal};
+use crate::ast::{
+    ExtendedMultiplex, MessageId, MultiplexIndicator, NumericValue, Signal, SignalExtendedValueType,
+};
+use crate::codec::{CodecError, CodecResult, DecodedMessage, DecodedSignal, MultiplexBranch};
+#[cfg(feature = "std")]
 use crate::parser::{
     collect_expected, next_rule, next_string, node_name_or_none, parse_next_uint, single_inner,
     validated_inner,
 };
+use crate::writer::VECTOR_PLACEHOLDER;
+#[cfg(feature = "std")]
 use crate::DbcError;
 
 /// CAN message (frame) details including signal details
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Message {
+pub struct Message<'a> {
     /// CAN id in header of CAN frame.
     /// Must be unique in DBC file.
     pub id: MessageId,
-    pub name: String,
+    pub name: Cow<'a, str>,
     pub size: u64,
-    pub transmitter: Option<String>,
+    pub transmitter: Option<Cow<'a, str>>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub signals: Vec<Signal>,
+    pub signals: Vec<Signal<'a>>,
+}
+
+/// Payload lengths of CAN FD frames for the DLC values 9 to 15
+const FD_LENGTHS: [u64; 7] = [12, 16, 20, 24, 32, 48, 64];
+
+/// Largest payload of any CAN frame, the CAN FD maximum
+const MAX_LENGTH: u64 = FD_LENGTHS[FD_LENGTHS.len() - 1];
+
+/// Payload length in bytes for a data length code (DLC).
+///
+/// DLC values up to 8 are the length itself, 9 to 15 map to the CAN FD lengths 12 to 64.
+#[must_use]
+pub fn dlc_to_length(dlc: u8) -> Option<u64> {
+    match dlc {
+        0..=8 => Some(u64::from(dlc)),
+        9..=15 => Some(FD_LENGTHS[usize::from(dlc - 9)]),
+        _ => None,
+    }
+}
+
+/// Data length code (DLC) for a payload length in bytes, `None` if no CAN or CAN FD frame has this length
+#[must_use]
+pub fn length_to_dlc(length: u64) -> Option<u8> {
+    match length {
+        0..=8 => u8::try_from(length).ok(),
+        _ => FD_LENGTHS
+            .iter()
+            .position(|&l| l == length)
+            .and_then(|idx| u8::try_from(idx + 9).ok()),
+    }
+}
+
+impl Message<'_> {
+    /// Data length code (DLC) of the message size, `None` if it is not a valid CAN or CAN FD length
+    #[must_use]
+    pub fn dlc(&self) -> Option<u8> {
+        length_to_dlc(self.size)
+    }
+
+    /// Decode all signals of this message from a CAN frame payload.
+    ///
+    /// Multiplexed signals are decoded regardless of the multiplexor value.
+    /// All signals are interpreted as integers, use [`Dbc::decode_message`](crate::Dbc::decode_message)
+    /// to take IEEE floating point signals (`SIG_VALTYPE_`) into account.
+    pub fn decode(&self, data: &[u8]) -> CodecResult<Vec<DecodedSignal<'_>>> {
+        self.decode_with(data, |_| SignalExtendedValueType::SignedOrUnsignedInteger)
+    }
+
+    /// Encode physical signal values, given as `(signal_name, value)` pairs, into a payload of `size` bytes.
+    ///
+    /// Bits of signals without a value are left as zero.
+    /// All signals are treated as integers, use [`Dbc::encode_message`](crate::Dbc::encode_message)
+    /// to take IEEE floating point signals (`SIG_VALTYPE_`) into account.
+    pub fn encode<I, S>(&self, values: I) -> CodecResult<Vec<u8>>
+    where
+        I: IntoIterator<Item = (S, f64)>,
+        S: AsRef<str>,
+    {
+        self.encode_with(values, |_| SignalExtendedValueType::SignedOrUnsignedInteger)
+    }
+
+    /// Decode all signals, looking up the extended value type of each signal with `value_type`
+    pub(crate) fn decode_with<F>(
+        &self,
+        data: &[u8],
+        value_type: F,
+    ) -> CodecResult<Vec<DecodedSignal<'_>>>
+    where
+        F: Fn(&Signal) -> SignalExtendedValueType,
+    {
+        self.signals
+            .iter()
+            .map(|signal| {
+                let raw = signal.decode_raw_as(data, value_type(signal))?;
+                Ok(DecodedSignal {
+                    signal,
+                    raw,
+                    physical: signal.to_physical(raw),
+                })
+            })
+            .collect()
+    }
+
+    /// Decode the active signals of a multiplexed message, see [`Dbc::decode_multiplexed`](crate::Dbc::decode_multiplexed)
+    pub(crate) fn decode_multiplexed_with<F>(
+        &self,
+        data: &[u8],
+        extended: &[ExtendedMultiplex],
+        value_type: F,
+    ) -> CodecResult<DecodedMessage<'_>>
+    where
+        F: Fn(&Signal) -> SignalExtendedValueType,
+    {
+        let selectors = self.multiplex_selectors(extended);
+        if let Some((signal, _)) = self
+            .signals
+            .iter()
+            .zip(&selectors)
+            .find(|(signal, selector)| selector.is_none() && signal.is_multiplexed())
+        {
+            return Err(self.unresolved_multiplexor(signal));
+        }
+        let mut states = vec![MuxState::Unresolved; self.signals.len()];
+        for idx in 0..self.signals.len() {
+            self.resolve_multiplexing(idx, data, &selectors, &mut states)?;
+        }
+
+        let mut signals = Vec::new();
+        let mut branch = Vec::new();
+        for (signal, state) in self.signals.iter().zip(states) {
+            if let MuxState::Active { depth, value } = state {
+                if let Some(value) = value {
+                    let multiplexor = signal;
+                    branch.push((depth, MultiplexBranch { multiplexor, value }));
+                }
+                let raw = signal.decode_raw_as(data, value_type(signal))?;
+                signals.push(DecodedSignal {
+                    signal,
+                    raw,
+                    physical: signal.to_physical(raw),
+                });
+            }
+        }
+        // stable sort, multiplexors of the same depth stay in definition order
+        branch.sort_by_key(|(depth, _)| *depth);
+
+        Ok(DecodedMessage {
+            message: self,
+            signals,
+            branch: branch.into_iter().map(|(_, branch)| branch).collect(),
+        })
+    }
+
+    /// Find the multiplexor of each multiplexed signal, and the multiplexor values selecting it.
+    ///
+    /// `SG_MUL_VAL_` entries take precedence, otherwise the message must have exactly one multiplexor switch.
+    /// Plain signals, multiplexor switches and multiplexed signals whose multiplexor can't be resolved map to `None`.
+    pub(crate) fn multiplex_selectors(
+        &self,
+        extended: &[ExtendedMultiplex],
+    ) -> Vec<Option<MuxSelector>> {
+        let mut switches = self
+            .signals
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.multiplexer_indicator == MultiplexIndicator::Multiplexor);
+        let simple_switch = match (switches.next(), switches.next()) {
+            (Some((idx, _)), None) => Some(idx),
+            _ => None,
+        };
+
+        self.signals
+            .iter()
+            .map(|signal| {
+                let value = match signal.multiplexer_indicator {
+                    MultiplexIndicator::MultiplexedSignal(v)
+                    | MultiplexIndicator::MultiplexorAndMultiplexedSignal(v) => v,
+                    MultiplexIndicator::Multiplexor | MultiplexIndicator::Plain => return None,
+                };
+                let mut entries = extended
+                    .iter()
+                    .filter(|e| e.message_id == self.id && e.signal_name == signal.name)
+                    .peekable();
+                if let Some(&first) = entries.peek() {
+                    let name = &first.multiplexor_signal_name;
+                    self.signals
+                        .iter()
+                        .position(|s| s.name == *name && s.is_multiplexor())
+                        .map(|multiplexor| MuxSelector {
+                            multiplexor,
+                            ranges: entries
+                                .filter(|e| e.multiplexor_signal_name == *name)
+                                .flat_map(|e| &e.mappings)
+                                .map(|m| (m.min_value, m.max_value))
+                                .collect(),
+                        })
+                } else {
+                    simple_switch.map(|multiplexor| MuxSelector {
+                        multiplexor,
+                        ranges: vec![(value, value)],
+                    })
+                }
+            })
+            .collect()
+    }
+
+    /// Determine whether a signal is active, resolving its chain of multiplexors first
+    fn resolve_multiplexing(
+        &self,
+        idx: usize,
+        data: &[u8],
+        selectors: &[Option<MuxSelector>],
+        states: &mut [MuxState],
+    ) -> CodecResult<MuxState> {
+        match states[idx] {
+            MuxState::Unresolved => {}
+            // a multiplexor depending on itself
+            MuxState::Visiting => return Err(self.unresolved_multiplexor(&self.signals[idx])),
+            state => return Ok(state),
+        }
+        states[idx] = MuxState::Visiting;
+
+        let depth = match &selectors[idx] {
+            None => Some(0),
+            Some(selector) => {
+                match self.resolve_multiplexing(selector.multiplexor, data, selectors, states)? {
+                    MuxState::Active {
+                        depth,
+                        value: Some(value),
+                    } if selector
+                        .ranges
+                        .iter()
+                        .any(|(min, max)| (min..=max).contains(&&value)) =>
+                    {
+                        Some(depth + 1)
+                    }
+                    _ => None,
+                }
+            }
+        };
+        let state = match depth {
+            None => MuxState::Inactive,
+            Some(depth) => {
+                let signal = &self.signals[idx];
+                let value = if signal.is_multiplexor() {
+                    // negative switch values never select a branch
+                    match signal.decode_raw(data)? {
+                        NumericValue::Uint(v) => Some(v),
+                        NumericValue::Int(v) => u64::try_from(v).ok(),
+                        NumericValue::Double(_) => None,
+                    }
+                } else {
+                    None
+                };
+                MuxState::Active { depth, value }
+            }
+        };
+        states[idx] = state;
+        Ok(state)
+    }
+
+    fn unresolved_multiplexor(&self, signal: &Signal) -> CodecError {
+        CodecError::UnresolvedMultiplexor {
+            message: self.name.to_string(),
+            signal: signal.name.to_string(),
+        }
+    }
+
+    /// Encode signal values, looking up the extended value type of each signal with `value_type`
+    pub(crate) fn encode_with<I, S, F>(&self, values: I, value_type: F) -> CodecResult<Vec<u8>>
+    where
+        I: IntoIterator<Item = (S, f64)>,
+        S: AsRef<str>,
+        F: Fn(&Signal) -> SignalExtendedValueType,
+    {
+        // reject malformed sizes before allocating the payload
+        let size = usize::try_from(self.size)
+            .ok()
+            .filter(|_| self.size <= MAX_LENGTH)
+            .ok_or_else(|| CodecError::UnsupportedMessageSize {
+                message: self.name.to_string(),
+                size: self.size,
+            })?;
+        let mut data = vec![0; size];
+        for (name, value) in values {
+            let name = name.as_ref();
+            let signal = self
+                .signals
+                .iter()
+                .find(|signal| signal.name == name)
+                .ok_or_else(|| CodecError::UnknownSignal {
+                    message: self.name.to_string(),
+                    signal: name.to_string(),
+                })?;
+            signal.encode_physical_as(&mut data, value, value_type(signal))?;
+        }
+        Ok(data)
+    }
 }
 
-impl TryFrom<Pair<'_, Rule>> for Message {
+/// The multiplexor a signal depends on, and the multiplexor values that make it active
+pub(crate) struct MuxSelector {
+    /// Index of the multiplexor in the message's signals
+    pub multiplexor: usize,
+    /// Inclusive ranges of multiplexor values
+    pub ranges: Vec<(u64, u64)>,
+}
+
+/// State of a signal while resolving the multiplexor tree
+#[derive(Copy, Clone)]
+enum MuxState {
+    Unresolved,
+    Visiting,
+    Inactive,
+    /// Active at the given nesting depth, with the switch value if the signal is a multiplexor
+    Active {
+        depth: usize,
+        value: Option<u64>,
+    },
+}
+
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for Message<'a> {
     type Error = DbcError;
 
     /// Parse message: `BO_ message_id message_name: message_size transmitter`
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::message)?;
 
         // Parse msg_var (contains msg_literal ~ message_id)
@@ -48,7 +361,25 @@ impl TryFrom<Pair<'_, Rule>> for Message {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for Message<'_> {
+    /// Write message: `BO_ message_id message_name: message_size transmitter`, followed by one line per signal
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BO_ {} {}: {} {}",
+            self.id,
+            self.name,
+            self.size,
+            self.transmitter.as_deref().unwrap_or(VECTOR_PLACEHOLDER),
+        )?;
+        for signal in &self.signals {
+            write!(f, "\n {signal}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::ast::{ByteOrder, MultiplexIndicator, ValueType};
@@ -65,12 +396,12 @@ BO_ 1 MCA_A1: 6 MFA
 
         let exp = Message {
             id: MessageId::Standard(1),
-            name: "MCA_A1".to_string(),
+            name: "MCA_A1".into(),
             size: 6,
-            transmitter: Some("MFA".to_string()),
+            transmitter: Some("MFA".into()),
             signals: vec![
                 Signal {
-                    name: "ABC_1".to_string(),
+                    name: "ABC_1".into(),
                     start_bit: 9,
                     size: 2,
                     byte_order: ByteOrder::LittleEndian,
@@ -79,12 +410,12 @@ BO_ 1 MCA_A1: 6 MFA
                     offset: 0.0,
                     min: NumericValue::Uint(0),
                     max: NumericValue::Uint(0),
-                    unit: "x".to_string(),
+                    unit: "x".into(),
                     multiplexer_indicator: MultiplexIndicator::Plain,
-                    receivers: vec!["XYZ_OUS".to_string()],
+                    receivers: vec!["XYZ_OUS".into()],
                 },
                 Signal {
-                    name: "BasL2".to_string(),
+                    name: "BasL2".into(),
                     start_bit: 3,
                     size: 2,
                     byte_order: ByteOrder::BigEndian,
@@ -93,9 +424,9 @@ BO_ 1 MCA_A1: 6 MFA
                     offset: 0.0,
                     min: NumericValue::Uint(0),
                     max: NumericValue::Uint(0),
-                    unit: "x".to_string(),
+                    unit: "x".into(),
                     multiplexer_indicator: MultiplexIndicator::Plain,
-                    receivers: vec!["DFA_FUS".to_string()],
+                    receivers: vec!["DFA_FUS".into()],
                 },
             ],
         };
@@ -112,12 +443,12 @@ BO_ 1 MCA_A1: 6 MFA
 
         let exp = Message {
             id: MessageId::Standard(1),
-            name: "MCA_A1".to_string(),
+            name: "MCA_A1".into(),
             size: 6,
-            transmitter: Some("MFA".to_string()),
+            transmitter: Some("MFA".into()),
             signals: vec![
                 Signal {
-                    name: "uint".to_string(),
+                    name: "uint".into(),
                     start_bit: 9,
                     size: 2,
                     byte_order: ByteOrder::LittleEndian,
@@ -126,12 +457,12 @@ BO_ 1 MCA_A1: 6 MFA
                     offset: 0.0,
                     min: NumericValue::Uint(0),
                     max: NumericValue::Uint(18_446_744_073_709_551_615),
-                    unit: "x".to_string(),
+                    unit: "x".into(),
                     multiplexer_indicator: MultiplexIndicator::Plain,
-                    receivers: vec!["XYZ_OUS".to_string()],
+                    receivers: vec!["XYZ_OUS".into()],
                 },
                 Signal {
-                    name: "int".to_string(),
+                    name: "int".into(),
                     start_bit: 3,
                     size: 2,
                     byte_order: ByteOrder::BigEndian,
@@ -140,9 +471,9 @@ BO_ 1 MCA_A1: 6 MFA
                     offset: 0.0,
                     min: NumericValue::Int(-9_223_372_036_854_775_808),
                     max: NumericValue::Uint(9_223_372_036_854_775_807),
-                    unit: "x".to_string(),
+                    unit: "x".into(),
                     multiplexer_indicator: MultiplexIndicator::Plain,
-                    receivers: vec!["DFA_FUS".to_string()],
+                    receivers: vec!["DFA_FUS".into()],
                 },
             ],
         };
@@ -150,10 +481,128 @@ BO_ 1 MCA_A1: 6 MFA
         assert_eq!(val, exp);
     }
 
+    #[test]
+    fn dlc_test() {
+        for dlc in 0..=15 {
+            let length = dlc_to_length(dlc).unwrap();
+            assert_eq!(length_to_dlc(length), Some(dlc));
+        }
+        assert_eq!(dlc_to_length(9), Some(12));
+        assert_eq!(dlc_to_length(15), Some(64));
+        assert_eq!(dlc_to_length(16), None);
+        assert_eq!(length_to_dlc(10), None);
+        assert_eq!(length_to_dlc(65), None);
+    }
+
     #[test]
     fn vector_placeholder_transmitter_test() {
         let def = "BO_ 1 MCA_A1: 6 Vector__XXX";
         let val = test_into::<Message>(def, Rule::message);
         assert_eq!(val.transmitter, None);
     }
+
+    #[test]
+    fn message_write_test() {
+        let def = r#"BO_ 2147483649 MCA_A1: 6 Vector__XXX
+ SG_ ABC_1 M : 9|2@1+ (1,0) [0|0] "x" XYZ_OUS
+ SG_ BasL2 m1 : 3|2@0- (1,0) [0|0.5] "x" DFA_FUS"#;
+        let val = test_into::<Message>(def, Rule::message);
+        assert_eq!(val.to_string(), def);
+    }
+
+    #[test]
+    fn message_decode_test() {
+        let def = r#"BO_ 1 MCA_A1: 8 MFA
+ SG_ Counter : 0|4@1+ (1,0) [0|15] "" XYZ_OUS
+ SG_ Value : 15|16@0- (0.5,0) [0|0] "x" XYZ_OUS
+ SG_ Full : 7|64@0+ (1,0) [0|0] "x" XYZ_OUS"#;
+        let message = test_into::<Message>(def, Rule::message);
+        let data = [0x0A, 0xFF, 0xFE, 0, 0, 0, 0, 0];
+        let values = message.decode(&data).unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].signal.name, "Counter");
+        assert_eq!(values[0].raw, NumericValue::Uint(0xA));
+        assert_eq!(values[1].raw, NumericValue::Int(-2));
+        assert!((values[1].physical - -1.0).abs() < f64::EPSILON);
+        assert_eq!(values[2].raw, NumericValue::Uint(0x0AFF_FE00_0000_0000));
+    }
+
+    #[test]
+    fn message_encode_test() {
+        let def = r#"BO_ 1 MCA_A1: 8 MFA
+ SG_ Counter : 0|4@1+ (1,0) [0|15] "" XYZ_OUS
+ SG_ Value : 15|16@0- (0.5,0) [0|0] "x" XYZ_OUS
+ SG_ Full : 7|64@0+ (1,0) [0|0] "x" XYZ_OUS"#;
+        let message = test_into::<Message>(def, Rule::message);
+        let data = message
+            .encode([("Counter", 10.0), ("Value", -1.0)])
+            .unwrap();
+        assert_eq!(data, [0x0A, 0xFF, 0xFE, 0, 0, 0, 0, 0]);
+
+        let decoded = message.decode(&data).unwrap();
+        assert!((decoded[1].physical - -1.0).abs() < f64::EPSILON);
+
+        assert_eq!(
+            message.encode([("Unknown", 1.0)]),
+            Err(CodecError::UnknownSignal {
+                message: "MCA_A1".into(),
+                signal: "Unknown".into(),
+            })
+        );
+        let def = r#"BO_ 1 Huge: 4294967295 MFA
+ SG_ Counter : 0|4@1+ (1,0) [0|15] "" XYZ_OUS"#;
+        let message = test_into::<Message>(def, Rule::message);
+        assert_eq!(
+            message.encode([("Counter", 1.0)]),
+            Err(CodecError::UnsupportedMessageSize {
+                message: "Huge".into(),
+                size: 4_294_967_295,
+            })
+        );
+    }
+
+    #[test]
+    fn message_decode_multiplexed_test() {
+        let def = r#"BO_ 1 MUX: 2 MFA
+ SG_ Switch M : 0|4@1+ (1,0) [0|0] "" XYZ_OUS
+ SG_ A m0 : 8|8@1+ (1,0) [0|0] "" XYZ_OUS
+ SG_ B m1 : 8|8@1+ (1,0) [0|0] "" XYZ_OUS
+ SG_ Plain : 4|4@1+ (1,0) [0|0] "" XYZ_OUS"#;
+        let message = test_into::<Message>(def, Rule::message);
+        let integer = |_: &Signal| SignalExtendedValueType::SignedOrUnsignedInteger;
+
+        let decoded = message
+            .decode_multiplexed_with(&[0x21, 0x05], &[], integer)
+            .unwrap();
+        let names: Vec<_> = decoded.signals.iter().map(|s| &s.signal.name).collect();
+        assert_eq!(names, ["Switch", "B", "Plain"]);
+        assert_eq!(decoded.branch.len(), 1);
+        assert_eq!(decoded.branch[0].multiplexor.name, "Switch");
+        assert_eq!(decoded.branch[0].value, 1);
+        assert_eq!(decoded.signal("B").unwrap().raw, NumericValue::Uint(5));
+
+        // no signal is selected by switch value 2
+        let decoded = message
+            .decode_multiplexed_with(&[0x02, 0x05], &[], integer)
+            .unwrap();
+        assert!(decoded.signal("A").is_none() && decoded.signal("B").is_none());
+    }
+
+    #[test]
+    fn message_decode_unresolved_multiplexor_test() {
+        let def = r#"BO_ 1 MUX: 2 MFA
+ SG_ Switch_1 M : 0|4@1+ (1,0) [0|0] "" XYZ_OUS
+ SG_ Switch_2 M : 4|4@1+ (1,0) [0|0] "" XYZ_OUS
+ SG_ A m0 : 8|8@1+ (1,0) [0|0] "" XYZ_OUS"#;
+        let message = test_into::<Message>(def, Rule::message);
+        assert_eq!(
+            message.decode_multiplexed_with(&[0; 2], &[], |_| {
+                SignalExtendedValueType::SignedOrUnsignedInteger
+            }),
+            Err(CodecError::UnresolvedMultiplexor {
+                message: "MUX".into(),
+                signal: "A".into(),
+            })
+        );
+    }
 }
@@ -1,11 +1,15 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
+#[cfg(feature = "std")]
 use crate::parser::parse_uint;
 use crate::DbcError;
 
 /// CAN id in header of CAN frame.
 /// Must be unique in DBC file.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageId {
     Standard(u16),
@@ -58,6 +62,7 @@ impl TryFrom<u32> for MessageId {
     }
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<Pair<'_, Rule>> for MessageId {
     type Error = DbcError;
 
@@ -66,7 +71,14 @@ impl TryFrom<Pair<'_, Rule>> for MessageId {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for MessageId {
+    /// Write the raw value of the message id including the bit for extended identifiers
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use can_dbc_pest::Rule;
 
@@ -1,20 +1,30 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
 use crate::ast::MessageId;
-use crate::parser::{collect_node_names, next_rule, validated_inner, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{collect_node_names, next_rule, validated_inner};
+use crate::writer::write_node_names;
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct MessageTransmitter {
+pub struct MessageTransmitter<'a> {
     pub message_id: MessageId,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub transmitter: Vec<String>,
+    pub transmitter: Vec<Cow<'a, str>>,
 }
 
-impl TryFrom<Pair<'_, Rule>> for MessageTransmitter {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for MessageTransmitter<'a> {
     type Error = DbcError;
 
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::message_transmitter)?;
 
         Ok(Self {
@@ -24,7 +34,16 @@ impl TryFrom<Pair<'_, Rule>> for MessageTransmitter {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for MessageTransmitter<'_> {
+    /// Write message transmitters: `BO_TX_BU_ message_id : transmitter1,transmitter2,... ;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BO_TX_BU_ {} : ", self.message_id)?;
+        write_node_names(f, &self.transmitter, ",")?;
+        f.write_str(";")
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -36,7 +55,7 @@ BO_TX_BU_ 12345 : XZY,ABC;
 ";
         let exp = MessageTransmitter {
             message_id: MessageId::Standard(12345),
-            transmitter: vec!["XZY".to_string(), "ABC".to_string()],
+            transmitter: vec!["XZY".into(), "ABC".into()],
         };
         let val = test_into::<MessageTransmitter>(def.trim_start(), Rule::message_transmitter);
         assert_eq!(val, exp);
@@ -3,13 +3,17 @@ mod attr_default;
 mod attribute_default;
 mod attribute_definition;
 mod attribute_value;
+#[cfg(feature = "std")]
 mod attribute_value_for_object;
+#[cfg(feature = "std")]
 mod attribute_value_for_object_type;
 mod attribute_value_for_relation;
 mod attribute_value_for_relation_type;
 mod attribute_value_type;
-mod baudrate;
+mod bit_timing;
 mod byte_order;
+mod category;
+mod category_definition;
 mod comment;
 mod dbc;
 mod env_type;
@@ -17,12 +21,15 @@ mod environment_variable;
 mod environment_variable_data;
 mod extended_multiplex;
 mod extended_multiplex_mapping;
+mod filter;
+mod into_static;
 mod message;
 mod message_id;
 mod message_transmitter;
 mod multiplex_indicator;
 mod node;
 mod numeric_value;
+mod object_ref;
 mod signal;
 mod signal_attribute_value;
 mod signal_extended_value_type;
@@ -43,13 +50,17 @@ pub use attr_default::*;
 pub use attribute_default::*;
 pub use attribute_definition::*;
 pub use attribute_value::*;
+#[cfg(feature = "std")]
 pub(crate) use attribute_value_for_object::*;
+#[cfg(feature = "std")]
 pub(crate) use attribute_value_for_object_type::*;
 pub use attribute_value_for_relation::*;
 pub use attribute_value_for_relation_type::*;
 pub use attribute_value_type::*;
-pub use baudrate::*;
+pub use bit_timing::*;
 pub use byte_order::*;
+pub use category::*;
+pub use category_definition::*;
 pub use comment::*;
 pub use dbc::*;
 pub use env_type::*;
@@ -57,12 +68,15 @@ pub use environment_variable::*;
 pub use environment_variable_data::*;
 pub use extended_multiplex::*;
 pub use extended_multiplex_mapping::*;
+pub use filter::*;
+pub use into_static::*;
 pub use message::*;
 pub use message_id::*;
 pub use message_transmitter::*;
 pub use multiplex_indicator::*;
 pub use node::*;
 pub use numeric_value::*;
+pub use object_ref::*;
 pub use signal::*;
 pub use signal_attribute_value::*;
 pub use signal_extended_value_type::*;
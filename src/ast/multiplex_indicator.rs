@@ -1,6 +1,7 @@
-use std::str;
+use alloc::string::ToString;
+use core::{fmt, str};
 
-use crate::parser::DbcError;
+use crate::error::DbcError;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -41,6 +42,19 @@ impl TryFrom<&str> for MultiplexIndicator {
     }
 }
 
+impl fmt::Display for MultiplexIndicator {
+    /// Write the indicator as used in a signal definition, e.g. `M`, `m3` or `m3M`.
+    /// Plain signals have no indicator and are written as an empty string.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Multiplexor => f.write_str("M"),
+            Self::MultiplexedSignal(v) => write!(f, "m{v}"),
+            Self::MultiplexorAndMultiplexedSignal(v) => write!(f, "m{v}M"),
+            Self::Plain => Ok(()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,23 +1,35 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::parser::{validated, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::validated;
 
 /// CAN network nodes, names must be unique
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Node(pub String);
+pub struct Node<'a>(pub Cow<'a, str>);
 
-impl TryFrom<Pair<'_, Rule>> for Node {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for Node<'a> {
     type Error = DbcError;
 
-    fn try_from(value: Pair<Rule>) -> Result<Self, Self::Error> {
-        Ok(Self(
-            validated(value, Rule::node_name)?.as_str().to_string(),
-        ))
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
+        Ok(Self(validated(value, Rule::node_name)?.as_str().into()))
+    }
+}
+
+impl fmt::Display for Node<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::parser::collect_all;
@@ -29,10 +41,10 @@ mod tests {
 BU_: ZU XYZ ABC OIU
 ";
         let exp = vec![
-            Node("ZU".to_string()),
-            Node("XYZ".to_string()),
-            Node("ABC".to_string()),
-            Node("OIU".to_string()),
+            Node("ZU".into()),
+            Node("XYZ".into()),
+            Node("ABC".into()),
+            Node("OIU".into()),
         ];
         let pair = parse(def.trim_start(), Rule::nodes).unwrap();
         let val: Vec<Node> = collect_all(&mut pair.into_inner()).unwrap();
@@ -1,4 +1,5 @@
-use std::str::FromStr;
+use alloc::string::ToString;
+use core::str::FromStr;
 
 use crate::DbcError;
 
@@ -10,6 +11,19 @@ pub enum NumericValue {
     Double(f64),
 }
 
+impl NumericValue {
+    /// Convert the value to `f64`, possibly losing precision for very large integers
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn as_f64(self) -> f64 {
+        match self {
+            NumericValue::Uint(v) => v as f64,
+            NumericValue::Int(v) => v as f64,
+            NumericValue::Double(v) => v,
+        }
+    }
+}
+
 impl FromStr for NumericValue {
     type Err = DbcError;
 
@@ -26,18 +40,21 @@ impl FromStr for NumericValue {
     }
 }
 
-impl std::fmt::Display for NumericValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for NumericValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             NumericValue::Uint(v) => write!(f, "{v}"),
             NumericValue::Int(v) => write!(f, "{v}"),
-            NumericValue::Double(v) => write!(f, "{v}"),
+            // Debug formatting keeps a fractional part or exponent, so the value is read back as a double
+            NumericValue::Double(v) => write!(f, "{v:?}"),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::format;
+
     use super::*;
 
     #[test]
@@ -63,4 +80,12 @@ mod tests {
         let value = "3.141592653589793";
         assert_eq!(format!("{}", NumericValue::from_str(value).unwrap()), value);
     }
+
+    #[test]
+    fn double_without_fraction() {
+        let value = NumericValue::from_str("80.0").unwrap();
+        assert_eq!(value, NumericValue::Double(80.0));
+        assert_eq!(format!("{value}"), "80.0");
+        assert_eq!(NumericValue::from_str(&value.to_string()).unwrap(), value);
+    }
 }
@@ -0,0 +1,152 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use crate::ast::MessageId;
+
+/// Reference to an object of the database, e.g. the object a comment or attribute value is assigned to
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ObjectRef {
+    Database,
+    Node(String),
+    Message(MessageId),
+    Signal {
+        message_id: MessageId,
+        signal_name: String,
+    },
+    EnvironmentVariable(String),
+    /// Relation of a node to a signal, the object of `BU_SG_REL_` attribute values
+    NodeToSignal {
+        node_name: String,
+        message_id: MessageId,
+        signal_name: String,
+    },
+    /// Relation of a node to a message, the object of `BU_BO_REL_` attribute values
+    NodeToMessage {
+        node_name: String,
+        message_id: MessageId,
+    },
+    /// Relation of a node to an environment variable, the object of `BU_EV_REL_` attribute values
+    NodeToEnvironmentVariable {
+        node_name: String,
+        variable_name: String,
+    },
+}
+
+impl ObjectRef {
+    /// Whether the object is a relation of a node to another object, the object of `BA_REL_` attribute values
+    #[must_use]
+    pub fn is_relation(&self) -> bool {
+        matches!(
+            self,
+            Self::NodeToSignal { .. }
+                | Self::NodeToMessage { .. }
+                | Self::NodeToEnvironmentVariable { .. }
+        )
+    }
+}
+
+/// Borrowed form of an [`ObjectRef`], to compare the objects of statements without allocating
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ObjectKey<'s> {
+    Database,
+    Node(&'s str),
+    Message(MessageId),
+    Signal(MessageId, &'s str),
+    EnvironmentVariable(&'s str),
+    NodeToSignal(&'s str, MessageId, &'s str),
+    NodeToMessage(&'s str, MessageId),
+    NodeToEnvironmentVariable(&'s str, &'s str),
+}
+
+impl ObjectRef {
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        match self {
+            Self::Database => ObjectKey::Database,
+            Self::Node(name) => ObjectKey::Node(name),
+            Self::Message(id) => ObjectKey::Message(*id),
+            Self::Signal {
+                message_id,
+                signal_name,
+            } => ObjectKey::Signal(*message_id, signal_name),
+            Self::EnvironmentVariable(name) => ObjectKey::EnvironmentVariable(name),
+            Self::NodeToSignal {
+                node_name,
+                message_id,
+                signal_name,
+            } => ObjectKey::NodeToSignal(node_name, *message_id, signal_name),
+            Self::NodeToMessage {
+                node_name,
+                message_id,
+            } => ObjectKey::NodeToMessage(node_name, *message_id),
+            Self::NodeToEnvironmentVariable {
+                node_name,
+                variable_name,
+            } => ObjectKey::NodeToEnvironmentVariable(node_name, variable_name),
+        }
+    }
+}
+
+impl From<ObjectKey<'_>> for ObjectRef {
+    fn from(key: ObjectKey<'_>) -> Self {
+        match key {
+            ObjectKey::Database => Self::Database,
+            ObjectKey::Node(name) => Self::Node(name.to_string()),
+            ObjectKey::Message(id) => Self::Message(id),
+            ObjectKey::Signal(message_id, signal_name) => Self::Signal {
+                message_id,
+                signal_name: signal_name.to_string(),
+            },
+            ObjectKey::EnvironmentVariable(name) => Self::EnvironmentVariable(name.to_string()),
+            ObjectKey::NodeToSignal(node_name, message_id, signal_name) => Self::NodeToSignal {
+                node_name: node_name.to_string(),
+                message_id,
+                signal_name: signal_name.to_string(),
+            },
+            ObjectKey::NodeToMessage(node_name, message_id) => Self::NodeToMessage {
+                node_name: node_name.to_string(),
+                message_id,
+            },
+            ObjectKey::NodeToEnvironmentVariable(node_name, variable_name) => {
+                Self::NodeToEnvironmentVariable {
+                    node_name: node_name.to_string(),
+                    variable_name: variable_name.to_string(),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ObjectRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database => f.write_str("database"),
+            Self::Node(name) => write!(f, "node {name}"),
+            Self::Message(id) => write!(f, "message {id}"),
+            Self::Signal {
+                message_id,
+                signal_name,
+            } => write!(f, "signal {signal_name} of message {message_id}"),
+            Self::EnvironmentVariable(name) => write!(f, "environment variable {name}"),
+            Self::NodeToSignal {
+                node_name,
+                message_id,
+                signal_name,
+            } => write!(
+                f,
+                "relation of node {node_name} to signal {signal_name} of message {message_id}"
+            ),
+            Self::NodeToMessage {
+                node_name,
+                message_id,
+            } => write!(f, "relation of node {node_name} to message {message_id}"),
+            Self::NodeToEnvironmentVariable {
+                node_name,
+                variable_name,
+            } => write!(
+                f,
+                "relation of node {node_name} to environment variable {variable_name}"
+            ),
+        }
+    }
+}
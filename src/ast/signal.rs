@@ -1,10 +1,20 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::ast::{ByteOrder, MultiplexIndicator, NumericValue, ValueType};
+use crate::ast::{ByteOrder, MultiplexIndicator, NumericValue, SignalExtendedValueType, ValueType};
+use crate::codec::{extract_bits, insert_bits, round, sign_extend, CodecError, CodecResult};
+#[cfg(feature = "std")]
 use crate::parser::{
     collect_node_names, next, next_optional_rule, next_rule, next_string, parse_min_max_numeric,
     parse_next_float, parse_next_inner_str, parse_next_uint, validated_inner,
 };
+use crate::writer::{write_node_names, Quoted};
+#[cfg(feature = "std")]
 use crate::DbcError;
 
 /// One or multiple signals are the payload of a CAN frame.
@@ -12,8 +22,8 @@ use crate::DbcError;
 /// `let value = |can_signal_value| can_signal_value * factor + offset;`
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Signal {
-    pub name: String,
+pub struct Signal<'a> {
+    pub name: Cow<'a, str>,
     pub multiplexer_indicator: MultiplexIndicator,
     pub start_bit: u64,
     pub size: u64,
@@ -23,16 +33,257 @@ pub struct Signal {
     pub offset: f64,
     pub min: NumericValue,
     pub max: NumericValue,
-    pub unit: String,
+    pub unit: Cow<'a, str>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub receivers: Vec<String>,
+    pub receivers: Vec<Cow<'a, str>>,
+}
+
+impl Signal<'_> {
+    /// Whether this signal is a multiplexor switch, including nested multiplexors (`mxM`)
+    #[must_use]
+    pub fn is_multiplexor(&self) -> bool {
+        matches!(
+            self.multiplexer_indicator,
+            MultiplexIndicator::Multiplexor
+                | MultiplexIndicator::MultiplexorAndMultiplexedSignal(_)
+        )
+    }
+
+    /// Whether this signal is multiplexed by another signal, including nested multiplexors (`mxM`)
+    #[must_use]
+    pub fn is_multiplexed(&self) -> bool {
+        matches!(
+            self.multiplexer_indicator,
+            MultiplexIndicator::MultiplexedSignal(_)
+                | MultiplexIndicator::MultiplexorAndMultiplexedSignal(_)
+        )
+    }
+
+    /// Extract the raw value of this signal from a CAN frame payload, interpreting it as an integer.
+    ///
+    /// Signed signals are sign-extended and returned as [`NumericValue::Int`],
+    /// unsigned signals are returned as [`NumericValue::Uint`].
+    pub fn decode_raw(&self, data: &[u8]) -> CodecResult<NumericValue> {
+        self.decode_raw_as(data, SignalExtendedValueType::SignedOrUnsignedInteger)
+    }
+
+    /// Extract the raw value of this signal from a CAN frame payload using the given `SIG_VALTYPE_`.
+    ///
+    /// IEEE floating point signals must be 32 or 64 bits wide and are returned as [`NumericValue::Double`].
+    #[expect(clippy::cast_possible_truncation)]
+    pub fn decode_raw_as(
+        &self,
+        data: &[u8],
+        value_type: SignalExtendedValueType,
+    ) -> CodecResult<NumericValue> {
+        self.check_size(value_type)?;
+        let value =
+            extract_bits(data, self.start_bit, self.size, self.byte_order).ok_or_else(|| {
+                CodecError::SignalOutOfBounds {
+                    signal: self.name.to_string(),
+                    payload_size: data.len(),
+                }
+            })?;
+        Ok(match (value_type, self.value_type) {
+            (SignalExtendedValueType::SignedOrUnsignedInteger, ValueType::Signed) => {
+                NumericValue::Int(sign_extend(value, self.size))
+            }
+            (SignalExtendedValueType::SignedOrUnsignedInteger, ValueType::Unsigned) => {
+                NumericValue::Uint(value)
+            }
+            // the size check guarantees that the value fits into 32 bits
+            (SignalExtendedValueType::IEEEfloat32Bit, _) => {
+                NumericValue::Double(f64::from(f32::from_bits(value as u32)))
+            }
+            (SignalExtendedValueType::IEEEdouble64bit, _) => {
+                NumericValue::Double(f64::from_bits(value))
+            }
+        })
+    }
+
+    /// Extract the physical value of this signal from a CAN frame payload,
+    /// i.e. the raw value scaled by `factor` and `offset`.
+    pub fn decode_physical(&self, data: &[u8]) -> CodecResult<f64> {
+        Ok(self.to_physical(self.decode_raw(data)?))
+    }
+
+    /// Extract the physical value of this signal from a CAN frame payload using the given `SIG_VALTYPE_`.
+    pub fn decode_physical_as(
+        &self,
+        data: &[u8],
+        value_type: SignalExtendedValueType,
+    ) -> CodecResult<f64> {
+        Ok(self.to_physical(self.decode_raw_as(data, value_type)?))
+    }
+
+    /// Convert a raw value to its physical value: `raw * factor + offset`
+    #[must_use]
+    pub fn to_physical(&self, raw: NumericValue) -> f64 {
+        raw.as_f64() * self.factor + self.offset
+    }
+
+    /// Convert a physical value to its raw value: `(physical - offset) / factor`, rounded to the nearest integer.
+    ///
+    /// The physical value is checked against `min` and `max`, unless both are equal (e.g. `[0|0]`),
+    /// which is commonly used for signals without a defined range.
+    pub fn to_raw(&self, physical: f64) -> CodecResult<NumericValue> {
+        self.to_raw_as(physical, SignalExtendedValueType::SignedOrUnsignedInteger)
+    }
+
+    /// Convert a physical value to its raw value using the given `SIG_VALTYPE_`, see [`Signal::to_raw`].
+    ///
+    /// Raw values of IEEE floating point signals are not rounded and returned as [`NumericValue::Double`].
+    pub fn to_raw_as(
+        &self,
+        physical: f64,
+        value_type: SignalExtendedValueType,
+    ) -> CodecResult<NumericValue> {
+        if self.min != self.max {
+            if physical < self.min.as_f64() {
+                return Err(CodecError::BelowMinimum {
+                    signal: self.name.to_string(),
+                    value: physical,
+                    min: self.min,
+                });
+            }
+            if physical > self.max.as_f64() {
+                return Err(CodecError::AboveMaximum {
+                    signal: self.name.to_string(),
+                    value: physical,
+                    max: self.max,
+                });
+            }
+        }
+        let raw = (physical - self.offset) / self.factor;
+        match value_type {
+            SignalExtendedValueType::SignedOrUnsignedInteger => self.integer_raw(round(raw)),
+            SignalExtendedValueType::IEEEfloat32Bit | SignalExtendedValueType::IEEEdouble64bit => {
+                Ok(NumericValue::Double(raw))
+            }
+        }
+    }
+
+    /// Store a raw value of this signal in a CAN frame payload, leaving all other bits untouched
+    pub fn encode_raw(&self, data: &mut [u8], raw: NumericValue) -> CodecResult<()> {
+        self.encode_raw_as(data, raw, SignalExtendedValueType::SignedOrUnsignedInteger)
+    }
+
+    /// Store a raw value of this signal in a CAN frame payload using the given `SIG_VALTYPE_`
+    pub fn encode_raw_as(
+        &self,
+        data: &mut [u8],
+        raw: NumericValue,
+        value_type: SignalExtendedValueType,
+    ) -> CodecResult<()> {
+        let bits = self.raw_bits(raw, value_type)?;
+        insert_bits(data, self.start_bit, self.size, self.byte_order, bits).ok_or_else(|| {
+            CodecError::SignalOutOfBounds {
+                signal: self.name.to_string(),
+                payload_size: data.len(),
+            }
+        })
+    }
+
+    /// Store a physical value of this signal in a CAN frame payload, see [`Signal::to_raw`]
+    pub fn encode_physical(&self, data: &mut [u8], physical: f64) -> CodecResult<()> {
+        self.encode_raw(data, self.to_raw(physical)?)
+    }
+
+    /// Store a physical value of this signal in a CAN frame payload using the given `SIG_VALTYPE_`
+    pub fn encode_physical_as(
+        &self,
+        data: &mut [u8],
+        physical: f64,
+        value_type: SignalExtendedValueType,
+    ) -> CodecResult<()> {
+        self.encode_raw_as(data, self.to_raw_as(physical, value_type)?, value_type)
+    }
+
+    /// Make sure the signal size can hold a value of the given type
+    fn check_size(&self, value_type: SignalExtendedValueType) -> CodecResult<()> {
+        let supported = match value_type {
+            SignalExtendedValueType::SignedOrUnsignedInteger => self.size <= 64,
+            SignalExtendedValueType::IEEEfloat32Bit => self.size == 32,
+            SignalExtendedValueType::IEEEdouble64bit => self.size == 64,
+        };
+        if supported {
+            Ok(())
+        } else {
+            Err(CodecError::UnsupportedSignalSize {
+                signal: self.name.to_string(),
+                size: self.size,
+            })
+        }
+    }
+
+    /// Convert a rounded floating point raw value to an integer raw value of this signal's value type
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn integer_raw(&self, raw: f64) -> CodecResult<NumericValue> {
+        // the bounds are 2^64 and 2^63, NaN fails all comparisons
+        match self.value_type {
+            ValueType::Unsigned if (0.0..18_446_744_073_709_551_616.0).contains(&raw) => {
+                Ok(NumericValue::Uint(raw as u64))
+            }
+            ValueType::Signed
+                if (-9_223_372_036_854_775_808.0..9_223_372_036_854_775_808.0).contains(&raw) =>
+            {
+                Ok(NumericValue::Int(raw as i64))
+            }
+            _ => Err(self.overflow(NumericValue::Double(raw))),
+        }
+    }
+
+    /// Range-check a raw value against the signal size and value type and return its bits
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn raw_bits(&self, raw: NumericValue, value_type: SignalExtendedValueType) -> CodecResult<u64> {
+        self.check_size(value_type)?;
+        match value_type {
+            SignalExtendedValueType::SignedOrUnsignedInteger => {}
+            SignalExtendedValueType::IEEEfloat32Bit => {
+                let value = raw.as_f64() as f32;
+                if value.is_infinite() && raw.as_f64().is_finite() {
+                    return Err(self.overflow(raw));
+                }
+                return Ok(u64::from(value.to_bits()));
+            }
+            SignalExtendedValueType::IEEEdouble64bit => return Ok(raw.as_f64().to_bits()),
+        }
+        let value = match raw {
+            NumericValue::Uint(v) => i128::from(v),
+            NumericValue::Int(v) => i128::from(v),
+            NumericValue::Double(v) => match self.integer_raw(round(v))? {
+                NumericValue::Uint(v) => i128::from(v),
+                NumericValue::Int(v) => i128::from(v),
+                NumericValue::Double(_) => unreachable!("integer_raw returns integers"),
+            },
+        };
+        let (min, max) = match (self.value_type, self.size) {
+            (_, 0) => (0, 0),
+            (ValueType::Unsigned, size) => (0, (1_i128 << size) - 1),
+            (ValueType::Signed, size) => (-(1_i128 << (size - 1)), (1_i128 << (size - 1)) - 1),
+        };
+        if !(min..=max).contains(&value) {
+            return Err(self.overflow(raw));
+        }
+        // two's complement representation, truncated to the signal size
+        Ok(value as u64 & (u64::MAX >> (64 - self.size.max(1))))
+    }
+
+    fn overflow(&self, raw: NumericValue) -> CodecError {
+        CodecError::Overflow {
+            signal: self.name.to_string(),
+            raw,
+            size: self.size,
+        }
+    }
 }
 
 /// Parse signal: `SG_ signal_name : start_bit|signal_size@byte_order+/- (factor,offset) [min|max] "unit" receiver`
-impl TryFrom<Pair<'_, Rule>> for Signal {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for Signal<'a> {
     type Error = DbcError;
 
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::signal)?;
 
         next_rule(&mut pairs, Rule::signal_nl_ident)?; // skip
@@ -70,7 +321,31 @@ impl TryFrom<Pair<'_, Rule>> for Signal {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for Signal<'_> {
+    /// Write signal: `SG_ signal_name : start_bit|signal_size@byte_order+/- (factor,offset) [min|max] "unit" receiver`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SG_ {} ", self.name)?;
+        if self.multiplexer_indicator != MultiplexIndicator::Plain {
+            write!(f, "{} ", self.multiplexer_indicator)?;
+        }
+        write!(
+            f,
+            ": {}|{}@{}{} ({},{}) [{}|{}] {} ",
+            self.start_bit,
+            self.size,
+            self.byte_order,
+            self.value_type,
+            self.factor,
+            self.offset,
+            self.min,
+            self.max,
+            Quoted(&self.unit),
+        )?;
+        write_node_names(f, &self.receivers, ",")
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -82,7 +357,7 @@ mod tests {
 "#;
 
         let exp = Signal {
-            name: "NAME".to_string(),
+            name: "NAME".into(),
             start_bit: 3,
             size: 2,
             byte_order: ByteOrder::LittleEndian,
@@ -91,9 +366,9 @@ mod tests {
             offset: 0.0,
             min: NumericValue::Uint(0),
             max: NumericValue::Uint(0),
-            unit: "x".to_string(),
+            unit: "x".into(),
             multiplexer_indicator: MultiplexIndicator::Plain,
-            receivers: vec!["UFA".to_string()],
+            receivers: vec!["UFA".into()],
         };
         let val = test_into::<Signal>(def, Rule::signal);
         assert_eq!(val, exp);
@@ -105,7 +380,7 @@ mod tests {
         let def = "\r\n \r\n SG_ BasL2 : 3|2@0- (1,0) [0|0] \"x\" DFA_FUS\r\n";
 
         let exp = Signal {
-            name: "BasL2".to_string(),
+            name: "BasL2".into(),
             start_bit: 3,
             size: 2,
             byte_order: ByteOrder::BigEndian,
@@ -114,9 +389,9 @@ mod tests {
             offset: 0.0,
             min: NumericValue::Uint(0),
             max: NumericValue::Uint(0),
-            unit: "x".to_string(),
+            unit: "x".into(),
             multiplexer_indicator: MultiplexIndicator::Plain,
-            receivers: vec!["DFA_FUS".to_string()],
+            receivers: vec!["DFA_FUS".into()],
         };
         let val = test_into::<Signal>(def, Rule::signal);
         assert_eq!(val, exp);
@@ -130,4 +405,202 @@ mod tests {
         let val = test_into::<Signal>(def, Rule::signal);
         assert_eq!(val.receivers, Vec::<String>::new());
     }
+
+    #[test]
+    fn signal_write_test() {
+        let def = r#"SG_ Signal_2 m1 : 3|12@0- (0.5,-10) [-10|2037.5] "Byte" ABC,DEF"#;
+        let input = format!("\n {def}");
+        let val = test_into::<Signal>(&input, Rule::signal);
+        assert_eq!(val.to_string(), def);
+    }
+
+    #[test]
+    fn signal_write_no_receivers_test() {
+        let def = r#"
+ SG_ NAME : 3|2@1- (1,0) [0|0] "x" Vector__XXX
+"#;
+        let val = test_into::<Signal>(def, Rule::signal);
+        assert_eq!(
+            val.to_string(),
+            r#"SG_ NAME : 3|2@1- (1,0) [0|0] "x" Vector__XXX"#
+        );
+    }
+
+    #[test]
+    fn decode_little_endian_test() {
+        let def = r#"
+ SG_ Temp : 8|12@1- (0.1,-40) [-40|100] "degC" Vector__XXX
+"#;
+        let signal = test_into::<Signal>(def, Rule::signal);
+        // raw 0xFFE => -2 as 12-bit two's complement
+        let data = [0x00, 0xFE, 0x0F, 0x00];
+        assert_eq!(signal.decode_raw(&data), Ok(NumericValue::Int(-2)));
+        let value = signal.decode_physical(&data).unwrap();
+        assert!((value - -40.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn decode_big_endian_test() {
+        let def = r#"
+ SG_ Speed : 7|16@0+ (0.01,0) [0|655.35] "km/h" Vector__XXX
+"#;
+        let signal = test_into::<Signal>(def, Rule::signal);
+        let data = [0x12, 0x34];
+        assert_eq!(signal.decode_raw(&data), Ok(NumericValue::Uint(0x1234)));
+        let value = signal.decode_physical(&data).unwrap();
+        assert!((value - 46.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn decode_out_of_bounds_test() {
+        let def = r#"
+ SG_ Speed : 7|16@0+ (1,0) [0|0] "" Vector__XXX
+"#;
+        let signal = test_into::<Signal>(def, Rule::signal);
+        assert_eq!(
+            signal.decode_raw(&[0x12]),
+            Err(CodecError::SignalOutOfBounds {
+                signal: "Speed".into(),
+                payload_size: 1,
+            })
+        );
+
+        // start bits near u64::MAX must not overflow
+        for def in [
+            r#" SG_ Speed : 18446744073709551615|16@1+ (1,0) [0|0] "" Vector__XXX"#,
+            r#" SG_ Speed : 18446744073709551608|16@0+ (1,0) [0|0] "" Vector__XXX"#,
+        ] {
+            let input = format!("\n{def}");
+            let signal = test_into::<Signal>(&input, Rule::signal);
+            let error = Err(CodecError::SignalOutOfBounds {
+                signal: "Speed".into(),
+                payload_size: 8,
+            });
+            assert_eq!(signal.decode_raw(&[0; 8]), error);
+            assert_eq!(
+                signal.encode_raw(&mut [0; 8], NumericValue::Uint(1)),
+                error.map(|_| ())
+            );
+        }
+    }
+
+    #[test]
+    fn encode_test() {
+        let def = r#"
+ SG_ Temp : 8|12@1- (0.1,-40) [-50|100] "degC" Vector__XXX
+"#;
+        let signal = test_into::<Signal>(def, Rule::signal);
+        let mut data = [0xFF; 4];
+        signal.encode_physical(&mut data, -40.2).unwrap();
+        assert_eq!(data, [0xFF, 0xFE, 0xFF, 0xFF]);
+        assert_eq!(signal.decode_raw(&data), Ok(NumericValue::Int(-2)));
+
+        assert_eq!(
+            signal.encode_physical(&mut data, -60.0),
+            Err(CodecError::BelowMinimum {
+                signal: "Temp".into(),
+                value: -60.0,
+                min: NumericValue::Int(-50),
+            })
+        );
+        assert_eq!(
+            signal.encode_physical(&mut data, 100.5),
+            Err(CodecError::AboveMaximum {
+                signal: "Temp".into(),
+                value: 100.5,
+                max: NumericValue::Uint(100),
+            })
+        );
+    }
+
+    #[test]
+    fn encode_overflow_test() {
+        // [0|0] means that the range is not checked
+        let def = r#"
+ SG_ Small : 0|4@1+ (1,0) [0|0] "" Vector__XXX
+"#;
+        let signal = test_into::<Signal>(def, Rule::signal);
+        let mut data = [0; 1];
+        signal.encode_physical(&mut data, 15.0).unwrap();
+        assert_eq!(data, [0x0F]);
+        assert_eq!(
+            signal.encode_physical(&mut data, 16.0),
+            Err(CodecError::Overflow {
+                signal: "Small".into(),
+                raw: NumericValue::Uint(16),
+                size: 4,
+            })
+        );
+        assert!(signal.encode_physical(&mut data, -1.0).is_err());
+        assert!(signal.encode_raw(&mut data, NumericValue::Int(-1)).is_err());
+    }
+
+    #[test]
+    fn float_32_test() {
+        let def = r#"
+ SG_ Float : 0|32@1- (1,0) [0|0] "" Vector__XXX
+"#;
+        let signal = test_into::<Signal>(def, Rule::signal);
+        let float = SignalExtendedValueType::IEEEfloat32Bit;
+        let data = 1.5_f32.to_le_bytes();
+        assert_eq!(
+            signal.decode_raw_as(&data, float),
+            Ok(NumericValue::Double(1.5))
+        );
+
+        let mut data = [0; 4];
+        signal.encode_physical_as(&mut data, -2.25, float).unwrap();
+        assert_eq!(data, (-2.25_f32).to_le_bytes());
+        assert_eq!(
+            signal.encode_physical_as(&mut data, 1e40, float),
+            Err(CodecError::Overflow {
+                signal: "Float".into(),
+                raw: NumericValue::Double(1e40),
+                size: 32,
+            })
+        );
+    }
+
+    #[test]
+    fn float_64_big_endian_test() {
+        let def = r#"
+ SG_ Double : 7|64@0- (2,1) [0|0] "" Vector__XXX
+"#;
+        let signal = test_into::<Signal>(def, Rule::signal);
+        let double = SignalExtendedValueType::IEEEdouble64bit;
+        let data = 0.1_f64.to_be_bytes();
+        assert_eq!(
+            signal.decode_raw_as(&data, double),
+            Ok(NumericValue::Double(0.1))
+        );
+        assert_eq!(signal.decode_physical_as(&data, double), Ok(1.2));
+
+        let mut data = [0; 8];
+        signal.encode_physical_as(&mut data, 4.5, double).unwrap();
+        assert_eq!(data, 1.75_f64.to_be_bytes());
+    }
+
+    #[test]
+    fn float_size_test() {
+        let def = r#"
+ SG_ Float : 0|16@1- (1,0) [0|0] "" Vector__XXX
+"#;
+        let signal = test_into::<Signal>(def, Rule::signal);
+        let err = Err(CodecError::UnsupportedSignalSize {
+            signal: "Float".into(),
+            size: 16,
+        });
+        assert_eq!(
+            signal.decode_raw_as(&[0; 8], SignalExtendedValueType::IEEEfloat32Bit),
+            err
+        );
+        assert_eq!(
+            signal.encode_raw_as(
+                &mut [0; 8],
+                NumericValue::Double(0.0),
+                SignalExtendedValueType::IEEEdouble64bit
+            ),
+            err.map(|_| ())
+        );
+    }
 }
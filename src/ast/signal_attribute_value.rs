@@ -1,7 +1,8 @@
+use alloc::borrow::Cow;
 // FIXME: not used!
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum SignalAttributeValue {
-    Text(String),
+pub enum SignalAttributeValue<'a> {
+    Text(Cow<'a, str>),
     Int(i64),
 }
@@ -1,5 +1,9 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::Rule;
 
+#[cfg(feature = "std")]
 use crate::DbcError;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -10,6 +14,7 @@ pub enum SignalExtendedValueType {
     IEEEdouble64bit,
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<Rule> for SignalExtendedValueType {
     type Error = DbcError;
 
@@ -22,3 +27,13 @@ impl TryFrom<Rule> for SignalExtendedValueType {
         }
     }
 }
+
+impl fmt::Display for SignalExtendedValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::SignedOrUnsignedInteger => "0",
+            Self::IEEEfloat32Bit => "1",
+            Self::IEEEdouble64bit => "2",
+        })
+    }
+}
@@ -1,20 +1,40 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::ast::{MessageId, SignalExtendedValueType};
-use crate::parser::{expect_empty, next, next_rule, next_string, validated_inner, DbcError};
+use crate::ast::{MessageId, ObjectKey, ObjectRef, SignalExtendedValueType};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{expect_empty, next, next_rule, next_string, validated_inner};
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct SignalExtendedValueTypeList {
+pub struct SignalExtendedValueTypeList<'a> {
     pub message_id: MessageId,
-    pub signal_name: String,
+    pub signal_name: Cow<'a, str>,
     pub signal_extended_value_type: SignalExtendedValueType,
 }
 
-impl TryFrom<Pair<'_, Rule>> for SignalExtendedValueTypeList {
+impl SignalExtendedValueTypeList<'_> {
+    /// The signal the value type applies to
+    #[must_use]
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        ObjectKey::Signal(self.message_id, &self.signal_name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for SignalExtendedValueTypeList<'a> {
     type Error = DbcError;
 
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::signal_value_type)?;
         let value = Self {
             message_id: next_rule(&mut pairs, Rule::message_id)?.try_into()?,
@@ -27,7 +47,18 @@ impl TryFrom<Pair<'_, Rule>> for SignalExtendedValueTypeList {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for SignalExtendedValueTypeList<'_> {
+    /// Write signal value type: `SIG_VALTYPE_ message_id signal_name : value_type;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SIG_VALTYPE_ {} {} : {};",
+            self.message_id, self.signal_name, self.signal_extended_value_type
+        )
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::ast::SignalExtendedValueType;
@@ -40,7 +71,7 @@ SIG_VALTYPE_ 2000 Signal_8 : 1;
 ";
         let exp = SignalExtendedValueTypeList {
             message_id: MessageId::Standard(2000),
-            signal_name: "Signal_8".to_string(),
+            signal_name: "Signal_8".into(),
             signal_extended_value_type: SignalExtendedValueType::IEEEfloat32Bit,
         };
 
@@ -1,27 +1,35 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
 use crate::ast::MessageId;
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
 use crate::parser::{
     collect_strings, expect_empty, next_rule, next_string, parse_next_uint, validated_inner,
-    DbcError,
 };
 
 /// Signal groups define a group of signals within a message
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct SignalGroups {
+pub struct SignalGroups<'a> {
     pub message_id: MessageId,
-    pub name: String,
+    pub name: Cow<'a, str>,
     pub repetitions: u64,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub signal_names: Vec<String>,
+    pub signal_names: Vec<Cow<'a, str>>,
 }
 
-impl TryFrom<Pair<'_, Rule>> for SignalGroups {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for SignalGroups<'a> {
     type Error = DbcError;
 
     /// Parse signal group: `SIG_GROUP_ message_id group_name multiplexer_id : signal1 signal2 ... ;`
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::signal_group)?;
 
         let value = Self {
@@ -36,7 +44,22 @@ impl TryFrom<Pair<'_, Rule>> for SignalGroups {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for SignalGroups<'_> {
+    /// Write signal group: `SIG_GROUP_ message_id group_name repetitions : signal1 signal2 ... ;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SIG_GROUP_ {} {} {} :",
+            self.message_id, self.name, self.repetitions
+        )?;
+        for signal_name in &self.signal_names {
+            write!(f, " {signal_name}")?;
+        }
+        f.write_str(";")
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -48,9 +71,9 @@ SIG_GROUP_ 23 X_3290 1 : A_b XY_Z;
 ";
         let exp = SignalGroups {
             message_id: MessageId::Standard(23),
-            name: "X_3290".to_string(),
+            name: "X_3290".into(),
             repetitions: 1,
-            signal_names: vec!["A_b".to_string(), "XY_Z".to_string()],
+            signal_names: vec!["A_b".into(), "XY_Z".into()],
         };
         let val = test_into::<SignalGroups>(def.trim_start(), Rule::signal_group);
         assert_eq!(val, exp);
@@ -1,9 +1,15 @@
-use crate::ast::{ByteOrder, ValueType};
+use alloc::borrow::Cow;
+use core::fmt;
 
+use crate::ast::{ByteOrder, NumericValue, Signal, ValueType};
+use crate::codec::round;
+use crate::writer::Quoted;
+
+/// Signal type (`SGTYPE_`), a template for the properties of signals referring to it with `SIG_TYPE_REF_`
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct SignalType {
-    pub name: String,
+pub struct SignalType<'a> {
+    pub name: Cow<'a, str>,
     pub signal_size: u64,
     pub byte_order: ByteOrder,
     pub value_type: ValueType,
@@ -11,7 +17,118 @@ pub struct SignalType {
     pub offset: f64,
     pub min: f64,
     pub max: f64,
-    pub unit: String,
+    pub unit: Cow<'a, str>,
     pub default_value: f64,
-    pub value_table: String,
+    /// Name of the value table (`VAL_TABLE_`) used by the signals, empty if none
+    pub value_table: Cow<'a, str>,
+}
+
+impl<'a> SignalType<'a> {
+    /// Apply size, byte order, value type, factor, offset, limits and unit of this type to `signal`
+    pub fn apply_to(&self, signal: &mut Signal<'a>) {
+        signal.size = self.signal_size;
+        signal.byte_order = self.byte_order;
+        signal.value_type = self.value_type;
+        signal.factor = self.factor;
+        signal.offset = self.offset;
+        signal.min = numeric(self.min);
+        signal.max = numeric(self.max);
+        signal.unit.clone_from(&self.unit);
+    }
+}
+
+/// Convert a limit to the numeric value the parser would produce for it
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn numeric(value: f64) -> NumericValue {
+    // limits beyond 2^53 are not exactly representable anyway
+    const EXACT: f64 = 9_007_199_254_740_992.0;
+    if value - round(value) != 0.0 || !(-EXACT..=EXACT).contains(&value) {
+        NumericValue::Double(value)
+    } else if value >= 0.0 {
+        NumericValue::Uint(value as u64)
+    } else {
+        NumericValue::Int(value as i64)
+    }
+}
+
+impl fmt::Display for SignalType<'_> {
+    /// Write signal type: `SGTYPE_ name : size@byte_order+/- (factor,offset) [min|max] "unit" default_value, value_table;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SGTYPE_ {} : {}@{}{} ({},{}) [{}|{}] {} {}, {};",
+            self.name,
+            self.signal_size,
+            self.byte_order,
+            self.value_type,
+            self.factor,
+            self.offset,
+            self.min,
+            self.max,
+            Quoted(&self.unit),
+            self.default_value,
+            self.value_table,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use super::*;
+    use crate::MultiplexIndicator;
+
+    fn signal_type() -> SignalType<'static> {
+        SignalType {
+            name: "Temperature".into(),
+            signal_size: 12,
+            byte_order: ByteOrder::BigEndian,
+            value_type: ValueType::Signed,
+            factor: 0.5,
+            offset: -40.0,
+            min: -40.0,
+            max: 87.5,
+            unit: "degC".into(),
+            default_value: 0.0,
+            value_table: "Temperatures".into(),
+        }
+    }
+
+    #[test]
+    fn signal_type_write_test() {
+        assert_eq!(
+            signal_type().to_string(),
+            r#"SGTYPE_ Temperature : 12@0- (0.5,-40) [-40|87.5] "degC" 0, Temperatures;"#
+        );
+    }
+
+    #[test]
+    fn apply_to_test() {
+        let mut signal = Signal {
+            name: "Engine_Temp".into(),
+            multiplexer_indicator: MultiplexIndicator::Plain,
+            start_bit: 7,
+            size: 8,
+            byte_order: ByteOrder::LittleEndian,
+            value_type: ValueType::Unsigned,
+            factor: 1.0,
+            offset: 0.0,
+            min: NumericValue::Uint(0),
+            max: NumericValue::Uint(255),
+            unit: Cow::Borrowed(""),
+            receivers: vec!["ECU".into()],
+        };
+        signal_type().apply_to(&mut signal);
+        assert_eq!(signal.start_bit, 7);
+        assert_eq!(signal.size, 12);
+        assert_eq!(signal.byte_order, ByteOrder::BigEndian);
+        assert_eq!(signal.value_type, ValueType::Signed);
+        assert_eq!((signal.factor, signal.offset), (0.5, -40.0));
+        assert_eq!(signal.min, NumericValue::Int(-40));
+        assert_eq!(signal.max, NumericValue::Double(87.5));
+        assert_eq!(signal.unit, "degC");
+        assert_eq!(signal.receivers, ["ECU"]);
+    }
 }
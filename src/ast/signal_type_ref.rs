@@ -1,9 +1,36 @@
-use crate::ast::MessageId;
+use alloc::borrow::Cow;
+use core::fmt;
 
+use crate::ast::{MessageId, ObjectKey, ObjectRef};
+
+/// Reference from a signal to its signal type (`SIG_TYPE_REF_`)
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct SignalTypeRef {
+pub struct SignalTypeRef<'a> {
     pub message_id: MessageId,
-    pub signal_name: String,
-    pub signal_type_name: String,
+    pub signal_name: Cow<'a, str>,
+    pub signal_type_name: Cow<'a, str>,
+}
+
+impl SignalTypeRef<'_> {
+    /// The signal referring to the signal type
+    #[must_use]
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        ObjectKey::Signal(self.message_id, &self.signal_name)
+    }
+}
+
+impl fmt::Display for SignalTypeRef<'_> {
+    /// Write signal type reference: `SIG_TYPE_REF_ message_id signal_name : signal_type_name;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SIG_TYPE_REF_ {} {} : {};",
+            self.message_id, self.signal_name, self.signal_type_name
+        )
+    }
 }
@@ -1,21 +1,35 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::parser::{validated, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::validated;
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Symbol(pub String);
+pub struct Symbol<'a>(pub Cow<'a, str>);
 
-impl TryFrom<Pair<'_, Rule>> for Symbol {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for Symbol<'a> {
     type Error = DbcError;
 
     /// Parse new symbols: NS_ : symbol1 symbol2 ...
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
-        Ok(Self(validated(value, Rule::ident)?.as_str().to_string()))
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
+        Ok(Self(validated(value, Rule::ident)?.as_str().into()))
+    }
+}
+
+impl fmt::Display for Symbol<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::parser::collect_all;
@@ -30,9 +44,9 @@ NS_ :
     BA_DEF_
 ";
         let exp = vec![
-            Symbol("NS_DESC_".to_string()),
-            Symbol("CM_".to_string()),
-            Symbol("BA_DEF_".to_string()),
+            Symbol("NS_DESC_".into()),
+            Symbol("CM_".into()),
+            Symbol("BA_DEF_".into()),
         ];
         let pair = parse(def.trim_start(), Rule::new_symbols).unwrap();
         let mut pairs = pair.into_inner();
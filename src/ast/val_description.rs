@@ -1,18 +1,25 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
+#[cfg(feature = "std")]
 use crate::parser::{expect_empty, parse_next_inner_str, parse_next_int, validated_inner};
+use crate::writer::Quoted;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct ValDescription {
+pub struct ValDescription<'a> {
     pub id: i64,
-    pub description: String,
+    pub description: Cow<'a, str>,
 }
 
-impl TryFrom<Pair<'_, Rule>> for ValDescription {
-    type Error = crate::parser::DbcError;
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for ValDescription<'a> {
+    type Error = crate::DbcError;
 
-    fn try_from(value: Pair<Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::table_value_description)?;
         let id = parse_next_int(&mut pairs, Rule::int)?;
         let description = parse_next_inner_str(&mut pairs, Rule::quoted_str)?;
@@ -21,7 +28,13 @@ impl TryFrom<Pair<'_, Rule>> for ValDescription {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for ValDescription<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.id, Quoted(&self.description))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -33,9 +46,17 @@ mod tests {
 "#;
         let exp = ValDescription {
             id: 2,
-            description: "ABC".to_string(),
+            description: "ABC".into(),
         };
         let val = test_into::<ValDescription>(def.trim_start(), Rule::table_value_description);
         assert_eq!(val, exp);
     }
+
+    #[test]
+    fn escaped_value_description_test() {
+        let def = r#"1 "5\" \"Display\"""#;
+        let val = test_into::<ValDescription>(def, Rule::table_value_description);
+        assert_eq!(val.description, r#"5" "Display""#);
+        assert_eq!(val.to_string(), def);
+    }
 }
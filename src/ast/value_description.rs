@@ -1,30 +1,56 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::ast::{MessageId, ValDescription};
-use crate::parser::{collect_expected, next_string, validated_inner, DbcError};
+use crate::ast::{MessageId, ObjectKey, ObjectRef, ValDescription};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{collect_expected, next_string, validated_inner};
 
 /// Encoding for signal raw values.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum ValueDescription {
+pub enum ValueDescription<'a> {
     Signal {
         message_id: MessageId,
-        name: String,
+        name: Cow<'a, str>,
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-        value_descriptions: Vec<ValDescription>,
+        value_descriptions: Vec<ValDescription<'a>>,
     },
     EnvironmentVariable {
-        name: String,
+        name: Cow<'a, str>,
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-        value_descriptions: Vec<ValDescription>,
+        value_descriptions: Vec<ValDescription<'a>>,
     },
 }
 
-impl TryFrom<Pair<'_, Rule>> for ValueDescription {
+impl ValueDescription<'_> {
+    /// The signal or environment variable the descriptions belong to
+    #[must_use]
+    pub fn object(&self) -> ObjectRef {
+        self.key().into()
+    }
+
+    pub(crate) fn key(&self) -> ObjectKey<'_> {
+        match self {
+            Self::Signal {
+                message_id, name, ..
+            } => ObjectKey::Signal(*message_id, name),
+            Self::EnvironmentVariable { name, .. } => ObjectKey::EnvironmentVariable(name),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for ValueDescription<'a> {
     type Error = DbcError;
 
     /// Parse value description: `VAL_ message_id signal_name value1 "description1" value2 "description2" ... ;`
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::value_table_def)?;
 
         // Check if first item is message_id (optional)
@@ -34,7 +60,7 @@ impl TryFrom<Pair<'_, Rule>> for ValueDescription {
                 message_id = Some(first_pair.try_into()?);
             } else {
                 // Put it back and treat as signal_name (environment variable case)
-                let name = first_pair.as_str().to_string();
+                let name = first_pair.as_str().into();
                 let value_descriptions =
                     collect_expected(&mut pairs, Rule::table_value_description)?;
                 return Ok(Self::EnvironmentVariable {
@@ -62,7 +88,34 @@ impl TryFrom<Pair<'_, Rule>> for ValueDescription {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for ValueDescription<'_> {
+    /// Write value description: `VAL_ [message_id] name value1 "description1" value2 "description2" ... ;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value_descriptions = match self {
+            Self::Signal {
+                message_id,
+                name,
+                value_descriptions,
+            } => {
+                write!(f, "VAL_ {message_id} {name}")?;
+                value_descriptions
+            }
+            Self::EnvironmentVariable {
+                name,
+                value_descriptions,
+            } => {
+                write!(f, "VAL_ {name}")?;
+                value_descriptions
+            }
+        };
+        for description in value_descriptions {
+            write!(f, " {description}")?;
+        }
+        f.write_str(" ;")
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -74,10 +127,10 @@ VAL_ 837 UF_HZ_OI 255 "NOP";
 "#;
         let exp = ValueDescription::Signal {
             message_id: MessageId::Standard(837),
-            name: "UF_HZ_OI".to_string(),
+            name: "UF_HZ_OI".into(),
             value_descriptions: vec![ValDescription {
                 id: 255,
-                description: "NOP".to_string(),
+                description: "NOP".into(),
             }],
         };
         let val = test_into::<ValueDescription>(def.trim_start(), Rule::value_table_def);
@@ -90,10 +143,10 @@ VAL_ 837 UF_HZ_OI 255 "NOP";
 VAL_ MY_ENV_VAR 255 "NOP";
 "#;
         let exp = ValueDescription::EnvironmentVariable {
-            name: "MY_ENV_VAR".to_string(),
+            name: "MY_ENV_VAR".into(),
             value_descriptions: vec![ValDescription {
                 id: 255,
-                description: "NOP".to_string(),
+                description: "NOP".into(),
             }],
         };
         let val = test_into::<ValueDescription>(def.trim_start(), Rule::value_table_def);
@@ -1,22 +1,31 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
 use crate::ast::ValDescription;
-use crate::parser::{collect_expected, next_string, validated_inner, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{collect_expected, next_string, validated_inner};
 
 /// Global value table
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct ValueTable {
-    pub name: String,
+pub struct ValueTable<'a> {
+    pub name: Cow<'a, str>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
-    pub descriptions: Vec<ValDescription>,
+    pub descriptions: Vec<ValDescription<'a>>,
 }
 
-impl TryFrom<Pair<'_, Rule>> for ValueTable {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for ValueTable<'a> {
     type Error = DbcError;
 
     /// Parse value table: `VAL_TABLE_ table_name value1 "description1" value2 "description2" ... ;`
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let mut pairs = validated_inner(value, Rule::value_table)?;
 
         let name = next_string(&mut pairs, Rule::table_name)?;
@@ -26,7 +35,18 @@ impl TryFrom<Pair<'_, Rule>> for ValueTable {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for ValueTable<'_> {
+    /// Write value table: `VAL_TABLE_ table_name value1 "description1" value2 "description2" ... ;`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VAL_TABLE_ {}", self.name)?;
+        for description in &self.descriptions {
+            write!(f, " {description}")?;
+        }
+        f.write_str(" ;")
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -37,15 +57,15 @@ mod tests {
 VAL_TABLE_ Tst 2 "ABC" 1 "Test A" ;
 "#;
         let exp = ValueTable {
-            name: "Tst".to_string(),
+            name: "Tst".into(),
             descriptions: vec![
                 ValDescription {
                     id: 2,
-                    description: "ABC".to_string(),
+                    description: "ABC".into(),
                 },
                 ValDescription {
                     id: 1,
-                    description: "Test A".to_string(),
+                    description: "Test A".into(),
                 },
             ],
         };
@@ -59,10 +79,10 @@ VAL_TABLE_ Tst 2 "ABC" 1 "Test A" ;
 VAL_TABLE_ Tst 2 "ABC";
 "#;
         let exp = ValueTable {
-            name: "Tst".to_string(),
+            name: "Tst".into(),
             descriptions: vec![ValDescription {
                 id: 2,
-                description: "ABC".to_string(),
+                description: "ABC".into(),
             }],
         };
         let val = test_into::<ValueTable>(def.trim_start(), Rule::value_table);
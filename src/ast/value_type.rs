@@ -1,5 +1,9 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
+#[cfg(feature = "std")]
 use crate::DbcError;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -9,6 +13,7 @@ pub enum ValueType {
     Unsigned,
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<Pair<'_, Rule>> for ValueType {
     type Error = DbcError;
 
@@ -21,7 +26,17 @@ impl TryFrom<Pair<'_, Rule>> for ValueType {
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for ValueType {
+    /// Write the value type as used in a signal definition: `-` for signed, `+` for unsigned
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Signed => "-",
+            Self::Unsigned => "+",
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -1,24 +1,39 @@
+use alloc::borrow::Cow;
+use core::fmt;
+
+#[cfg(feature = "std")]
 use can_dbc_pest::{Pair, Rule};
 
-use crate::parser::{inner_str, single_inner, validated, DbcError};
+#[cfg(feature = "std")]
+use crate::error::DbcError;
+#[cfg(feature = "std")]
+use crate::parser::{inner_str, single_inner, validated};
+use crate::writer::Quoted;
 
 /// Version identifier of the DBC file.
 ///
 /// Format: `VERSION "<VersionIdentifier>"`
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Version(pub String);
+pub struct Version<'a>(pub Cow<'a, str>);
 
-impl TryFrom<Pair<'_, Rule>> for Version {
+#[cfg(feature = "std")]
+impl<'a> TryFrom<Pair<'a, Rule>> for Version<'a> {
     type Error = DbcError;
 
-    fn try_from(value: Pair<'_, Rule>) -> Result<Self, Self::Error> {
+    fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
         let v = single_inner(validated(value, Rule::version)?, Rule::quoted_str)?;
         Ok(Self(inner_str(v)))
     }
 }
 
-#[cfg(test)]
+impl fmt::Display for Version<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VERSION {}", Quoted(&self.0))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::test_helpers::*;
@@ -28,8 +43,7 @@ mod tests {
         let def = r#"
 VERSION "HNPBNNNYNNNNNNNNNNNNNNNNNNNNNNNNYNYYYYYYYY>4>%%%/4>'%**4YYY///"
 "#;
-        let exp =
-            Version("HNPBNNNYNNNNNNNNNNNNNNNNNNNNNNNNYNYYYYYYYY>4>%%%/4>'%**4YYY///".to_string());
+        let exp = Version("HNPBNNNYNNNNNNNNNNNNNNNNNNNNNNNNYNYYYYYYYY>4>%%%/4>'%**4YYY///".into());
         let val = test_into::<Version>(def.trim_start(), Rule::version);
         assert_eq!(val, exp);
     }
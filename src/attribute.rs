@@ -0,0 +1,433 @@
+//!
+//! Attribute values interpreted according to their `BA_DEF_` definitions
+//!
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use crate::ast::{
+    AttributeDefinition, AttributeValue, AttributeValueType, Dbc, MessageId, NumericValue,
+    ObjectRef,
+};
+
+pub type AttributeResult<T> = Result<T, AttributeError>;
+
+/// Error returned when an attribute value does not match its definition
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum AttributeError {
+    #[error("attribute \"{0}\" is not defined")]
+    Undefined(String),
+    #[error("{value} is not a valid {expected} value")]
+    WrongKind {
+        expected: &'static str,
+        value: String,
+    },
+    #[error("{value} is outside the range [{min}|{max}]")]
+    OutOfRange {
+        value: String,
+        min: NumericValue,
+        max: NumericValue,
+    },
+    #[error("{value} is neither an index nor a label of the enum values")]
+    UnknownEnumValue { value: String },
+}
+
+/// Attribute value interpreted according to the value type of its definition
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedAttributeValue<'a> {
+    /// `ENUM` value, given as index or label in the DBC
+    Enum {
+        index: usize,
+        label: &'a str,
+    },
+    Hex(u64),
+    Float(f64),
+    Int(i64),
+    String(&'a str),
+}
+
+impl fmt::Display for TypedAttributeValue<'_> {
+    /// Write the value as shown to users, i.e. enum values by their label and hex values with `0x` prefix
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Enum { label, .. } => f.write_str(label),
+            Self::Hex(v) => write!(f, "0x{v:X}"),
+            Self::Float(v) => write!(f, "{v}"),
+            Self::Int(v) => write!(f, "{v}"),
+            Self::String(v) => f.write_str(v),
+        }
+    }
+}
+
+impl AttributeValueType<'_> {
+    /// Interpret `value` according to this type.
+    ///
+    /// Numeric values are checked against the range of `INT`, `HEX` and `FLOAT` types,
+    /// except for the range `0 0` that is used for attributes without limits.
+    /// Enum values may be given as index or as label.
+    pub fn typed_value<'b>(
+        &'b self,
+        value: &'b AttributeValue<'_>,
+    ) -> AttributeResult<TypedAttributeValue<'b>> {
+        let wrong_kind = |expected| AttributeError::WrongKind {
+            expected,
+            value: value.to_string(),
+        };
+        let typed = match (self, value) {
+            (Self::Int(..), AttributeValue::Uint(v)) => {
+                TypedAttributeValue::Int(i64::try_from(*v).map_err(|_| self.out_of_range(value))?)
+            }
+            (Self::Int(..), AttributeValue::Int(v)) => TypedAttributeValue::Int(*v),
+            (Self::Hex(..), AttributeValue::Uint(v)) => TypedAttributeValue::Hex(*v),
+            (Self::Hex(..), AttributeValue::Int(v)) => {
+                TypedAttributeValue::Hex(u64::try_from(*v).map_err(|_| self.out_of_range(value))?)
+            }
+            (Self::Float(..), AttributeValue::Uint(v)) => {
+                TypedAttributeValue::Float(NumericValue::Uint(*v).as_f64())
+            }
+            (Self::Float(..), AttributeValue::Int(v)) => {
+                TypedAttributeValue::Float(NumericValue::Int(*v).as_f64())
+            }
+            (Self::Float(..), AttributeValue::Double(v)) => TypedAttributeValue::Float(*v),
+            (Self::String, AttributeValue::String(v)) => TypedAttributeValue::String(v),
+            (
+                Self::Enum(labels),
+                AttributeValue::Uint(_) | AttributeValue::Int(_) | AttributeValue::String(_),
+            ) => {
+                let index = match value {
+                    AttributeValue::Uint(v) => usize::try_from(*v).ok(),
+                    AttributeValue::Int(v) => usize::try_from(*v).ok(),
+                    AttributeValue::String(v) => labels.iter().position(|label| label == v),
+                    AttributeValue::Double(_) => None,
+                };
+                let Some((index, label)) =
+                    index.and_then(|index| Some((index, labels.get(index)?)))
+                else {
+                    return Err(AttributeError::UnknownEnumValue {
+                        value: value.to_string(),
+                    });
+                };
+                TypedAttributeValue::Enum { index, label }
+            }
+            (Self::Int(..), _) => return Err(wrong_kind("INT")),
+            (Self::Hex(..), _) => return Err(wrong_kind("HEX")),
+            (Self::Float(..), _) => return Err(wrong_kind("FLOAT")),
+            (Self::String, _) => return Err(wrong_kind("STRING")),
+            (Self::Enum(_), _) => return Err(wrong_kind("ENUM")),
+        };
+        match (self, &typed) {
+            (Self::Int(min, max), TypedAttributeValue::Int(v)) => {
+                self.check_range(*min, *max, NumericValue::Int(*v).as_f64(), value)?;
+            }
+            (Self::Hex(min, max), TypedAttributeValue::Hex(v)) => {
+                self.check_range(*min, *max, NumericValue::Uint(*v).as_f64(), value)?;
+            }
+            (Self::Float(min, max), TypedAttributeValue::Float(v)) => {
+                self.check_range(*min, *max, *v, value)?;
+            }
+            _ => {}
+        }
+        Ok(typed)
+    }
+
+    fn check_range(
+        &self,
+        min: NumericValue,
+        max: NumericValue,
+        v: f64,
+        value: &AttributeValue<'_>,
+    ) -> AttributeResult<()> {
+        let (min, max) = (min.as_f64(), max.as_f64());
+        let unlimited = min.to_bits() == 0.0_f64.to_bits() && max.to_bits() == 0.0_f64.to_bits();
+        if unlimited || (min..=max).contains(&v) {
+            Ok(())
+        } else {
+            Err(self.out_of_range(value))
+        }
+    }
+
+    fn out_of_range(&self, value: &AttributeValue<'_>) -> AttributeError {
+        let (min, max) = match self {
+            Self::Int(min, max) | Self::Hex(min, max) | Self::Float(min, max) => (*min, *max),
+            Self::String | Self::Enum(_) => (NumericValue::Uint(0), NumericValue::Uint(0)),
+        };
+        AttributeError::OutOfRange {
+            value: value.to_string(),
+            min,
+            max,
+        }
+    }
+}
+
+impl<'a> Dbc<'a> {
+    /// Lookup the definition of the attribute `name` for the kind of `object`,
+    /// from `BA_DEF_REL_` if `object` is a relation and from `BA_DEF_` otherwise.
+    ///
+    /// The same name may be defined for several kinds of objects with different types,
+    /// only the definition matching `object` is returned.
+    #[must_use]
+    pub fn attribute_definition_for(
+        &self,
+        object: &ObjectRef,
+        name: &str,
+    ) -> Option<&AttributeDefinition<'a>> {
+        self.definitions(object)
+            .iter()
+            .find(|def| def.name() == name && defined_for(def, object))
+    }
+
+    /// Interpret a value of the attribute `name` of `object` according to the attribute's definition
+    pub fn typed_attribute<'b>(
+        &'b self,
+        object: &ObjectRef,
+        name: &str,
+        value: &'b AttributeValue<'_>,
+    ) -> AttributeResult<TypedAttributeValue<'b>> {
+        let def = self
+            .attribute_definition_for(object, name)
+            .ok_or_else(|| AttributeError::Undefined(name.to_string()))?;
+        def.value_type().typed_value(value)
+    }
+
+    /// Lookup the value of the attribute `name` assigned to `object` by `BA_`, or by `BA_REL_` for relations
+    #[must_use]
+    pub fn attribute(&self, object: &ObjectRef, name: &str) -> Option<&AttributeValue<'_>> {
+        match object {
+            ObjectRef::Database => self
+                .attribute_values_database
+                .iter()
+                .find(|a| a.name == name)
+                .map(|a| &a.value),
+            ObjectRef::Node(node_name) => self
+                .attribute_values_node
+                .iter()
+                .find(|a| a.node_name == *node_name && a.name == name)
+                .map(|a| &a.value),
+            ObjectRef::Message(message_id) => self.message_attribute(*message_id, name),
+            ObjectRef::Signal {
+                message_id,
+                signal_name,
+            } => self.signal_attribute(*message_id, signal_name, name),
+            ObjectRef::EnvironmentVariable(variable_name) => self
+                .attribute_values_env
+                .iter()
+                .find(|a| a.variable_name == *variable_name && a.name == name)
+                .map(|a| &a.value),
+            ObjectRef::NodeToSignal {
+                node_name,
+                message_id,
+                signal_name,
+            } => self.node_signal_attribute(node_name, *message_id, signal_name, name),
+            ObjectRef::NodeToMessage {
+                node_name,
+                message_id,
+            } => self.node_message_attribute(node_name, *message_id, name),
+            ObjectRef::NodeToEnvironmentVariable {
+                node_name,
+                variable_name,
+            } => self.node_environment_variable_attribute(node_name, variable_name, name),
+        }
+    }
+
+    /// Lookup the value of the attribute `name` assigned to `object`.
+    ///
+    /// Falls back to the default `BA_DEF_DEF_`, or `BA_DEF_DEF_REL_` for relations, if no value is assigned.
+    /// The default only applies if the attribute is defined for the kind of `object`,
+    /// or if the attribute has no definition at all.
+    #[must_use]
+    pub fn resolved_attribute(
+        &self,
+        object: &ObjectRef,
+        name: &str,
+    ) -> Option<&AttributeValue<'_>> {
+        self.attribute(object, name)
+            .or_else(|| self.attribute_default(object, name))
+    }
+
+    /// Lookup the default of the attribute `name` for `object`,
+    /// i.e. `BA_DEF_DEF_`, or `BA_DEF_DEF_REL_` for relations.
+    ///
+    /// The default only applies if the attribute is defined for the kind of `object`,
+    /// or if the attribute has no definition at all.
+    #[must_use]
+    pub fn attribute_default(&self, object: &ObjectRef, name: &str) -> Option<&AttributeValue<'_>> {
+        if self.attribute_definition_for(object, name).is_none()
+            && self
+                .definitions(object)
+                .iter()
+                .any(|def| def.name() == name)
+        {
+            return None;
+        }
+        let defaults = if object.is_relation() {
+            &self.relation_attribute_defaults
+        } else {
+            &self.attribute_defaults
+        };
+        defaults.iter().find(|d| d.name == name).map(|d| &d.value)
+    }
+
+    /// Lookup the value of the attribute `name` assigned to `object`, falling back to its default like
+    /// [`Dbc::resolved_attribute`], and interpret it according to the attribute's definition.
+    /// Returns `Ok(None)` if neither a value nor a default applies.
+    pub fn resolved_typed_attribute(
+        &self,
+        object: &ObjectRef,
+        name: &str,
+    ) -> AttributeResult<Option<TypedAttributeValue<'_>>> {
+        let Some(value) = self.resolved_attribute(object, name) else {
+            return Ok(None);
+        };
+        self.typed_attribute(object, name, value).map(Some)
+    }
+
+    /// Lookup a message-level attribute value, falling back to the default `BA_DEF_DEF_`,
+    /// and interpret it according to the attribute's definition.
+    /// Returns `Ok(None)` if neither a value nor a default is defined.
+    pub fn typed_message_attribute(
+        &self,
+        message_id: MessageId,
+        name: &str,
+    ) -> AttributeResult<Option<TypedAttributeValue<'_>>> {
+        self.resolved_typed_attribute(&ObjectRef::Message(message_id), name)
+    }
+
+    /// Lookup a signal-level attribute value, falling back to the default `BA_DEF_DEF_`,
+    /// and interpret it according to the attribute's definition.
+    /// Returns `Ok(None)` if neither a value nor a default is defined.
+    pub fn typed_signal_attribute(
+        &self,
+        message_id: MessageId,
+        signal_name: &str,
+        name: &str,
+    ) -> AttributeResult<Option<TypedAttributeValue<'_>>> {
+        let object = ObjectRef::Signal {
+            message_id,
+            signal_name: signal_name.to_string(),
+        };
+        self.resolved_typed_attribute(&object, name)
+    }
+
+    /// `BA_DEF_REL_` definitions if `object` is a relation, `BA_DEF_` definitions otherwise
+    fn definitions(&self, object: &ObjectRef) -> &[AttributeDefinition<'a>] {
+        if object.is_relation() {
+            &self.relation_attribute_definitions
+        } else {
+            &self.attribute_definitions
+        }
+    }
+}
+
+/// Attribute definitions are identified by the kind of object they are defined for and their name
+pub(crate) fn definition_key(def: &AttributeDefinition) -> (u8, String) {
+    let kind = match def {
+        AttributeDefinition::Plain(..) => 0,
+        AttributeDefinition::Node(..) => 1,
+        AttributeDefinition::Message(..) => 2,
+        AttributeDefinition::Signal(..) => 3,
+        AttributeDefinition::EnvironmentVariable(..) => 4,
+        AttributeDefinition::NodeToSignal(..) => 5,
+        AttributeDefinition::NodeToMessage(..) => 6,
+        AttributeDefinition::NodeToEnvironmentVariable(..) => 7,
+    };
+    (kind, def.name().to_string())
+}
+
+/// Whether `def` defines an attribute for the kind of `object`
+pub(crate) fn defined_for(def: &AttributeDefinition, object: &ObjectRef) -> bool {
+    matches!(
+        (object, def),
+        (ObjectRef::Database, AttributeDefinition::Plain(..))
+            | (ObjectRef::Node(_), AttributeDefinition::Node(..))
+            | (ObjectRef::Message(_), AttributeDefinition::Message(..))
+            | (ObjectRef::Signal { .. }, AttributeDefinition::Signal(..))
+            | (
+                ObjectRef::EnvironmentVariable(_),
+                AttributeDefinition::EnvironmentVariable(..)
+            )
+            | (
+                ObjectRef::NodeToSignal { .. },
+                AttributeDefinition::NodeToSignal(..)
+            )
+            | (
+                ObjectRef::NodeToMessage { .. },
+                AttributeDefinition::NodeToMessage(..)
+            )
+            | (
+                ObjectRef::NodeToEnvironmentVariable { .. },
+                AttributeDefinition::NodeToEnvironmentVariable(..)
+            )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    fn int(min: i64, max: i64) -> AttributeValueType<'static> {
+        AttributeValueType::Int(NumericValue::Int(min), NumericValue::Int(max))
+    }
+
+    #[test]
+    fn numeric_test() {
+        let value = AttributeValue::Uint(100);
+        assert_eq!(
+            int(0, 1000).typed_value(&value),
+            Ok(TypedAttributeValue::Int(100))
+        );
+        assert_eq!(
+            int(0, 0).typed_value(&value),
+            Ok(TypedAttributeValue::Int(100))
+        );
+        let err = int(-10, 10).typed_value(&value).unwrap_err();
+        assert_eq!(err.to_string(), "100 is outside the range [-10|10]");
+
+        let hex = AttributeValueType::Hex(NumericValue::Uint(0), NumericValue::Uint(0x7FF));
+        assert_eq!(hex.typed_value(&value), Ok(TypedAttributeValue::Hex(100)));
+        assert_eq!(TypedAttributeValue::Hex(0x7FF).to_string(), "0x7FF");
+        assert!(hex.typed_value(&AttributeValue::Int(-1)).is_err());
+
+        let float = AttributeValueType::Float(NumericValue::Double(0.5), NumericValue::Uint(2));
+        assert_eq!(
+            float.typed_value(&AttributeValue::Double(1.5)),
+            Ok(TypedAttributeValue::Float(1.5))
+        );
+        assert!(float.typed_value(&value).is_err());
+    }
+
+    #[test]
+    fn wrong_kind_test() {
+        let err = int(0, 0)
+            .typed_value(&AttributeValue::String("fast".into()))
+            .unwrap_err();
+        assert_eq!(err.to_string(), r#""fast" is not a valid INT value"#);
+        let err = AttributeValueType::String
+            .typed_value(&AttributeValue::Double(1.5))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "1.5 is not a valid STRING value");
+    }
+
+    #[test]
+    fn enum_test() {
+        let ty = AttributeValueType::Enum(vec!["Cyclic".into(), "Spontaneous".into()]);
+        let expected = TypedAttributeValue::Enum {
+            index: 1,
+            label: "Spontaneous",
+        };
+        assert_eq!(
+            ty.typed_value(&AttributeValue::Uint(1)),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            ty.typed_value(&AttributeValue::String("Spontaneous".into())),
+            Ok(expected)
+        );
+        let err = ty.typed_value(&AttributeValue::Uint(2)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2 is neither an index nor a label of the enum values"
+        );
+    }
+}
@@ -0,0 +1,625 @@
+//!
+//! Builders to construct a [`Dbc`] in code
+//!
+
+use std::borrow::Cow;
+
+use crate::ast::{
+    numeric, AttributeDefault, AttributeDefinition, AttributeValue, AttributeValueForDatabase,
+    AttributeValueForMessage, AttributeValueForNode, AttributeValueForSignal, ByteOrder, Comment,
+    Dbc, ExtendedMultiplex, ExtendedMultiplexMapping, Message, MessageId, MultiplexIndicator, Node,
+    NumericValue, Signal, ValDescription, ValueDescription, ValueType, Version,
+};
+use crate::validation::{Diagnostic, Severity};
+
+/// Error returned by [`DbcBuilder::build`] if the database has errors
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+#[error("Invalid database: {}", .diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+pub struct BuildError {
+    /// All problems found by [`Dbc::validate`], including warnings
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Builder for a [`Dbc`].
+///
+/// Comments, value descriptions, attribute values and multiplexing details given to the builders
+/// are stored in the corresponding sections of the database, keyed by the object they belong to.
+#[derive(Clone, Debug)]
+pub struct DbcBuilder<'a> {
+    dbc: Dbc<'a>,
+}
+
+impl Default for DbcBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> DbcBuilder<'a> {
+    /// Start an empty database with an empty bit timing section (`BS_:`)
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            dbc: Dbc {
+                bit_timing: Some(Vec::new()),
+                ..Dbc::default()
+            },
+        }
+    }
+
+    #[must_use]
+    pub fn version(mut self, version: impl Into<Cow<'a, str>>) -> Self {
+        self.dbc.version = Version(version.into());
+        self
+    }
+
+    /// Add a node to `BU_`
+    #[must_use]
+    pub fn node(mut self, name: impl Into<Cow<'a, str>>) -> Self {
+        self.dbc.nodes.push(Node(name.into()));
+        self
+    }
+
+    /// Add a comment to a node: `CM_ BU_ node_name "comment";`
+    #[must_use]
+    pub fn node_comment(
+        mut self,
+        node_name: impl Into<Cow<'a, str>>,
+        comment: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.dbc.comments.push(Comment::Node {
+            name: node_name.into(),
+            comment: comment.into(),
+        });
+        self
+    }
+
+    /// Assign an attribute value to a node: `BA_ "name" BU_ node_name value;`
+    #[must_use]
+    pub fn node_attribute(
+        mut self,
+        node_name: impl Into<Cow<'a, str>>,
+        name: impl Into<Cow<'a, str>>,
+        value: AttributeValue<'a>,
+    ) -> Self {
+        self.dbc.attribute_values_node.push(AttributeValueForNode {
+            name: name.into(),
+            node_name: node_name.into(),
+            value,
+        });
+        self
+    }
+
+    /// Add a comment to the database: `CM_ "comment";`
+    #[must_use]
+    pub fn comment(mut self, comment: impl Into<Cow<'a, str>>) -> Self {
+        self.dbc.comments.push(Comment::Plain {
+            comment: comment.into(),
+        });
+        self
+    }
+
+    /// Assign an attribute value to the database: `BA_ "name" value;`
+    #[must_use]
+    pub fn attribute(mut self, name: impl Into<Cow<'a, str>>, value: AttributeValue<'a>) -> Self {
+        self.dbc
+            .attribute_values_database
+            .push(AttributeValueForDatabase {
+                name: name.into(),
+                value,
+            });
+        self
+    }
+
+    /// Define an attribute: `BA_DEF_ [object_type] "name" value_type;`
+    #[must_use]
+    pub fn attribute_definition(mut self, definition: AttributeDefinition<'a>) -> Self {
+        self.dbc.attribute_definitions.push(definition);
+        self
+    }
+
+    /// Set the default value of an attribute: `BA_DEF_DEF_ "name" value;`
+    #[must_use]
+    pub fn attribute_default(
+        mut self,
+        name: impl Into<Cow<'a, str>>,
+        value: AttributeValue<'a>,
+    ) -> Self {
+        self.dbc.attribute_defaults.push(AttributeDefault {
+            name: name.into(),
+            value,
+        });
+        self
+    }
+
+    /// Add a message with its signals, and store their comments, value descriptions,
+    /// attribute values and multiplexing details in the database
+    #[must_use]
+    pub fn message(mut self, builder: MessageBuilder<'a>) -> Self {
+        self.add_message(builder);
+        self
+    }
+
+    /// Finish the database, checking it with [`Dbc::validate`].
+    ///
+    /// Fails if an error is reported, e.g. if signals overlap or a signal is multiplexed by an unknown signal.
+    /// Warnings, e.g. a transmitter or receiver that is not added with [`DbcBuilder::node`]
+    /// or an attribute that is not defined, are returned alongside the database.
+    pub fn build(self) -> Result<(Dbc<'a>, Vec<Diagnostic>), BuildError> {
+        let diagnostics = self.dbc.validate();
+        if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+            Err(BuildError { diagnostics })
+        } else {
+            Ok((self.dbc, diagnostics))
+        }
+    }
+
+    fn add_message(&mut self, builder: MessageBuilder<'a>) {
+        let MessageBuilder {
+            mut message,
+            signals,
+            comment,
+            attributes,
+        } = builder;
+        let id = message.id;
+        if let Some(comment) = comment {
+            self.dbc.comments.push(Comment::Message { id, comment });
+        }
+        for (name, value) in attributes {
+            self.dbc
+                .attribute_values_message
+                .push(AttributeValueForMessage {
+                    name,
+                    message_id: id,
+                    value,
+                });
+        }
+
+        let mut switches: Vec<Cow<'a, str>> = Vec::new();
+        for signal in &signals {
+            let own = signal.multiplexor.then_some(&signal.signal.name);
+            let parent = signal.multiplexed_by.as_ref().map(|(name, _)| name);
+            for name in own.into_iter().chain(parent) {
+                if !switches.contains(name) {
+                    switches.push(name.clone());
+                }
+            }
+        }
+        let known = |name: &Cow<'a, str>| signals.iter().any(|s| s.signal.name == *name);
+        let unknown_switch = switches.iter().any(|name| !known(name));
+
+        let mut resolved = Vec::with_capacity(signals.len());
+        for builder in &signals {
+            let mut signal = builder.signal.clone();
+            let is_switch = switches.contains(&signal.name);
+            signal.multiplexer_indicator = match (&builder.multiplexed_by, is_switch) {
+                (None, false) => MultiplexIndicator::Plain,
+                (None, true) => MultiplexIndicator::Multiplexor,
+                (Some((_, values)), false) => MultiplexIndicator::MultiplexedSignal(values[0]),
+                (Some((_, values)), true) => {
+                    MultiplexIndicator::MultiplexorAndMultiplexedSignal(values[0])
+                }
+            };
+            if let Some((multiplexor, values)) = &builder.multiplexed_by {
+                // a single multiplexor switch selecting by a single value needs no `SG_MUL_VAL_`
+                if switches.len() > 1 || values.len() > 1 || unknown_switch {
+                    self.dbc.extended_multiplex.push(ExtendedMultiplex {
+                        message_id: id,
+                        signal_name: signal.name.clone(),
+                        multiplexor_signal_name: multiplexor.clone(),
+                        mappings: mappings(values),
+                    });
+                }
+            }
+            if let Some(comment) = &builder.comment {
+                self.dbc.comments.push(Comment::Signal {
+                    message_id: id,
+                    name: signal.name.clone(),
+                    comment: comment.clone(),
+                });
+            }
+            if !builder.value_descriptions.is_empty() {
+                self.dbc.value_descriptions.push(ValueDescription::Signal {
+                    message_id: id,
+                    name: signal.name.clone(),
+                    value_descriptions: builder.value_descriptions.clone(),
+                });
+            }
+            for (name, value) in &builder.attributes {
+                self.dbc
+                    .attribute_values_signal
+                    .push(AttributeValueForSignal {
+                        name: name.clone(),
+                        message_id: id,
+                        signal_name: signal.name.clone(),
+                        value: value.clone(),
+                    });
+            }
+            resolved.push(signal);
+        }
+        message.signals = resolved;
+        self.dbc.messages.push(message);
+    }
+}
+
+/// Merge multiplexor values into as few ranges as possible
+fn mappings(values: &[u64]) -> Vec<ExtendedMultiplexMapping> {
+    let mut values = values.to_vec();
+    values.sort_unstable();
+    values.dedup();
+    let mut mappings: Vec<ExtendedMultiplexMapping> = Vec::new();
+    for value in values {
+        match mappings.last_mut() {
+            Some(last) if last.max_value.checked_add(1) == Some(value) => last.max_value = value,
+            _ => mappings.push(ExtendedMultiplexMapping {
+                min_value: value,
+                max_value: value,
+            }),
+        }
+    }
+    mappings
+}
+
+/// Builder for a [`Message`] added with [`DbcBuilder::message`]
+#[derive(Clone, Debug)]
+pub struct MessageBuilder<'a> {
+    message: Message<'a>,
+    signals: Vec<SignalBuilder<'a>>,
+    comment: Option<Cow<'a, str>>,
+    attributes: Vec<(Cow<'a, str>, AttributeValue<'a>)>,
+}
+
+impl<'a> MessageBuilder<'a> {
+    /// Start a message of `size` bytes without transmitter and signals
+    #[must_use]
+    pub fn new(id: MessageId, name: impl Into<Cow<'a, str>>, size: u64) -> Self {
+        Self {
+            message: Message {
+                id,
+                name: name.into(),
+                size,
+                transmitter: None,
+                signals: Vec::new(),
+            },
+            signals: Vec::new(),
+            comment: None,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Set the transmitting node
+    #[must_use]
+    pub fn transmitter(mut self, node_name: impl Into<Cow<'a, str>>) -> Self {
+        self.message.transmitter = Some(node_name.into());
+        self
+    }
+
+    /// Add a signal
+    #[must_use]
+    pub fn signal(mut self, signal: SignalBuilder<'a>) -> Self {
+        self.signals.push(signal);
+        self
+    }
+
+    /// Set the message comment: `CM_ BO_ message_id "comment";`
+    #[must_use]
+    pub fn comment(mut self, comment: impl Into<Cow<'a, str>>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Assign an attribute value to the message: `BA_ "name" BO_ message_id value;`
+    #[must_use]
+    pub fn attribute(mut self, name: impl Into<Cow<'a, str>>, value: AttributeValue<'a>) -> Self {
+        self.attributes.push((name.into(), value));
+        self
+    }
+}
+
+/// Builder for a [`Signal`] added with [`MessageBuilder::signal`].
+///
+/// Signals are unsigned little endian (Intel) signals with factor 1, offset 0 and range `[0|0]` by default.
+#[derive(Clone, Debug)]
+pub struct SignalBuilder<'a> {
+    signal: Signal<'a>,
+    multiplexor: bool,
+    multiplexed_by: Option<(Cow<'a, str>, Vec<u64>)>,
+    comment: Option<Cow<'a, str>>,
+    value_descriptions: Vec<ValDescription<'a>>,
+    attributes: Vec<(Cow<'a, str>, AttributeValue<'a>)>,
+}
+
+impl<'a> SignalBuilder<'a> {
+    /// Start a signal of `size` bits starting at `start_bit`
+    #[must_use]
+    pub fn new(name: impl Into<Cow<'a, str>>, start_bit: u64, size: u64) -> Self {
+        Self {
+            signal: Signal {
+                name: name.into(),
+                multiplexer_indicator: MultiplexIndicator::Plain,
+                start_bit,
+                size,
+                byte_order: ByteOrder::LittleEndian,
+                value_type: ValueType::Unsigned,
+                factor: 1.0,
+                offset: 0.0,
+                min: NumericValue::Uint(0),
+                max: NumericValue::Uint(0),
+                unit: Cow::Borrowed(""),
+                receivers: Vec::new(),
+            },
+            multiplexor: false,
+            multiplexed_by: None,
+            comment: None,
+            value_descriptions: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.signal.byte_order = byte_order;
+        self
+    }
+
+    #[must_use]
+    pub fn value_type(mut self, value_type: ValueType) -> Self {
+        self.signal.value_type = value_type;
+        self
+    }
+
+    /// Set the scaling of the raw value: `physical = raw * factor + offset`
+    #[must_use]
+    pub fn scaling(mut self, factor: f64, offset: f64) -> Self {
+        self.signal.factor = factor;
+        self.signal.offset = offset;
+        self
+    }
+
+    /// Set the range of the physical value
+    #[must_use]
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.signal.min = numeric(min);
+        self.signal.max = numeric(max);
+        self
+    }
+
+    #[must_use]
+    pub fn unit(mut self, unit: impl Into<Cow<'a, str>>) -> Self {
+        self.signal.unit = unit.into();
+        self
+    }
+
+    /// Add a receiving node
+    #[must_use]
+    pub fn receiver(mut self, node_name: impl Into<Cow<'a, str>>) -> Self {
+        self.signal.receivers.push(node_name.into());
+        self
+    }
+
+    /// Mark the signal as a multiplexor switch.
+    /// Signals referenced by [`SignalBuilder::multiplexed_by`] are marked automatically.
+    #[must_use]
+    pub fn multiplexor(mut self) -> Self {
+        self.multiplexor = true;
+        self
+    }
+
+    /// Make the signal active only if the multiplexor switch `multiplexor` has the given value.
+    ///
+    /// Call it multiple times to select the signal by several values. `SG_MUL_VAL_` entries are added
+    /// as needed, i.e. if the message has several multiplexor switches or the signal is selected by several values.
+    #[must_use]
+    pub fn multiplexed_by(mut self, multiplexor: impl Into<Cow<'a, str>>, value: u64) -> Self {
+        let multiplexor = multiplexor.into();
+        match &mut self.multiplexed_by {
+            Some((name, values)) if *name == multiplexor => values.push(value),
+            _ => self.multiplexed_by = Some((multiplexor, vec![value])),
+        }
+        self
+    }
+
+    /// Set the signal comment: `CM_ SG_ message_id signal_name "comment";`
+    #[must_use]
+    pub fn comment(mut self, comment: impl Into<Cow<'a, str>>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Add descriptions of raw values: `VAL_ message_id signal_name value "description" ...;`
+    #[must_use]
+    pub fn value_descriptions<I, S>(mut self, descriptions: I) -> Self
+    where
+        I: IntoIterator<Item = (i64, S)>,
+        S: Into<Cow<'a, str>>,
+    {
+        self.value_descriptions
+            .extend(
+                descriptions
+                    .into_iter()
+                    .map(|(id, description)| ValDescription {
+                        id,
+                        description: description.into(),
+                    }),
+            );
+        self
+    }
+
+    /// Assign an attribute value to the signal: `BA_ "name" SG_ message_id signal_name value;`
+    #[must_use]
+    pub fn attribute(mut self, name: impl Into<Cow<'a, str>>, value: AttributeValue<'a>) -> Self {
+        self.attributes.push((name.into(), value));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::AttributeValueType;
+    use crate::DiagnosticCode;
+
+    fn engine() -> MessageBuilder<'static> {
+        MessageBuilder::new(MessageId::Standard(100), "Engine", 8)
+            .transmitter("ECU")
+            .comment("Engine status")
+            .attribute("GenMsgCycleTime", AttributeValue::Uint(100))
+            .signal(
+                SignalBuilder::new("Speed", 0, 16)
+                    .scaling(0.1, 0.0)
+                    .range(0.0, 6553.5)
+                    .unit("rpm")
+                    .receiver("Dashboard")
+                    .comment("Engine speed"),
+            )
+            .signal(
+                SignalBuilder::new("State", 16, 2)
+                    .receiver("Dashboard")
+                    .value_descriptions([(0, "Off"), (1, "Running")])
+                    .attribute("GenSigStartValue", AttributeValue::Uint(0)),
+            )
+    }
+
+    fn builder() -> DbcBuilder<'static> {
+        DbcBuilder::new()
+            .node("ECU")
+            .node("Dashboard")
+            .attribute_definition(AttributeDefinition::Message(
+                "GenMsgCycleTime".into(),
+                AttributeValueType::Int(NumericValue::Uint(0), NumericValue::Uint(10000)),
+            ))
+            .attribute_definition(AttributeDefinition::Signal(
+                "GenSigStartValue".into(),
+                AttributeValueType::Int(NumericValue::Uint(0), NumericValue::Uint(10000)),
+            ))
+            .attribute_default("GenMsgCycleTime", AttributeValue::Uint(0))
+    }
+
+    #[test]
+    fn build_test() {
+        let dbc = builder().message(engine()).build().unwrap().0;
+        let id = MessageId::Standard(100);
+        assert_eq!(dbc.message_comment(id), Some("Engine status"));
+        assert_eq!(dbc.signal_comment(id, "Speed"), Some("Engine speed"));
+        assert_eq!(
+            dbc.value_descriptions_for_signal(id, "State").unwrap(),
+            [
+                ValDescription {
+                    id: 0,
+                    description: "Off".into()
+                },
+                ValDescription {
+                    id: 1,
+                    description: "Running".into()
+                }
+            ]
+        );
+        assert_eq!(
+            dbc.message_attribute(id, "GenMsgCycleTime"),
+            Some(&AttributeValue::Uint(100))
+        );
+        assert_eq!(
+            dbc.signal_attribute(id, "State", "GenSigStartValue"),
+            Some(&AttributeValue::Uint(0))
+        );
+        let speed = dbc.signal_by_name(id, "Speed").unwrap();
+        assert_eq!(speed.max, NumericValue::Double(6553.5));
+
+        let text = dbc.to_string();
+        assert_eq!(Dbc::try_from(text.as_str()).unwrap(), dbc);
+    }
+
+    #[test]
+    fn multiplexed_by_test() {
+        let message = MessageBuilder::new(MessageId::Standard(1), "Mux", 8)
+            .signal(SignalBuilder::new("Selector", 0, 8))
+            .signal(SignalBuilder::new("A", 8, 8).multiplexed_by("Selector", 0))
+            .signal(
+                SignalBuilder::new("B", 8, 8)
+                    .multiplexed_by("Selector", 1)
+                    .multiplexed_by("Selector", 3)
+                    .multiplexed_by("Selector", 2),
+            );
+        let dbc = DbcBuilder::new().message(message).build().unwrap().0;
+        let indicators: Vec<_> = dbc.messages[0]
+            .signals
+            .iter()
+            .map(|s| s.multiplexer_indicator)
+            .collect();
+        assert_eq!(
+            indicators,
+            [
+                MultiplexIndicator::Multiplexor,
+                MultiplexIndicator::MultiplexedSignal(0),
+                MultiplexIndicator::MultiplexedSignal(1),
+            ]
+        );
+        assert_eq!(
+            dbc.extended_multiplex,
+            [ExtendedMultiplex {
+                message_id: MessageId::Standard(1),
+                signal_name: "B".into(),
+                multiplexor_signal_name: "Selector".into(),
+                mappings: vec![ExtendedMultiplexMapping {
+                    min_value: 1,
+                    max_value: 3,
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn nested_multiplexor_test() {
+        let message = MessageBuilder::new(MessageId::Standard(1), "Mux", 8)
+            .signal(SignalBuilder::new("Outer", 0, 4))
+            .signal(SignalBuilder::new("Inner", 4, 4).multiplexed_by("Outer", 1))
+            .signal(SignalBuilder::new("A", 8, 8).multiplexed_by("Inner", 2));
+        let dbc = DbcBuilder::new().message(message).build().unwrap().0;
+        let signals = &dbc.messages[0].signals;
+        assert_eq!(
+            signals[1].multiplexer_indicator,
+            MultiplexIndicator::MultiplexorAndMultiplexedSignal(1)
+        );
+        assert_eq!(dbc.extended_multiplex.len(), 2);
+        assert_eq!(dbc.extended_multiplex[1].multiplexor_signal_name, "Inner");
+    }
+
+    #[test]
+    fn dangling_references_test() {
+        let message = engine().signal(SignalBuilder::new("A", 24, 8).multiplexed_by("Missing", 0));
+        let err = DbcBuilder::new().message(message).build().unwrap_err();
+        let codes: Vec<_> = err.diagnostics.iter().map(|d| d.code).collect();
+        assert_eq!(
+            codes,
+            [
+                DiagnosticCode::UnknownMultiplexor,
+                DiagnosticCode::UnknownNode,
+                DiagnosticCode::UnknownNode,
+                DiagnosticCode::UnknownNode,
+                DiagnosticCode::UndefinedAttribute,
+                DiagnosticCode::UndefinedAttribute,
+            ]
+        );
+        assert!(err
+            .to_string()
+            .starts_with("Invalid database: error[unknown-multiplexor] signal A of message 100"));
+    }
+
+    #[test]
+    fn warnings_test() {
+        let (dbc, warnings) = DbcBuilder::new().message(engine()).build().unwrap();
+        assert_eq!(dbc.messages.len(), 1);
+        let codes: Vec<_> = warnings.iter().map(|d| d.code).collect();
+        assert_eq!(
+            codes,
+            [
+                DiagnosticCode::UnknownNode,
+                DiagnosticCode::UnknownNode,
+                DiagnosticCode::UnknownNode,
+                DiagnosticCode::UndefinedAttribute,
+                DiagnosticCode::UndefinedAttribute,
+            ]
+        );
+        assert!(warnings.iter().all(|d| d.severity == Severity::Warning));
+    }
+}
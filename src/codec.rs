@@ -0,0 +1,358 @@
+//!
+//! Encoding and decoding of signal values in CAN frame payloads
+//!
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::ast::{ByteOrder, Message, MessageId, NumericValue, Signal};
+
+pub type CodecResult<T> = Result<T, CodecError>;
+
+/// Error type for encoding and decoding CAN frame payloads
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CodecError {
+    #[error("Unknown message {0}")]
+    UnknownMessage(MessageId),
+    #[error("Signal {signal} does not fit into a payload of {payload_size} bytes")]
+    SignalOutOfBounds { signal: String, payload_size: usize },
+    #[error("Signal {signal} has an unsupported size of {size} bits")]
+    UnsupportedSignalSize { signal: String, size: u64 },
+    #[error("Message {message} has an unsupported size of {size} bytes")]
+    UnsupportedMessageSize { message: String, size: u64 },
+    #[error("Unknown signal {signal} in message {message}")]
+    UnknownSignal { message: String, signal: String },
+    #[error("Value {value} of signal {signal} is below the minimum {min}")]
+    BelowMinimum {
+        signal: String,
+        value: f64,
+        min: NumericValue,
+    },
+    #[error("Value {value} of signal {signal} is above the maximum {max}")]
+    AboveMaximum {
+        signal: String,
+        value: f64,
+        max: NumericValue,
+    },
+    #[error("Raw value {raw} of signal {signal} does not fit into {size} bits")]
+    Overflow {
+        signal: String,
+        raw: NumericValue,
+        size: u64,
+    },
+    #[error("Multiplexor of signal {signal} in message {message} cannot be resolved")]
+    UnresolvedMultiplexor { message: String, signal: String },
+}
+
+/// A single signal value decoded from a CAN frame payload
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedSignal<'a> {
+    pub signal: &'a Signal<'a>,
+    /// Raw value as stored in the payload
+    pub raw: NumericValue,
+    /// Physical value, i.e. `raw * factor + offset`
+    pub physical: f64,
+}
+
+/// Signals of a multiplexed CAN frame payload, decoded according to the active multiplexor branch
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedMessage<'a> {
+    pub message: &'a Message<'a>,
+    /// Values of all active signals, in the order they are defined in the message
+    pub signals: Vec<DecodedSignal<'a>>,
+    /// The branch taken, i.e. the value of each active multiplexor, outermost multiplexor first
+    pub branch: Vec<MultiplexBranch<'a>>,
+}
+
+impl<'a> DecodedMessage<'a> {
+    /// Lookup the decoded value of an active signal by name
+    #[must_use]
+    pub fn signal(&self, name: &str) -> Option<&DecodedSignal<'a>> {
+        self.signals.iter().find(|s| s.signal.name == name)
+    }
+}
+
+/// Value of an active multiplexor switch
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiplexBranch<'a> {
+    pub multiplexor: &'a Signal<'a>,
+    pub value: u64,
+}
+
+/// Read a single bit of the payload, using the DBC bit numbering (bit 0 is the LSB of byte 0)
+fn bit(data: &[u8], pos: u64) -> Option<u64> {
+    let byte = data.get(usize::try_from(pos / 8).ok()?)?;
+    Some(u64::from((byte >> (pos % 8)) & 1))
+}
+
+/// Position of the next less significant bit of a big endian (Motorola) signal, `None` on overflow
+fn next_big_endian_pos(pos: u64) -> Option<u64> {
+    if pos % 8 == 0 {
+        pos.checked_add(15)
+    } else {
+        Some(pos - 1)
+    }
+}
+
+/// Positions of all bits of a signal, most significant bit first for big endian (Motorola) signals
+/// and least significant bit first for little endian (Intel) signals.
+/// Positions that would overflow `u64` are `None`.
+#[cfg(feature = "std")]
+pub(crate) fn bit_positions(
+    start_bit: u64,
+    size: u64,
+    byte_order: ByteOrder,
+) -> impl Iterator<Item = Option<u64>> {
+    let mut pos = Some(start_bit);
+    (0..size).map(move |idx| match byte_order {
+        ByteOrder::LittleEndian => start_bit.checked_add(idx),
+        ByteOrder::BigEndian => {
+            let current = pos;
+            pos = pos.and_then(next_big_endian_pos);
+            current
+        }
+    })
+}
+
+/// Extract `size` bits (at most 64) starting at `start_bit` from the payload.
+///
+/// For little endian (Intel) signals `start_bit` is the least significant bit,
+/// for big endian (Motorola) signals it is the most significant bit.
+/// Returns `None` if any of the bits is outside the payload.
+pub(crate) fn extract_bits(
+    data: &[u8],
+    start_bit: u64,
+    size: u64,
+    byte_order: ByteOrder,
+) -> Option<u64> {
+    let mut value = 0;
+    match byte_order {
+        ByteOrder::LittleEndian => {
+            for idx in 0..size {
+                value |= bit(data, start_bit.checked_add(idx)?)? << idx;
+            }
+        }
+        ByteOrder::BigEndian => {
+            let mut pos = Some(start_bit);
+            for _ in 0..size {
+                let current = pos?;
+                value = (value << 1) | bit(data, current)?;
+                pos = next_big_endian_pos(current);
+            }
+        }
+    }
+    Some(value)
+}
+
+/// Store the lowest `size` bits of `value` at `start_bit` in the payload,
+/// using the same bit numbering as [`extract_bits`].
+/// Returns `None` without modifying the payload if any of the bits is outside of it.
+pub(crate) fn insert_bits(
+    data: &mut [u8],
+    start_bit: u64,
+    size: u64,
+    byte_order: ByteOrder,
+    value: u64,
+) -> Option<()> {
+    // bounds check before modifying anything
+    extract_bits(data, start_bit, size, byte_order)?;
+
+    let mut set_bit = |pos: u64, bit: u64| {
+        // positions were validated above
+        let byte = &mut data[usize::try_from(pos / 8).expect("position checked")];
+        let mask = 1 << (pos % 8);
+        if bit == 0 {
+            *byte &= !mask;
+        } else {
+            *byte |= mask;
+        }
+    };
+    match byte_order {
+        ByteOrder::LittleEndian => {
+            for idx in 0..size {
+                set_bit(start_bit + idx, (value >> idx) & 1);
+            }
+        }
+        ByteOrder::BigEndian => {
+            let mut pos = start_bit;
+            for idx in (0..size).rev() {
+                set_bit(pos, (value >> idx) & 1);
+                // the last position may overflow, it is never used
+                pos = next_big_endian_pos(pos).unwrap_or(pos);
+            }
+        }
+    }
+    Some(())
+}
+
+/// Sign-extend a two's complement value of `size` bits to 64 bits
+#[expect(clippy::cast_possible_wrap)]
+pub(crate) fn sign_extend(value: u64, size: u64) -> i64 {
+    if size == 0 || size >= 64 {
+        value as i64
+    } else if value >> (size - 1) & 1 == 1 {
+        (value | u64::MAX << size) as i64
+    } else {
+        value as i64
+    }
+}
+
+/// Round half away from zero like `f64::round`, which is not available in `core`
+#[expect(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub(crate) fn round(value: f64) -> f64 {
+    // values beyond 2^52 have no fractional part, NaN and infinities are returned as is
+    const INTEGRAL: f64 = 4_503_599_627_370_496.0;
+    if !(-INTEGRAL < value && value < INTEGRAL) {
+        return value;
+    }
+    let truncated = value as i64 as f64;
+    let fract = value - truncated;
+    if fract >= 0.5 {
+        truncated + 1.0
+    } else if fract <= -0.5 {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_little_endian_test() {
+        let data = [0x12, 0x34, 0x56, 0x78];
+        assert_eq!(
+            extract_bits(&data, 0, 8, ByteOrder::LittleEndian),
+            Some(0x12)
+        );
+        assert_eq!(
+            extract_bits(&data, 4, 12, ByteOrder::LittleEndian),
+            Some(0x341)
+        );
+        assert_eq!(
+            extract_bits(&data, 0, 32, ByteOrder::LittleEndian),
+            Some(0x7856_3412)
+        );
+        assert_eq!(extract_bits(&data, 30, 4, ByteOrder::LittleEndian), None);
+    }
+
+    #[test]
+    fn extract_big_endian_test() {
+        let data = [0x12, 0x34, 0x56, 0x78];
+        assert_eq!(extract_bits(&data, 7, 8, ByteOrder::BigEndian), Some(0x12));
+        assert_eq!(
+            extract_bits(&data, 3, 12, ByteOrder::BigEndian),
+            Some(0x234)
+        );
+        assert_eq!(
+            extract_bits(&data, 7, 32, ByteOrder::BigEndian),
+            Some(0x1234_5678)
+        );
+        assert_eq!(extract_bits(&data, 1, 2, ByteOrder::BigEndian), Some(0x2));
+        assert_eq!(extract_bits(&data, 31, 8, ByteOrder::BigEndian), Some(0x78));
+        assert_eq!(extract_bits(&data, 31, 9, ByteOrder::BigEndian), None);
+    }
+
+    #[test]
+    fn extract_64_bit_test() {
+        let data = [0xFF; 8];
+        assert_eq!(
+            extract_bits(&data, 0, 64, ByteOrder::LittleEndian),
+            Some(u64::MAX)
+        );
+        assert_eq!(
+            extract_bits(&data, 7, 64, ByteOrder::BigEndian),
+            Some(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn insert_bits_test() {
+        for (start_bit, size, byte_order) in [
+            (4, 12, ByteOrder::LittleEndian),
+            (3, 12, ByteOrder::BigEndian),
+            (7, 32, ByteOrder::BigEndian),
+            (0, 32, ByteOrder::LittleEndian),
+        ] {
+            let mut data = [0xAA; 4];
+            let value = 0x0BAD_CAFE & (u64::MAX >> (64 - size));
+            insert_bits(&mut data, start_bit, size, byte_order, value).unwrap();
+            assert_eq!(
+                extract_bits(&data, start_bit, size, byte_order),
+                Some(value)
+            );
+        }
+
+        // bits outside of the signal are left untouched
+        let mut data = [0xFF, 0xFF];
+        insert_bits(&mut data, 3, 8, ByteOrder::BigEndian, 0).unwrap();
+        assert_eq!(data, [0xF0, 0x0F]);
+
+        let mut data = [0xFF];
+        assert_eq!(
+            insert_bits(&mut data, 4, 8, ByteOrder::LittleEndian, 0),
+            None
+        );
+        assert_eq!(data, [0xFF]);
+    }
+
+    #[test]
+    fn start_bit_overflow_test() {
+        let mut data = [0xFF; 8];
+        for byte_order in [ByteOrder::LittleEndian, ByteOrder::BigEndian] {
+            for start_bit in [u64::MAX, u64::MAX - 7, u64::MAX - 8] {
+                assert_eq!(extract_bits(&data, start_bit, 16, byte_order), None);
+                assert_eq!(insert_bits(&mut data, start_bit, 16, byte_order, 0), None);
+            }
+        }
+        assert_eq!(data, [0xFF; 8]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn bit_positions_test() {
+        let positions: Vec<_> = bit_positions(3, 6, ByteOrder::LittleEndian)
+            .map(Option::unwrap)
+            .collect();
+        assert_eq!(positions, [3, 4, 5, 6, 7, 8]);
+        let positions: Vec<_> = bit_positions(1, 4, ByteOrder::BigEndian)
+            .map(Option::unwrap)
+            .collect();
+        assert_eq!(positions, [1, 0, 15, 14]);
+        let positions: Vec<_> = bit_positions(u64::MAX, 2, ByteOrder::LittleEndian).collect();
+        assert_eq!(positions, [Some(u64::MAX), None]);
+    }
+
+    #[test]
+    fn sign_extend_test() {
+        assert_eq!(sign_extend(0b111, 3), -1);
+        assert_eq!(sign_extend(0b100, 3), -4);
+        assert_eq!(sign_extend(0b011, 3), 3);
+        assert_eq!(sign_extend(u64::MAX, 64), -1);
+        assert_eq!(sign_extend(0x80, 8), -128);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[expect(clippy::float_cmp)]
+    fn round_test() {
+        for value in [
+            0.0,
+            0.4,
+            0.5,
+            1.5,
+            2.5,
+            -0.5,
+            -1.4,
+            -2.5,
+            1e17,
+            0.499_999_999_999_999_94,
+        ] {
+            assert_eq!(round(value), value.round(), "{value}");
+        }
+        assert!(round(f64::NAN).is_nan());
+        assert_eq!(round(f64::INFINITY), f64::INFINITY);
+    }
+}
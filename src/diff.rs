@@ -0,0 +1,962 @@
+//!
+//! Semantic comparison of two versions of a database
+//!
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::ast::{
+    AttributeDefault, Dbc, Message, MessageId, NumericValue, ObjectRef, Signal,
+    SignalExtendedValueType,
+};
+use crate::attribute::definition_key;
+use crate::writer::Quoted;
+
+/// Changes between two versions of a database, see [`Dbc::diff`].
+///
+/// Displaying the diff writes a report with one line per change.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DbcDiff {
+    pub changes: Vec<Change>,
+}
+
+impl DbcDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether any change breaks receivers of the old version
+    #[must_use]
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|change| change.breaking)
+    }
+
+    /// Changes that break receivers of the old version
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+}
+
+impl fmt::Display for DbcDiff {
+    /// Write a summary line followed by one line per change
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+        writeln!(
+            f,
+            "{} changes, {} breaking",
+            self.changes.len(),
+            self.breaking_changes().count()
+        )?;
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A single change of an object
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    /// The changed object. Messages are matched by id, signals by name within their message.
+    pub object: ObjectRef,
+    pub kind: ChangeKind,
+    /// Whether receivers built for the old version decode the new version incorrectly or fail
+    pub breaking: bool,
+}
+
+impl fmt::Display for Change {
+    /// Write the change as `breaking: object: description` or `non-breaking: object: description`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let impact = if self.breaking {
+            "breaking"
+        } else {
+            "non-breaking"
+        };
+        write!(f, "{impact}: {}: {}", self.object, self.kind)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChangeKind {
+    /// The object only exists in the new version
+    Added,
+    /// The object only exists in the old version
+    Removed,
+    /// The message name changed
+    Renamed {
+        old: String,
+        new: String,
+    },
+    /// The message size changed
+    SizeChanged {
+        old: u64,
+        new: u64,
+    },
+    TransmitterChanged {
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// Start bit, size, byte order, value type, extended value type or multiplexing of a signal changed,
+    /// written as e.g. `m1 8|16@1+`, followed by `SG_MUL_VAL_` entries as e.g. `by Mode 1-1,3-5`
+    LayoutChanged {
+        old: String,
+        new: String,
+    },
+    /// Factor or offset of a signal changed
+    ScalingChanged {
+        old: (f64, f64),
+        new: (f64, f64),
+    },
+    RangeChanged {
+        old: (NumericValue, NumericValue),
+        new: (NumericValue, NumericValue),
+    },
+    UnitChanged {
+        old: String,
+        new: String,
+    },
+    ReceiversChanged {
+        old: Vec<String>,
+        new: Vec<String>,
+    },
+    /// Raw values whose description (`VAL_`) was added, removed or changed
+    ValueDescriptionsChanged {
+        added: Vec<i64>,
+        removed: Vec<i64>,
+        changed: Vec<i64>,
+    },
+    /// An attribute value was assigned, removed or changed
+    AttributeChanged {
+        name: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// A comment was added, removed or changed
+    CommentChanged {
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// The definition of an environment variable (`EV_` and `ENVVAR_DATA_`) changed, written as DBC text
+    DefinitionChanged {
+        old: String,
+        new: String,
+    },
+    /// A value table (`VAL_TABLE_`) was added, removed or changed, written as DBC text
+    ValueTableChanged {
+        name: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// An attribute definition (`BA_DEF_` or `BA_DEF_REL_`) was added, removed or changed, written as DBC text
+    AttributeDefinitionChanged {
+        name: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// An attribute default (`BA_DEF_DEF_` or `BA_DEF_DEF_REL_`) was added, removed or changed, written as DBC text.
+    /// This changes the resolved value of all objects without an assigned value.
+    AttributeDefaultChanged {
+        name: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// A signal group (`SIG_GROUP_`) of the message was added, removed or changed, written as DBC text
+    SignalGroupChanged {
+        name: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+impl fmt::Display for ChangeKind {
+    #[expect(clippy::too_many_lines)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added => f.write_str("added"),
+            Self::Removed => f.write_str("removed"),
+            Self::Renamed { old, new } => write!(f, "renamed from {old} to {new}"),
+            Self::SizeChanged { old, new } => {
+                write!(f, "size changed from {old} to {new} bytes")
+            }
+            Self::TransmitterChanged { old, new } => write!(
+                f,
+                "transmitter changed from {} to {}",
+                old.as_deref().unwrap_or("none"),
+                new.as_deref().unwrap_or("none")
+            ),
+            Self::LayoutChanged { old, new } => write!(f, "layout changed from {old} to {new}"),
+            Self::ScalingChanged { old, new } => write!(
+                f,
+                "factor and offset changed from ({},{}) to ({},{})",
+                old.0, old.1, new.0, new.1
+            ),
+            Self::RangeChanged { old, new } => write!(
+                f,
+                "range changed from [{}|{}] to [{}|{}]",
+                old.0, old.1, new.0, new.1
+            ),
+            Self::UnitChanged { old, new } => {
+                write!(f, "unit changed from {} to {}", Quoted(old), Quoted(new))
+            }
+            Self::ReceiversChanged { old, new } => write!(
+                f,
+                "receivers changed from {} to {}",
+                old.join(","),
+                new.join(",")
+            ),
+            Self::ValueDescriptionsChanged {
+                added,
+                removed,
+                changed,
+            } => {
+                f.write_str("value descriptions changed")?;
+                let mut sep = " (";
+                for (label, values) in
+                    [("added", added), ("removed", removed), ("changed", changed)]
+                {
+                    if values.is_empty() {
+                        continue;
+                    }
+                    let values: Vec<_> = values.iter().map(ToString::to_string).collect();
+                    write!(f, "{sep}{label} {}", values.join(", "))?;
+                    sep = "; ";
+                }
+                if sep == "; " {
+                    f.write_str(")")?;
+                }
+                Ok(())
+            }
+            Self::AttributeChanged { name, old, new } => match (old, new) {
+                (None, Some(new)) => write!(f, "attribute {} set to {new}", Quoted(name)),
+                (Some(old), None) => write!(f, "attribute {} removed, was {old}", Quoted(name)),
+                (old, new) => write!(
+                    f,
+                    "attribute {} changed from {} to {}",
+                    Quoted(name),
+                    old.as_deref().unwrap_or_default(),
+                    new.as_deref().unwrap_or_default()
+                ),
+            },
+            Self::CommentChanged { old, new } => match (old, new) {
+                (None, Some(new)) => write!(f, "comment added: {}", Quoted(new)),
+                (Some(old), None) => write!(f, "comment removed, was {}", Quoted(old)),
+                (old, new) => write!(
+                    f,
+                    "comment changed from {} to {}",
+                    Quoted(old.as_deref().unwrap_or_default()),
+                    Quoted(new.as_deref().unwrap_or_default())
+                ),
+            },
+            Self::DefinitionChanged { old, new } => {
+                write!(f, "definition changed from {old} to {new}")
+            }
+            Self::ValueTableChanged { name, old, new } => write_statement_change(
+                f,
+                &format!("value table {name}"),
+                old.as_deref(),
+                new.as_deref(),
+            ),
+            Self::AttributeDefinitionChanged { name, old, new } => write_statement_change(
+                f,
+                &format!("definition of attribute {}", Quoted(name)),
+                old.as_deref(),
+                new.as_deref(),
+            ),
+            Self::AttributeDefaultChanged { name, old, new } => write_statement_change(
+                f,
+                &format!("default of attribute {}", Quoted(name)),
+                old.as_deref(),
+                new.as_deref(),
+            ),
+            Self::SignalGroupChanged { name, old, new } => write_statement_change(
+                f,
+                &format!("signal group {name}"),
+                old.as_deref(),
+                new.as_deref(),
+            ),
+        }
+    }
+}
+
+/// Write the change of a statement given as DBC text, e.g. `value table Gears changed from ... to ...`
+fn write_statement_change(
+    f: &mut fmt::Formatter<'_>,
+    what: &str,
+    old: Option<&str>,
+    new: Option<&str>,
+) -> fmt::Result {
+    match (old, new) {
+        (None, Some(new)) => write!(f, "{what} added: {new}"),
+        (Some(old), None) => write!(f, "{what} removed, was {old}"),
+        (old, new) => write!(
+            f,
+            "{what} changed from {} to {}",
+            old.unwrap_or_default(),
+            new.unwrap_or_default()
+        ),
+    }
+}
+
+impl Dbc<'_> {
+    /// Compare this database with a newer version `new`.
+    ///
+    /// Messages are matched by id and signals by name, so reordering statements results in no changes.
+    /// Comments and attribute values are compared for the database, nodes, messages and signals
+    /// that exist in both versions.
+    ///
+    /// Environment variables, value tables, attribute definitions and defaults, and signal groups are compared
+    /// as well. `SG_MUL_VAL_` entries are part of the layout of the multiplexed signal.
+    /// Signal types, categories and filters are not compared.
+    #[must_use]
+    pub fn diff(&self, new: &Dbc<'_>) -> DbcDiff {
+        let mut differ = Differ {
+            old: self,
+            new,
+            changes: Vec::new(),
+        };
+        differ.diff_nodes();
+        differ.diff_messages();
+        differ.diff_environment_variables();
+        differ.diff_value_tables();
+        differ.diff_signal_groups();
+        differ.diff_comments();
+        differ.diff_attribute_definitions();
+        differ.diff_attributes();
+        DbcDiff {
+            changes: differ.changes,
+        }
+    }
+}
+
+struct Differ<'o, 'n> {
+    old: &'o Dbc<'o>,
+    new: &'n Dbc<'n>,
+    changes: Vec<Change>,
+}
+
+impl Differ<'_, '_> {
+    fn push(&mut self, object: ObjectRef, kind: ChangeKind, breaking: bool) {
+        self.changes.push(Change {
+            object,
+            kind,
+            breaking,
+        });
+    }
+
+    fn diff_nodes(&mut self) {
+        for node in &self.old.nodes {
+            if !self.new.nodes.contains(node) {
+                self.push(
+                    ObjectRef::Node(node.0.to_string()),
+                    ChangeKind::Removed,
+                    false,
+                );
+            }
+        }
+        for node in &self.new.nodes {
+            if !self.old.nodes.contains(node) {
+                self.push(
+                    ObjectRef::Node(node.0.to_string()),
+                    ChangeKind::Added,
+                    false,
+                );
+            }
+        }
+    }
+
+    fn diff_messages(&mut self) {
+        for old in &self.old.messages {
+            match message(self.new, old.id) {
+                Some(new) => self.diff_message(old, new),
+                None => self.push(ObjectRef::Message(old.id), ChangeKind::Removed, true),
+            }
+        }
+        for new in &self.new.messages {
+            if message(self.old, new.id).is_none() {
+                self.push(ObjectRef::Message(new.id), ChangeKind::Added, false);
+            }
+        }
+    }
+
+    fn diff_message(&mut self, old: &Message, new: &Message) {
+        let object = || ObjectRef::Message(old.id);
+        if old.name != new.name {
+            let kind = ChangeKind::Renamed {
+                old: old.name.to_string(),
+                new: new.name.to_string(),
+            };
+            self.push(object(), kind, false);
+        }
+        if old.size != new.size {
+            let kind = ChangeKind::SizeChanged {
+                old: old.size,
+                new: new.size,
+            };
+            self.push(object(), kind, true);
+        }
+        if old.transmitter != new.transmitter {
+            let kind = ChangeKind::TransmitterChanged {
+                old: old.transmitter.as_deref().map(ToString::to_string),
+                new: new.transmitter.as_deref().map(ToString::to_string),
+            };
+            self.push(object(), kind, false);
+        }
+
+        for old_signal in &old.signals {
+            let object = ObjectRef::Signal {
+                message_id: old.id,
+                signal_name: old_signal.name.to_string(),
+            };
+            match new.signals.iter().find(|s| s.name == old_signal.name) {
+                Some(new_signal) => self.diff_signal(object, old_signal, new_signal),
+                None => self.push(object, ChangeKind::Removed, true),
+            }
+        }
+        for new_signal in &new.signals {
+            if !old.signals.iter().any(|s| s.name == new_signal.name) {
+                let object = ObjectRef::Signal {
+                    message_id: new.id,
+                    signal_name: new_signal.name.to_string(),
+                };
+                self.push(object, ChangeKind::Added, false);
+            }
+        }
+    }
+
+    fn diff_signal(&mut self, object: ObjectRef, old: &Signal, new: &Signal) {
+        let ObjectRef::Signal { message_id, .. } = object else {
+            return;
+        };
+        let old_layout = layout(self.old, message_id, old);
+        let new_layout = layout(self.new, message_id, new);
+        if old_layout != new_layout {
+            let kind = ChangeKind::LayoutChanged {
+                old: old_layout,
+                new: new_layout,
+            };
+            self.push(object.clone(), kind, true);
+        }
+        if old.factor.to_bits() != new.factor.to_bits()
+            || old.offset.to_bits() != new.offset.to_bits()
+        {
+            let kind = ChangeKind::ScalingChanged {
+                old: (old.factor, old.offset),
+                new: (new.factor, new.offset),
+            };
+            self.push(object.clone(), kind, true);
+        }
+        if (old.min, old.max) != (new.min, new.max) {
+            let kind = ChangeKind::RangeChanged {
+                old: (old.min, old.max),
+                new: (new.min, new.max),
+            };
+            self.push(object.clone(), kind, false);
+        }
+        if old.unit != new.unit {
+            let kind = ChangeKind::UnitChanged {
+                old: old.unit.to_string(),
+                new: new.unit.to_string(),
+            };
+            self.push(object.clone(), kind, false);
+        }
+        if receivers(old) != receivers(new) {
+            let kind = ChangeKind::ReceiversChanged {
+                old: old.receivers.iter().map(ToString::to_string).collect(),
+                new: new.receivers.iter().map(ToString::to_string).collect(),
+            };
+            self.push(object.clone(), kind, false);
+        }
+        self.diff_value_descriptions(object, message_id, &old.name);
+    }
+
+    fn diff_value_descriptions(&mut self, object: ObjectRef, message_id: MessageId, name: &str) {
+        let old = self
+            .old
+            .value_descriptions_for_signal(message_id, name)
+            .unwrap_or_default();
+        let new = self
+            .new
+            .value_descriptions_for_signal(message_id, name)
+            .unwrap_or_default();
+        let mut removed = Vec::new();
+        let mut changed = Vec::new();
+        for old in old {
+            match new.iter().find(|new| new.id == old.id) {
+                Some(new) if new.description != old.description => changed.push(old.id),
+                Some(_) => {}
+                None => removed.push(old.id),
+            }
+        }
+        let added: Vec<_> = new
+            .iter()
+            .filter(|new| !old.iter().any(|old| old.id == new.id))
+            .map(|new| new.id)
+            .collect();
+        if !(added.is_empty() && removed.is_empty() && changed.is_empty()) {
+            let breaking = !(removed.is_empty() && changed.is_empty());
+            let kind = ChangeKind::ValueDescriptionsChanged {
+                added,
+                removed,
+                changed,
+            };
+            self.push(object, kind, breaking);
+        }
+    }
+
+    fn diff_environment_variables(&mut self) {
+        let old = environment_variables(self.old);
+        let new = environment_variables(self.new);
+        for (name, old, new) in changed(&old, &new) {
+            let kind = match (old, new) {
+                (Some(old), Some(new)) => ChangeKind::DefinitionChanged { old, new },
+                (Some(_), None) => ChangeKind::Removed,
+                (None, _) => ChangeKind::Added,
+            };
+            self.push(ObjectRef::EnvironmentVariable(name), kind, false);
+        }
+    }
+
+    fn diff_value_tables(&mut self) {
+        let old = value_tables(self.old);
+        let new = value_tables(self.new);
+        for (name, old, new) in changed(&old, &new) {
+            let kind = ChangeKind::ValueTableChanged { name, old, new };
+            self.push(ObjectRef::Database, kind, false);
+        }
+    }
+
+    fn diff_signal_groups(&mut self) {
+        let old = signal_groups(self.old);
+        let new = signal_groups(self.new);
+        for ((message_id, name), old, new) in changed(&old, &new) {
+            let object = ObjectRef::Message(message_id);
+            if self.in_both(&object) {
+                let kind = ChangeKind::SignalGroupChanged { name, old, new };
+                self.push(object, kind, false);
+            }
+        }
+    }
+
+    fn diff_attribute_definitions(&mut self) {
+        let old = attribute_definitions(self.old);
+        let new = attribute_definitions(self.new);
+        for ((.., name), old, new) in changed(&old, &new) {
+            let kind = ChangeKind::AttributeDefinitionChanged { name, old, new };
+            self.push(ObjectRef::Database, kind, false);
+        }
+        let old = attribute_defaults(self.old);
+        let new = attribute_defaults(self.new);
+        for ((_, name), old, new) in changed(&old, &new) {
+            let kind = ChangeKind::AttributeDefaultChanged { name, old, new };
+            self.push(ObjectRef::Database, kind, false);
+        }
+    }
+
+    fn diff_comments(&mut self) {
+        let old = comments(self.old);
+        let new = comments(self.new);
+        for (object, old_comment) in &old {
+            let new_comment = new.iter().find(|(o, _)| o == object).map(|(_, c)| *c);
+            if new_comment != Some(*old_comment) && self.in_both(object) {
+                let kind = ChangeKind::CommentChanged {
+                    old: Some(old_comment.to_string()),
+                    new: new_comment.map(ToString::to_string),
+                };
+                self.push(object.clone(), kind, false);
+            }
+        }
+        for (object, new_comment) in &new {
+            if !old.iter().any(|(o, _)| o == object) && self.in_both(object) {
+                let kind = ChangeKind::CommentChanged {
+                    old: None,
+                    new: Some(new_comment.to_string()),
+                };
+                self.push(object.clone(), kind, false);
+            }
+        }
+    }
+
+    fn diff_attributes(&mut self) {
+        let owned = |(object, name, value)| (ObjectRef::from(object), name, value);
+        let old = self.old.attribute_values().map(owned).collect::<Vec<_>>();
+        let new = self.new.attribute_values().map(owned).collect::<Vec<_>>();
+        for (object, name, old_value) in &old {
+            let new_value = new
+                .iter()
+                .find(|(o, n, _)| o == object && n == name)
+                .map(|(_, _, v)| *v);
+            if new_value != Some(*old_value) && self.in_both(object) {
+                let kind = ChangeKind::AttributeChanged {
+                    name: name.to_string(),
+                    old: Some(old_value.to_string()),
+                    new: new_value.map(ToString::to_string),
+                };
+                self.push(object.clone(), kind, false);
+            }
+        }
+        for (object, name, new_value) in &new {
+            if !old.iter().any(|(o, n, _)| o == object && n == name) && self.in_both(object) {
+                let kind = ChangeKind::AttributeChanged {
+                    name: name.to_string(),
+                    old: None,
+                    new: Some(new_value.to_string()),
+                };
+                self.push(object.clone(), kind, false);
+            }
+        }
+    }
+
+    /// Whether the object exists in both versions.
+    /// Comments and attributes of added or removed objects are covered by the object's change.
+    fn in_both(&self, object: &ObjectRef) -> bool {
+        [self.old, self.new].iter().all(|dbc| exists(dbc, object))
+    }
+}
+
+fn exists(dbc: &Dbc, object: &ObjectRef) -> bool {
+    let node = |name: &str| dbc.nodes.iter().any(|node| node.0 == name);
+    let env = |name: &str| dbc.environment_variables.iter().any(|env| env.name == name);
+    match object {
+        ObjectRef::Database => true,
+        ObjectRef::Node(name) => node(name),
+        ObjectRef::Message(id) => message(dbc, *id).is_some(),
+        ObjectRef::Signal {
+            message_id,
+            signal_name,
+        } => dbc.signal_by_name(*message_id, signal_name).is_some(),
+        ObjectRef::EnvironmentVariable(name) => env(name),
+        ObjectRef::NodeToSignal {
+            node_name,
+            message_id,
+            signal_name,
+        } => node(node_name) && dbc.signal_by_name(*message_id, signal_name).is_some(),
+        ObjectRef::NodeToMessage {
+            node_name,
+            message_id,
+        } => node(node_name) && message(dbc, *message_id).is_some(),
+        ObjectRef::NodeToEnvironmentVariable {
+            node_name,
+            variable_name,
+        } => node(node_name) && env(variable_name),
+    }
+}
+
+fn message<'a>(dbc: &'a Dbc, id: MessageId) -> Option<&'a Message<'a>> {
+    dbc.messages.iter().find(|message| message.id == id)
+}
+
+/// Bit layout of a signal including its multiplexing, e.g. `m1 8|16@1+`,
+/// followed by `SG_MUL_VAL_` entries as e.g. `by Mode 1-1,3-5`
+fn layout(dbc: &Dbc, message_id: MessageId, signal: &Signal) -> String {
+    let mut layout = format!(
+        "{}|{}@{}{}",
+        signal.start_bit, signal.size, signal.byte_order, signal.value_type
+    );
+    let multiplexing = signal.multiplexer_indicator.to_string();
+    if !multiplexing.is_empty() {
+        layout = format!("{multiplexing} {layout}");
+    }
+    match dbc.extended_value_type_for_signal(message_id, &signal.name) {
+        Some(SignalExtendedValueType::IEEEfloat32Bit) => layout.push_str(" float32"),
+        Some(SignalExtendedValueType::IEEEdouble64bit) => layout.push_str(" float64"),
+        Some(SignalExtendedValueType::SignedOrUnsignedInteger) | None => {}
+    }
+    let mut extended: Vec<_> = dbc
+        .extended_multiplex
+        .iter()
+        .filter(|ext| ext.message_id == message_id && ext.signal_name == signal.name)
+        .map(|ext| {
+            let mappings: Vec<_> = ext.mappings.iter().map(ToString::to_string).collect();
+            format!(" by {} {}", ext.multiplexor_signal_name, mappings.join(","))
+        })
+        .collect();
+    extended.sort();
+    layout.extend(extended);
+    layout
+}
+
+/// Receivers of a signal, sorted because their order has no meaning
+fn receivers<'a>(signal: &'a Signal) -> Vec<&'a str> {
+    let mut receivers: Vec<_> = signal.receivers.iter().map(AsRef::as_ref).collect();
+    receivers.sort_unstable();
+    receivers.dedup();
+    receivers
+}
+
+/// Entries whose text differs between `old` and `new`, or that only exist in one of them.
+/// The first entry of a key wins, like the lookups of [`Dbc`].
+fn changed<K: PartialEq + Clone>(
+    old: &[(K, String)],
+    new: &[(K, String)],
+) -> Vec<(K, Option<String>, Option<String>)> {
+    fn first<'e, K: PartialEq>(entries: &'e [(K, String)], key: &K) -> Option<&'e String> {
+        entries.iter().find(|(k, _)| k == key).map(|(_, text)| text)
+    }
+    let mut changes = Vec::new();
+    for (idx, (key, text)) in old.iter().enumerate() {
+        let new_text = first(new, key);
+        if first(&old[..idx], key).is_none() && new_text != Some(text) {
+            changes.push((key.clone(), Some(text.clone()), new_text.cloned()));
+        }
+    }
+    for (idx, (key, text)) in new.iter().enumerate() {
+        if first(&new[..idx], key).is_none() && first(old, key).is_none() {
+            changes.push((key.clone(), None, Some(text.clone())));
+        }
+    }
+    changes
+}
+
+fn environment_variables(dbc: &Dbc) -> Vec<(String, String)> {
+    dbc.environment_variables
+        .iter()
+        .map(|env| {
+            let mut text = env.to_string();
+            if let Some(data) = dbc
+                .environment_variable_data
+                .iter()
+                .find(|data| data.env_var_name == env.name)
+            {
+                text = format!("{text} {data}");
+            }
+            (env.name.to_string(), text)
+        })
+        .collect()
+}
+
+fn value_tables(dbc: &Dbc) -> Vec<(String, String)> {
+    dbc.value_tables
+        .iter()
+        .map(|table| (table.name.to_string(), table.to_string()))
+        .collect()
+}
+
+fn signal_groups(dbc: &Dbc) -> Vec<((MessageId, String), String)> {
+    dbc.signal_groups
+        .iter()
+        .map(|group| {
+            (
+                (group.message_id, group.name.to_string()),
+                group.to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Attribute definitions keyed by their kind, including relation kinds, and name
+fn attribute_definitions(dbc: &Dbc) -> Vec<((u8, String), String)> {
+    dbc.attribute_definitions
+        .iter()
+        .chain(&dbc.relation_attribute_definitions)
+        .map(|def| (definition_key(def), def.to_string()))
+        .collect()
+}
+
+/// Attribute defaults keyed by whether they are relation defaults and their name
+fn attribute_defaults(dbc: &Dbc) -> Vec<((bool, String), String)> {
+    let plain = dbc.attribute_defaults.iter().map(|default| {
+        (
+            (false, default.name.to_string()),
+            Relation(default, false).to_string(),
+        )
+    });
+    let relation = dbc.relation_attribute_defaults.iter().map(|default| {
+        (
+            (true, default.name.to_string()),
+            Relation(default, true).to_string(),
+        )
+    });
+    plain.chain(relation).collect()
+}
+
+/// Write a default as plain or as relation statement
+struct Relation<'t, T>(&'t T, bool);
+
+impl fmt::Display for Relation<'_, AttributeDefault<'_>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write(f, self.1)
+    }
+}
+
+fn comments<'a>(dbc: &'a Dbc) -> Vec<(ObjectRef, &'a str)> {
+    dbc.comments
+        .iter()
+        .map(|comment| (comment.object(), comment.text()))
+        .collect()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::ast::{ByteOrder, MultiplexIndicator};
+
+    const OLD: &str = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU Dashboard
+
+BO_ 100 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (0.1,0) [0|6553.5] "rpm" Dashboard
+ SG_ State : 16|2@1+ (1,0) [0|3] "" Dashboard
+ SG_ Temp : 24|8@1- (1,-40) [-40|215] "degC" Dashboard
+
+BO_ 200 Brake: 2 ECU
+ SG_ Pressure : 0|16@1+ (1,0) [0|0] "bar" Dashboard
+
+CM_ BO_ 100 "engine status";
+CM_ SG_ 100 Speed "engine speed";
+
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 10000;
+BA_ "GenMsgCycleTime" BO_ 100 100;
+
+VAL_ 100 State 0 "off" 1 "on" 2 "error" ;
+"#;
+
+    const NEW: &str = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU Dashboard Logger
+
+BO_ 100 EngineStatus: 8 ECU
+ SG_ Temp : 24|8@1- (1,-40) [-40|215] "degC" Dashboard,Logger
+ SG_ State : 16|2@1+ (1,0) [0|3] "" Dashboard
+ SG_ Speed : 0|16@1+ (0.25,0) [0|16383.75] "rpm" Dashboard
+ SG_ Load : 32|8@1+ (1,0) [0|100] "%" Dashboard
+
+BO_ 300 Gear: 1 ECU
+ SG_ Gear : 0|4@1+ (1,0) [0|0] "" Dashboard
+
+CM_ SG_ 100 Speed "engine speed in rpm";
+CM_ SG_ 100 Load "engine load";
+
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 10000;
+BA_ "GenMsgCycleTime" BO_ 100 50;
+
+VAL_ 100 State 0 "off" 1 "running" 3 "unknown" ;
+"#;
+
+    #[test]
+    fn no_changes_test() {
+        let dbc = Dbc::try_from(OLD).unwrap();
+        let mut reordered = dbc.clone();
+        reordered.messages.reverse();
+        reordered.messages[1].signals.reverse();
+        let diff = dbc.diff(&reordered);
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "No changes\n");
+    }
+
+    #[test]
+    fn diff_test() {
+        let old = Dbc::try_from(OLD).unwrap();
+        let new = Dbc::try_from(NEW).unwrap();
+        let diff = old.diff(&new);
+        assert!(diff.is_breaking());
+        assert_eq!(
+            diff.to_string(),
+            r#"12 changes, 3 breaking
+non-breaking: node Logger: added
+non-breaking: message 100: renamed from Engine to EngineStatus
+breaking: signal Speed of message 100: factor and offset changed from (0.1,0) to (0.25,0)
+non-breaking: signal Speed of message 100: range changed from [0|6553.5] to [0|16383.75]
+breaking: signal State of message 100: value descriptions changed (added 3; removed 2; changed 1)
+non-breaking: signal Temp of message 100: receivers changed from Dashboard to Dashboard,Logger
+non-breaking: signal Load of message 100: added
+breaking: message 200: removed
+non-breaking: message 300: added
+non-breaking: message 100: comment removed, was "engine status"
+non-breaking: signal Speed of message 100: comment changed from "engine speed" to "engine speed in rpm"
+non-breaking: message 100: attribute "GenMsgCycleTime" changed from 100 to 50
+"#
+        );
+    }
+
+    #[test]
+    fn sections_test() {
+        let old = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU Dashboard
+
+VAL_TABLE_ Gears 0 "P" 1 "D" ;
+VAL_TABLE_ Unused 0 "off" ;
+
+BO_ 100 Engine: 8 ECU
+ SG_ Mode M : 0|2@1+ (1,0) [0|3] "" Dashboard,ECU
+ SG_ Speed m1 : 8|16@1+ (1,0) [0|0] "rpm" Dashboard
+
+EV_ Ignition: 0 [0|1] "" 0 1 DUMMY_NODE_VECTOR0 ECU;
+
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 100;
+BA_DEF_DEF_ "GenMsgCycleTime" 10;
+
+SIG_GROUP_ 100 Group 1 : Mode;
+
+SG_MUL_VAL_ 100 Speed Mode 1-1;
+"#;
+        let new = old
+            .replace("Dashboard,ECU", "ECU,Dashboard")
+            .replace(r#"1 "D" ;"#, r#"1 "D" 2 "R" ;"#)
+            .replace("VAL_TABLE_ Unused 0 \"off\" ;\n", "")
+            .replace("[0|1] \"\"", "[0|2] \"\"")
+            .replace("INT 0 100", "INT 0 200")
+            .replace("\"GenMsgCycleTime\" 10", "\"GenMsgCycleTime\" 20")
+            .replace(": Mode;", ": Mode Speed;")
+            .replace("1-1", "1-2");
+        let old = Dbc::try_from(old).unwrap();
+        let new = Dbc::try_from(new.as_str()).unwrap();
+        assert_eq!(
+            old.diff(&new).to_string(),
+            r#"7 changes, 1 breaking
+breaking: signal Speed of message 100: layout changed from m1 8|16@1+ by Mode 1-1 to m1 8|16@1+ by Mode 1-2
+non-breaking: environment variable Ignition: definition changed from EV_ Ignition: 0 [0|1] "" 0 1 DUMMY_NODE_VECTOR0 ECU; to EV_ Ignition: 0 [0|2] "" 0 1 DUMMY_NODE_VECTOR0 ECU;
+non-breaking: database: value table Gears changed from VAL_TABLE_ Gears 0 "P" 1 "D" ; to VAL_TABLE_ Gears 0 "P" 1 "D" 2 "R" ;
+non-breaking: database: value table Unused removed, was VAL_TABLE_ Unused 0 "off" ;
+non-breaking: message 100: signal group Group changed from SIG_GROUP_ 100 Group 1 : Mode; to SIG_GROUP_ 100 Group 1 : Mode Speed;
+non-breaking: database: definition of attribute "GenMsgCycleTime" changed from BA_DEF_ BO_ "GenMsgCycleTime" INT 0 100; to BA_DEF_ BO_ "GenMsgCycleTime" INT 0 200;
+non-breaking: database: default of attribute "GenMsgCycleTime" changed from BA_DEF_DEF_ "GenMsgCycleTime" 10; to BA_DEF_DEF_ "GenMsgCycleTime" 20;
+"#
+        );
+    }
+
+    #[test]
+    fn layout_test() {
+        let old = Dbc::try_from(OLD).unwrap();
+        let mut new = old.clone();
+        new.messages[0].size = 4;
+        new.messages[0].signals[0].byte_order = ByteOrder::BigEndian;
+        new.messages[0].signals[1].multiplexer_indicator = MultiplexIndicator::Multiplexor;
+        let kinds: Vec<_> = old.diff(&new).changes.into_iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                ChangeKind::SizeChanged { old: 8, new: 4 },
+                ChangeKind::LayoutChanged {
+                    old: "0|16@1+".into(),
+                    new: "0|16@0+".into()
+                },
+                ChangeKind::LayoutChanged {
+                    old: "16|2@1+".into(),
+                    new: "M 16|2@1+".into()
+                },
+            ]
+        );
+    }
+}
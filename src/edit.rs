@@ -0,0 +1,499 @@
+//!
+//! Renaming and removing objects of a [`Dbc`], keeping all references to them consistent
+//!
+
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+
+use crate::ast::{
+    AttributeValueForRelationType, CategoryObject, Comment, Dbc, Message, MessageId, Node,
+    ObjectRef, ValueDescription,
+};
+
+pub type EditResult<T> = Result<T, EditError>;
+
+/// Error returned by the editing methods of [`Dbc`], e.g. [`Dbc::rename_signal`].
+/// The database is left unchanged if an error is returned.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum EditError {
+    #[error("{0} is not defined")]
+    NotFound(ObjectRef),
+    #[error("{0} is already defined")]
+    AlreadyExists(ObjectRef),
+}
+
+impl<'a> Dbc<'a> {
+    /// Rename a signal of a message, updating all statements referring to it,
+    /// e.g. comments, value descriptions, attribute values, signal groups and `SG_MUL_VAL_`.
+    pub fn rename_signal(
+        &mut self,
+        message_id: MessageId,
+        name: &str,
+        new_name: impl Into<Cow<'a, str>>,
+    ) -> EditResult<()> {
+        let new_name = new_name.into();
+        let message = self.message_mut(message_id)?;
+        if new_name == name {
+            return signal_index(message, name).map(|_| ());
+        }
+        if message.signals.iter().any(|s| s.name == new_name) {
+            return Err(EditError::AlreadyExists(ObjectRef::Signal {
+                message_id,
+                signal_name: new_name.to_string(),
+            }));
+        }
+        let idx = signal_index(message, name)?;
+        message.signals[idx].name.clone_from(&new_name);
+
+        let rename = |id: MessageId, signal_name: &mut Cow<'a, str>| {
+            if id == message_id && signal_name == name {
+                signal_name.clone_from(&new_name);
+            }
+        };
+        for comment in &mut self.comments {
+            if let Comment::Signal {
+                message_id, name, ..
+            } = comment
+            {
+                rename(*message_id, name);
+            }
+        }
+        for value in &mut self.attribute_values_signal {
+            rename(value.message_id, &mut value.signal_name);
+        }
+        for value in &mut self.relation_attribute_values {
+            if let AttributeValueForRelationType::NodeToSignal {
+                message_id,
+                signal_name,
+                ..
+            } = &mut value.details
+            {
+                rename(*message_id, signal_name);
+            }
+        }
+        for description in &mut self.value_descriptions {
+            if let ValueDescription::Signal {
+                message_id, name, ..
+            } = description
+            {
+                rename(*message_id, name);
+            }
+        }
+        for reference in &mut self.signal_type_refs {
+            rename(reference.message_id, &mut reference.signal_name);
+        }
+        for group in &mut self.signal_groups {
+            for signal_name in &mut group.signal_names {
+                rename(group.message_id, signal_name);
+            }
+        }
+        for ext in &mut self.signal_extended_value_type_list {
+            rename(ext.message_id, &mut ext.signal_name);
+        }
+        for ext in &mut self.extended_multiplex {
+            rename(ext.message_id, &mut ext.signal_name);
+            rename(ext.message_id, &mut ext.multiplexor_signal_name);
+        }
+        Ok(())
+    }
+
+    /// Rename a node, updating all statements referring to it,
+    /// e.g. transmitters, receivers, comments and attribute values.
+    pub fn rename_node(&mut self, name: &str, new_name: impl Into<Cow<'a, str>>) -> EditResult<()> {
+        let new_name = new_name.into();
+        let idx = self.node_index(name)?;
+        if new_name == name {
+            return Ok(());
+        }
+        if self.nodes.iter().any(|node| node.0 == new_name) {
+            return Err(EditError::AlreadyExists(ObjectRef::Node(
+                new_name.to_string(),
+            )));
+        }
+        self.nodes[idx].0.clone_from(&new_name);
+
+        let rename = |node_name: &mut Cow<'a, str>| {
+            if node_name == name {
+                node_name.clone_from(&new_name);
+            }
+        };
+        for message in &mut self.messages {
+            message.transmitter.iter_mut().for_each(&rename);
+            for signal in &mut message.signals {
+                signal.receivers.iter_mut().for_each(&rename);
+            }
+        }
+        for tx in &mut self.message_transmitters {
+            tx.transmitter.iter_mut().for_each(&rename);
+        }
+        for env in &mut self.environment_variables {
+            env.access_nodes.iter_mut().for_each(&rename);
+        }
+        for comment in &mut self.comments {
+            if let Comment::Node { name, .. } = comment {
+                rename(name);
+            }
+        }
+        for value in &mut self.attribute_values_node {
+            rename(&mut value.node_name);
+        }
+        for value in &mut self.relation_attribute_values {
+            rename(relation_node_mut(&mut value.details));
+        }
+        for category in &mut self.categories {
+            if let CategoryObject::Node(name) = &mut category.object {
+                rename(name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Change the id of a message, updating all statements referring to the message or its signals
+    pub fn change_message_id(&mut self, id: MessageId, new_id: MessageId) -> EditResult<()> {
+        let idx = self.message_index(id)?;
+        if new_id == id {
+            return Ok(());
+        }
+        if self.message_index(new_id).is_ok() {
+            return Err(EditError::AlreadyExists(ObjectRef::Message(new_id)));
+        }
+        self.messages[idx].id = new_id;
+
+        let change = |message_id: &mut MessageId| {
+            if *message_id == id {
+                *message_id = new_id;
+            }
+        };
+        for tx in &mut self.message_transmitters {
+            change(&mut tx.message_id);
+        }
+        for comment in &mut self.comments {
+            match comment {
+                Comment::Message { id, .. } => change(id),
+                Comment::Signal { message_id, .. } => change(message_id),
+                Comment::Node { .. } | Comment::EnvVar { .. } | Comment::Plain { .. } => {}
+            }
+        }
+        for value in &mut self.attribute_values_message {
+            change(&mut value.message_id);
+        }
+        for value in &mut self.attribute_values_signal {
+            change(&mut value.message_id);
+        }
+        for value in &mut self.relation_attribute_values {
+            match &mut value.details {
+                AttributeValueForRelationType::NodeToSignal { message_id, .. }
+                | AttributeValueForRelationType::NodeToMessage { message_id, .. } => {
+                    change(message_id);
+                }
+                AttributeValueForRelationType::NodeToEnvironmentVariable { .. } => {}
+            }
+        }
+        for description in &mut self.value_descriptions {
+            if let ValueDescription::Signal { message_id, .. } = description {
+                change(message_id);
+            }
+        }
+        for category in &mut self.categories {
+            if let CategoryObject::Message(message_id) = &mut category.object {
+                change(message_id);
+            }
+        }
+        for reference in &mut self.signal_type_refs {
+            change(&mut reference.message_id);
+        }
+        for group in &mut self.signal_groups {
+            change(&mut group.message_id);
+        }
+        for ext in &mut self.signal_extended_value_type_list {
+            change(&mut ext.message_id);
+        }
+        for ext in &mut self.extended_multiplex {
+            change(&mut ext.message_id);
+        }
+        Ok(())
+    }
+
+    /// Remove a message and all statements referring to the message or its signals
+    pub fn remove_message(&mut self, id: MessageId) -> EditResult<Message<'a>> {
+        let idx = self.message_index(id)?;
+        let message = self.messages.remove(idx);
+
+        self.message_transmitters.retain(|tx| tx.message_id != id);
+        self.comments.retain(|comment| match comment {
+            Comment::Message { id: message_id, .. } | Comment::Signal { message_id, .. } => {
+                *message_id != id
+            }
+            Comment::Node { .. } | Comment::EnvVar { .. } | Comment::Plain { .. } => true,
+        });
+        self.attribute_values_message
+            .retain(|value| value.message_id != id);
+        self.attribute_values_signal
+            .retain(|value| value.message_id != id);
+        self.relation_attribute_values
+            .retain(|value| match &value.details {
+                AttributeValueForRelationType::NodeToSignal { message_id, .. }
+                | AttributeValueForRelationType::NodeToMessage { message_id, .. } => {
+                    *message_id != id
+                }
+                AttributeValueForRelationType::NodeToEnvironmentVariable { .. } => true,
+            });
+        self.value_descriptions
+            .retain(|description| match description {
+                ValueDescription::Signal { message_id, .. } => *message_id != id,
+                ValueDescription::EnvironmentVariable { .. } => true,
+            });
+        self.categories
+            .retain(|category| category.object != CategoryObject::Message(id));
+        self.signal_type_refs
+            .retain(|reference| reference.message_id != id);
+        self.signal_groups.retain(|group| group.message_id != id);
+        self.signal_extended_value_type_list
+            .retain(|ext| ext.message_id != id);
+        self.extended_multiplex.retain(|ext| ext.message_id != id);
+        Ok(message)
+    }
+
+    /// Remove a node and all statements referring to it.
+    ///
+    /// Messages transmitted by the node are kept without transmitter,
+    /// and the node is removed from the receivers of all signals.
+    pub fn remove_node(&mut self, name: &str) -> EditResult<Node<'a>> {
+        let idx = self.node_index(name)?;
+        let node = self.nodes.remove(idx);
+
+        for message in &mut self.messages {
+            if message.transmitter.as_deref() == Some(name) {
+                message.transmitter = None;
+            }
+            for signal in &mut message.signals {
+                signal.receivers.retain(|receiver| receiver != name);
+            }
+        }
+        for tx in &mut self.message_transmitters {
+            tx.transmitter.retain(|transmitter| transmitter != name);
+        }
+        self.message_transmitters
+            .retain(|tx| !tx.transmitter.is_empty());
+        for env in &mut self.environment_variables {
+            env.access_nodes.retain(|node| node != name);
+        }
+        self.comments.retain(|comment| match comment {
+            Comment::Node { name: node, .. } => node != name,
+            _ => true,
+        });
+        self.attribute_values_node
+            .retain(|value| value.node_name != name);
+        self.relation_attribute_values
+            .retain(|value| value.details.node_name() != name);
+        self.categories.retain(|category| match &category.object {
+            CategoryObject::Node(node) => node != name,
+            _ => true,
+        });
+        Ok(node)
+    }
+
+    fn message_index(&self, id: MessageId) -> EditResult<usize> {
+        self.messages
+            .iter()
+            .position(|message| message.id == id)
+            .ok_or(EditError::NotFound(ObjectRef::Message(id)))
+    }
+
+    fn message_mut(&mut self, id: MessageId) -> EditResult<&mut Message<'a>> {
+        let idx = self.message_index(id)?;
+        Ok(&mut self.messages[idx])
+    }
+
+    fn node_index(&self, name: &str) -> EditResult<usize> {
+        self.nodes
+            .iter()
+            .position(|node| node.0 == name)
+            .ok_or_else(|| EditError::NotFound(ObjectRef::Node(name.to_string())))
+    }
+}
+
+fn signal_index(message: &Message, name: &str) -> EditResult<usize> {
+    message
+        .signals
+        .iter()
+        .position(|signal| signal.name == name)
+        .ok_or_else(|| {
+            EditError::NotFound(ObjectRef::Signal {
+                message_id: message.id,
+                signal_name: name.to_string(),
+            })
+        })
+}
+
+fn relation_node_mut<'b, 'a>(
+    details: &'b mut AttributeValueForRelationType<'a>,
+) -> &'b mut Cow<'a, str> {
+    match details {
+        AttributeValueForRelationType::NodeToSignal { node_name, .. }
+        | AttributeValueForRelationType::NodeToMessage { node_name, .. }
+        | AttributeValueForRelationType::NodeToEnvironmentVariable { node_name, .. } => node_name,
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    const DBC: &str = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU Dashboard
+
+BO_ 100 Engine: 8 ECU
+ SG_ Mux M : 0|8@1+ (1,0) [0|0] "" Dashboard
+ SG_ Speed m1 : 8|16@1+ (1,0) [0|0] "" Dashboard,ECU
+
+BO_ 200 Other: 8 Dashboard
+ SG_ Speed : 0|16@1+ (1,0) [0|0] "" ECU
+
+BO_TX_BU_ 100 : ECU,Dashboard;
+
+EV_ Env: 0 [0|1] "" 0 1 DUMMY_NODE_VECTOR0 ECU;
+
+CM_ BU_ ECU "engine control unit";
+CM_ BO_ 100 "engine";
+CM_ SG_ 100 Speed "engine speed";
+CM_ SG_ 200 Speed "other speed";
+
+BA_DEF_ BU_ "NodeAttr" INT 0 10;
+BA_DEF_ BO_ "MsgAttr" INT 0 10;
+BA_DEF_ SG_ "SigAttr" INT 0 10;
+BA_DEF_REL_ BU_SG_REL_ "RelAttr" INT 0 10;
+BA_ "NodeAttr" BU_ ECU 1;
+BA_ "MsgAttr" BO_ 100 2;
+BA_ "SigAttr" SG_ 100 Speed 3;
+BA_ "SigAttr" SG_ 200 Speed 4;
+BA_REL_ "RelAttr" BU_SG_REL_ ECU SG_ 100 Speed 5;
+
+VAL_ 100 Speed 0 "stopped" ;
+
+SIG_GROUP_ 100 Group 1 : Mux Speed;
+
+SIG_VALTYPE_ 100 Speed : 1;
+
+SG_MUL_VAL_ 100 Speed Mux 1-1;
+"#;
+
+    fn dbc() -> Dbc<'static> {
+        Dbc::try_from(DBC).unwrap()
+    }
+
+    #[test]
+    fn rename_signal_test() {
+        let mut dbc = dbc();
+        dbc.rename_signal(MessageId::Standard(100), "Speed", "Rpm")
+            .unwrap();
+        dbc.rename_signal(MessageId::Standard(100), "Mux", "Selector")
+            .unwrap();
+        let expected = DBC
+            .replace("SG_ Speed m1", "SG_ Rpm m1")
+            .replace("100 Speed", "100 Rpm")
+            .replace("Mux Speed", "Selector Rpm")
+            .replace("SG_ Mux M", "SG_ Selector M")
+            .replace("Rpm Mux", "Rpm Selector");
+        assert_eq!(dbc, Dbc::try_from(expected.as_str()).unwrap());
+        assert!(dbc.validate().is_empty());
+    }
+
+    #[test]
+    fn rename_node_test() {
+        let mut dbc = dbc();
+        dbc.rename_node("ECU", "Engine").unwrap();
+        let expected = DBC.replace("ECU", "Engine");
+        assert_eq!(dbc, Dbc::try_from(expected.as_str()).unwrap());
+    }
+
+    #[test]
+    fn change_message_id_test() {
+        let mut dbc = dbc();
+        dbc.change_message_id(MessageId::Standard(100), MessageId::Extended(300))
+            .unwrap();
+        let expected = DBC.replace(" 100 ", " 2147483948 ");
+        assert_eq!(dbc, Dbc::try_from(expected.as_str()).unwrap());
+    }
+
+    #[test]
+    fn remove_message_test() {
+        let mut dbc = dbc();
+        let message = dbc.remove_message(MessageId::Standard(100)).unwrap();
+        assert_eq!(message.name, "Engine");
+        assert_eq!(dbc.messages.len(), 1);
+        assert_eq!(dbc.comments.len(), 2);
+        assert_eq!(dbc.attribute_values_signal.len(), 1);
+        assert!(dbc.message_transmitters.is_empty());
+        assert!(dbc.relation_attribute_values.is_empty());
+        assert!(dbc.value_descriptions.is_empty());
+        assert!(dbc.signal_groups.is_empty());
+        assert!(dbc.signal_extended_value_type_list.is_empty());
+        assert!(dbc.extended_multiplex.is_empty());
+        assert!(dbc.validate().is_empty());
+    }
+
+    #[test]
+    fn remove_node_test() {
+        let mut dbc = dbc();
+        let node = dbc.remove_node("ECU").unwrap();
+        assert_eq!(node, Node("ECU".into()));
+        assert_eq!(dbc.messages[0].transmitter, None);
+        assert_eq!(dbc.messages[0].signals[1].receivers, ["Dashboard"]);
+        assert!(dbc.messages[1].signals[0].receivers.is_empty());
+        assert_eq!(dbc.message_transmitters[0].transmitter, ["Dashboard"]);
+        assert!(dbc.environment_variables[0].access_nodes.is_empty());
+        assert_eq!(dbc.comments.len(), 3);
+        assert!(dbc.attribute_values_node.is_empty());
+        assert!(dbc.relation_attribute_values.is_empty());
+        assert!(dbc.validate().is_empty());
+    }
+
+    #[test]
+    fn edit_errors_test() {
+        let mut dbc = dbc();
+        let original = dbc.clone();
+        assert_eq!(
+            dbc.rename_signal(MessageId::Standard(100), "Speed", "Mux"),
+            Err(EditError::AlreadyExists(ObjectRef::Signal {
+                message_id: MessageId::Standard(100),
+                signal_name: "Mux".into()
+            }))
+        );
+        assert_eq!(
+            dbc.rename_signal(MessageId::Standard(100), "Missing", "New")
+                .unwrap_err()
+                .to_string(),
+            "signal Missing of message 100 is not defined"
+        );
+        assert_eq!(
+            dbc.rename_node("ECU", "Dashboard"),
+            Err(EditError::AlreadyExists(ObjectRef::Node(
+                "Dashboard".into()
+            )))
+        );
+        assert_eq!(
+            dbc.change_message_id(MessageId::Standard(100), MessageId::Standard(200)),
+            Err(EditError::AlreadyExists(ObjectRef::Message(
+                MessageId::Standard(200)
+            )))
+        );
+        assert_eq!(
+            dbc.remove_message(MessageId::Standard(1)),
+            Err(EditError::NotFound(ObjectRef::Message(
+                MessageId::Standard(1)
+            )))
+        );
+        assert_eq!(
+            dbc.remove_node("Missing"),
+            Err(EditError::NotFound(ObjectRef::Node("Missing".into())))
+        );
+        assert_eq!(dbc, original);
+    }
+}
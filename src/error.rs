@@ -0,0 +1,176 @@
+//!
+//! Error type shared by parsing and the AST
+//!
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString as _};
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use can_dbc_pest::{Error as PestError, Rule};
+
+pub type DbcResult<T> = Result<T, DbcError>;
+
+/// Error type for DBC parsing operations
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum DbcError {
+    #[cfg(feature = "std")]
+    #[error("No more rules expected, but found: {0:?}")]
+    ExpectedEmpty(Rule),
+    #[cfg(feature = "std")]
+    #[error("Expected rule: {0:?}, found: {1:?}")]
+    ExpectedRule(Rule, Rule),
+    #[cfg(feature = "std")]
+    #[error("Expected one of these rules: {0:?}, found: {1:?}")]
+    ExpectedOneOfRules(Vec<Rule>, Rule),
+    #[cfg(feature = "std")]
+    #[error("Expected a quoted string or a number, found: {0:?}")]
+    ExpectedStrNumber(Rule),
+    #[error("Invalid Float value: '{0}'")]
+    InvalidFloat(String),
+    #[error("Invalid Int value: '{0}'")]
+    InvalidInt(String),
+    #[error("Invalid Uint value: '{0}'")]
+    InvalidUint(String),
+    #[error("Message ID out of range: {0}")]
+    MessageIdOutOfRange(u64),
+    #[error("Multiple multiplexors defined for a message")]
+    MultipleMultiplexors,
+    #[error("No more parsing rules available")]
+    NoMoreRules,
+    #[error("Feature not implemented: {0}")]
+    NotImplemented(&'static str),
+    #[cfg(feature = "std")]
+    #[error(transparent)]
+    Pest(Box<PestError<Rule>>),
+    #[error("Signal defined without an associated message")]
+    SignalWithoutMessage,
+    #[error("Unknown multiplex indicator: {0}")]
+    UnknownMultiplexIndicator(String),
+    #[cfg(feature = "std")]
+    #[error("Unknown rule: {0:?}")]
+    UnknownRule(Rule),
+    #[error("Invalid numeric value: '{0}'")]
+    InvalidNumericValue(String),
+    #[error("Invalid {keyword} statement, expected {expected}")]
+    InvalidStatement {
+        keyword: &'static str,
+        expected: &'static str,
+    },
+    #[error("{error} at line {}, column {}", location.line, location.column)]
+    Located {
+        error: Box<DbcError>,
+        location: Box<SourceLocation>,
+    },
+}
+
+impl DbcError {
+    /// Attach `location`, unless the error already has a location
+    #[cfg(feature = "std")]
+    pub(crate) fn at_location(self, location: SourceLocation) -> Self {
+        match self {
+            Self::Located { .. } | Self::Pest(_) => self,
+            error => Self::Located {
+                error: Box::new(error),
+                location: Box::new(location),
+            },
+        }
+    }
+
+    /// Location of the offending source element, if known.
+    /// Syntax errors ([`DbcError::Pest`]) carry their own position instead.
+    #[must_use]
+    pub fn location(&self) -> Option<&SourceLocation> {
+        match self {
+            Self::Located { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    /// The error without its location
+    #[must_use]
+    pub fn inner(&self) -> &Self {
+        match self {
+            Self::Located { error, .. } => error.inner(),
+            error => error,
+        }
+    }
+
+    /// Render the error as a human-friendly report similar to compiler diagnostics,
+    /// with `path` naming the parsed source:
+    ///
+    /// ```text
+    /// error: Invalid Uint value: '12x'
+    ///  --> network.dbc:3:5
+    ///   |
+    /// 3 | BO_ 12x Engine: 8 ECU
+    ///   |     ^^^
+    /// ```
+    #[must_use]
+    pub fn report(&self, path: &str) -> String {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Pest(error) => {
+                format!("error: invalid syntax\n{}", error.clone().with_path(path))
+            }
+            Self::Located { location, .. } => {
+                format!("error: {}\n{}", self.inner(), location.report(path))
+            }
+            error => format!("error: {error}\n --> {path}\n"),
+        }
+    }
+}
+
+/// Location of a parsed element in the DBC source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column in characters, starting at 1
+    pub column: usize,
+    /// Source text of the element
+    pub snippet: String,
+    /// The complete source line the element starts in
+    pub source_line: String,
+}
+
+impl SourceLocation {
+    /// Location of `snippet` starting at byte offset `start` of `input`
+    #[cfg(feature = "std")]
+    pub(crate) fn at(input: &str, start: usize, snippet: &str) -> Self {
+        let before = &input[..start];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[start..]
+            .find('\n')
+            .map_or(input.len(), |idx| start + idx);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: snippet.to_string(),
+            source_line: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+
+    /// Render the source line with the element underlined
+    fn report(&self, path: &str) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        // keep tabs so that the marker lines up with the source line
+        let indent: String = self
+            .source_line
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let available = self.source_line.chars().count() + 1 - self.column;
+        let width = self.snippet.lines().next().map_or(0, |l| l.chars().count());
+        let marker = "^".repeat(width.min(available).max(1));
+        format!(
+            "{gutter}--> {path}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {indent}{marker}\n",
+            self.line, self.column, self.line, self.source_line
+        )
+    }
+}
@@ -0,0 +1,554 @@
+//!
+//! Hash map based lookups over a parsed [`Dbc`]
+//!
+
+use std::collections::HashMap;
+
+use crate::ast::{
+    AttributeDefinition, AttributeValue, Comment, Dbc, ExtendedMultiplex, Message, MessageId, Node,
+    ObjectRef, Signal, SignalExtendedValueType, ValDescription, ValueDescription,
+};
+use crate::attribute::defined_for;
+use crate::codec::{CodecError, CodecResult, DecodedMessage, DecodedSignal};
+
+/// Read-only view of a [`Dbc`] with constant time lookups, built once with [`Dbc::index`].
+///
+/// The lookups return the same results as the corresponding methods of [`Dbc`],
+/// i.e. the first definition wins if an object is defined multiple times.
+#[derive(Clone, Debug)]
+pub struct DbcIndex<'a> {
+    dbc: &'a Dbc<'a>,
+    messages: HashMap<MessageId, &'a Message<'a>>,
+    message_names: HashMap<&'a str, &'a Message<'a>>,
+    signals: HashMap<(MessageId, &'a str), &'a Signal<'a>>,
+    nodes: HashMap<&'a str, &'a Node<'a>>,
+    transmitted: HashMap<&'a str, Vec<&'a Message<'a>>>,
+    message_comments: HashMap<MessageId, &'a str>,
+    signal_comments: HashMap<(MessageId, &'a str), &'a str>,
+    node_comments: HashMap<&'a str, &'a str>,
+    value_descriptions: HashMap<(MessageId, &'a str), &'a [ValDescription<'a>]>,
+    value_types: HashMap<(MessageId, &'a str), &'a SignalExtendedValueType>,
+    extended_multiplex: HashMap<MessageId, Vec<ExtendedMultiplex<'a>>>,
+    attribute_definitions: HashMap<&'a str, Vec<&'a AttributeDefinition<'a>>>,
+    attribute_defaults: HashMap<&'a str, &'a AttributeValue<'a>>,
+    relation_attribute_definitions: HashMap<&'a str, Vec<&'a AttributeDefinition<'a>>>,
+    relation_attribute_defaults: HashMap<&'a str, &'a AttributeValue<'a>>,
+    message_attributes: HashMap<(MessageId, &'a str), &'a AttributeValue<'a>>,
+    signal_attributes: HashMap<(MessageId, &'a str, &'a str), &'a AttributeValue<'a>>,
+    node_attributes: HashMap<(&'a str, &'a str), &'a AttributeValue<'a>>,
+}
+
+impl Dbc<'_> {
+    /// Build an index for constant time lookups and decoding, see [`DbcIndex`]
+    #[must_use]
+    pub fn index(&self) -> DbcIndex<'_> {
+        DbcIndex::new(self)
+    }
+}
+
+impl<'a> DbcIndex<'a> {
+    #[must_use]
+    pub fn new(dbc: &'a Dbc<'a>) -> Self {
+        let mut index = Self {
+            dbc,
+            messages: HashMap::with_capacity(dbc.messages.len()),
+            message_names: HashMap::with_capacity(dbc.messages.len()),
+            signals: HashMap::new(),
+            nodes: HashMap::with_capacity(dbc.nodes.len()),
+            transmitted: HashMap::new(),
+            message_comments: HashMap::new(),
+            signal_comments: HashMap::new(),
+            node_comments: HashMap::new(),
+            value_descriptions: HashMap::new(),
+            value_types: HashMap::new(),
+            extended_multiplex: HashMap::new(),
+            attribute_definitions: HashMap::new(),
+            attribute_defaults: HashMap::new(),
+            relation_attribute_definitions: HashMap::new(),
+            relation_attribute_defaults: HashMap::new(),
+            message_attributes: HashMap::new(),
+            signal_attributes: HashMap::new(),
+            node_attributes: HashMap::new(),
+        };
+
+        index.add_objects();
+        index.add_signal_details();
+        index.add_attributes();
+        index
+    }
+
+    /// The indexed database
+    #[must_use]
+    pub fn dbc(&self) -> &'a Dbc<'a> {
+        self.dbc
+    }
+
+    /// Lookup a message by id
+    #[must_use]
+    pub fn message(&self, message_id: MessageId) -> Option<&'a Message<'a>> {
+        self.messages.get(&message_id).copied()
+    }
+
+    /// Lookup a message by name
+    #[must_use]
+    pub fn message_by_name(&self, name: &str) -> Option<&'a Message<'a>> {
+        self.message_names.get(name).copied()
+    }
+
+    /// Lookup a signal of a message
+    #[must_use]
+    pub fn signal_by_name(
+        &self,
+        message_id: MessageId,
+        signal_name: &str,
+    ) -> Option<&'a Signal<'a>> {
+        self.signals.get(&(message_id, signal_name)).copied()
+    }
+
+    /// Lookup a node listed in `BU_`
+    #[must_use]
+    pub fn node(&self, name: &str) -> Option<&'a Node<'a>> {
+        self.nodes.get(name).copied()
+    }
+
+    /// Messages with `node` as their transmitter, in the order they are defined
+    #[must_use]
+    pub fn messages_transmitted_by(&self, node: &str) -> &[&'a Message<'a>] {
+        self.transmitted.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// Lookup a message comment
+    #[must_use]
+    pub fn message_comment(&self, message_id: MessageId) -> Option<&'a str> {
+        self.message_comments.get(&message_id).copied()
+    }
+
+    /// Lookup a signal comment
+    #[must_use]
+    pub fn signal_comment(&self, message_id: MessageId, signal_name: &str) -> Option<&'a str> {
+        self.signal_comments
+            .get(&(message_id, signal_name))
+            .copied()
+    }
+
+    /// Lookup a node comment
+    #[must_use]
+    pub fn node_comment(&self, node_name: &str) -> Option<&'a str> {
+        self.node_comments.get(node_name).copied()
+    }
+
+    /// Lookup value descriptions for signal
+    #[must_use]
+    pub fn value_descriptions_for_signal(
+        &self,
+        message_id: MessageId,
+        signal_name: &str,
+    ) -> Option<&'a [ValDescription<'a>]> {
+        self.value_descriptions
+            .get(&(message_id, signal_name))
+            .copied()
+    }
+
+    /// Lookup the extended value for a given signal
+    #[must_use]
+    pub fn extended_value_type_for_signal(
+        &self,
+        message_id: MessageId,
+        signal_name: &str,
+    ) -> Option<&'a SignalExtendedValueType> {
+        self.value_types.get(&(message_id, signal_name)).copied()
+    }
+
+    /// Lookup an assigned message-level (`BO_`) attribute value.
+    #[must_use]
+    pub fn message_attribute(
+        &self,
+        message_id: MessageId,
+        name: &str,
+    ) -> Option<&'a AttributeValue<'a>> {
+        self.message_attributes.get(&(message_id, name)).copied()
+    }
+
+    /// Lookup an assigned signal-level (`SG_`) attribute value.
+    #[must_use]
+    pub fn signal_attribute(
+        &self,
+        message_id: MessageId,
+        signal_name: &str,
+        name: &str,
+    ) -> Option<&'a AttributeValue<'a>> {
+        self.signal_attributes
+            .get(&(message_id, signal_name, name))
+            .copied()
+    }
+
+    /// Lookup an assigned node-level (`BU_`) attribute value.
+    #[must_use]
+    pub fn node_attribute(&self, node_name: &str, name: &str) -> Option<&'a AttributeValue<'a>> {
+        self.node_attributes.get(&(node_name, name)).copied()
+    }
+
+    /// Lookup the default of the attribute `name` for `object`, see [`Dbc::attribute_default`].
+    #[must_use]
+    pub fn attribute_default(
+        &self,
+        object: &ObjectRef,
+        name: &str,
+    ) -> Option<&'a AttributeValue<'a>> {
+        let (definitions, defaults) = if object.is_relation() {
+            (
+                &self.relation_attribute_definitions,
+                &self.relation_attribute_defaults,
+            )
+        } else {
+            (&self.attribute_definitions, &self.attribute_defaults)
+        };
+        match definitions.get(name) {
+            Some(defs) if !defs.iter().any(|def| defined_for(def, object)) => None,
+            _ => defaults.get(name).copied(),
+        }
+    }
+
+    /// Lookup a message-level attribute value.
+    /// Falls back to the default `BA_DEF_DEF_` if a value is not defined, see [`Dbc::resolved_attribute`].
+    #[must_use]
+    pub fn resolved_message_attribute(
+        &self,
+        message_id: MessageId,
+        name: &str,
+    ) -> Option<&'a AttributeValue<'a>> {
+        self.message_attribute(message_id, name).or_else(|| {
+            self.default_if(name, |def| matches!(def, AttributeDefinition::Message(..)))
+        })
+    }
+
+    /// Lookup a signal-level attribute value.
+    /// Falls back to the default `BA_DEF_DEF_` if a value is not defined, see [`Dbc::resolved_attribute`].
+    #[must_use]
+    pub fn resolved_signal_attribute(
+        &self,
+        message_id: MessageId,
+        signal_name: &str,
+        name: &str,
+    ) -> Option<&'a AttributeValue<'a>> {
+        self.signal_attribute(message_id, signal_name, name)
+            .or_else(|| self.default_if(name, |def| matches!(def, AttributeDefinition::Signal(..))))
+    }
+
+    /// The default of attribute `name` if one of its definitions is accepted by `defined_for`,
+    /// or if the attribute has no definition at all
+    fn default_if<F>(&self, name: &str, defined_for: F) -> Option<&'a AttributeValue<'a>>
+    where
+        F: Fn(&AttributeDefinition) -> bool,
+    {
+        match self.attribute_definitions.get(name) {
+            Some(defs) if !defs.iter().any(|def| defined_for(def)) => None,
+            _ => self.attribute_defaults.get(name).copied(),
+        }
+    }
+
+    /// Decode all signals of a message from a CAN frame payload, see [`Dbc::decode_message`]
+    pub fn decode_message(
+        &self,
+        message_id: MessageId,
+        data: &[u8],
+    ) -> CodecResult<Vec<DecodedSignal<'a>>> {
+        self.message_by_id(message_id)?
+            .decode_with(data, |signal| self.value_type(message_id, signal))
+    }
+
+    /// Encode physical signal values into a payload of the message, see [`Dbc::encode_message`]
+    pub fn encode_message<I, S>(&self, message_id: MessageId, values: I) -> CodecResult<Vec<u8>>
+    where
+        I: IntoIterator<Item = (S, f64)>,
+        S: AsRef<str>,
+    {
+        self.message_by_id(message_id)?
+            .encode_with(values, |signal| self.value_type(message_id, signal))
+    }
+
+    /// Decode the active signals of a multiplexed message, see [`Dbc::decode_multiplexed`]
+    pub fn decode_multiplexed(
+        &self,
+        message_id: MessageId,
+        data: &[u8],
+    ) -> CodecResult<DecodedMessage<'a>> {
+        let extended = self
+            .extended_multiplex
+            .get(&message_id)
+            .map_or(&[][..], Vec::as_slice);
+        self.message_by_id(message_id)?
+            .decode_multiplexed_with(data, extended, |signal| self.value_type(message_id, signal))
+    }
+
+    /// Index messages, signals and nodes
+    fn add_objects(&mut self) {
+        let dbc = self.dbc;
+        for message in &dbc.messages {
+            self.messages.entry(message.id).or_insert(message);
+            self.message_names
+                .entry(message.name.as_ref())
+                .or_insert(message);
+            if let Some(transmitter) = &message.transmitter {
+                self.transmitted
+                    .entry(transmitter.as_ref())
+                    .or_default()
+                    .push(message);
+            }
+        }
+        // signals are looked up in the first message with the id only
+        for (&id, message) in &self.messages {
+            for signal in &message.signals {
+                self.signals
+                    .entry((id, signal.name.as_ref()))
+                    .or_insert(signal);
+            }
+        }
+        for node in &dbc.nodes {
+            self.nodes.entry(node.0.as_ref()).or_insert(node);
+        }
+    }
+
+    /// Index comments, value descriptions, value types and extended multiplexing
+    fn add_signal_details(&mut self) {
+        let dbc = self.dbc;
+        for comment in &dbc.comments {
+            match comment {
+                Comment::Message { id, comment } => {
+                    self.message_comments.entry(*id).or_insert(comment);
+                }
+                Comment::Signal {
+                    message_id,
+                    name,
+                    comment,
+                } => {
+                    self.signal_comments
+                        .entry((*message_id, name.as_ref()))
+                        .or_insert(comment);
+                }
+                Comment::Node { name, comment } => {
+                    self.node_comments.entry(name.as_ref()).or_insert(comment);
+                }
+                Comment::EnvVar { .. } | Comment::Plain { .. } => {}
+            }
+        }
+        for description in &dbc.value_descriptions {
+            if let ValueDescription::Signal {
+                message_id,
+                name,
+                value_descriptions,
+            } = description
+            {
+                self.value_descriptions
+                    .entry((*message_id, name.as_ref()))
+                    .or_insert(value_descriptions);
+            }
+        }
+        for value_type in &dbc.signal_extended_value_type_list {
+            self.value_types
+                .entry((value_type.message_id, value_type.signal_name.as_ref()))
+                .or_insert(&value_type.signal_extended_value_type);
+        }
+        for extended in &dbc.extended_multiplex {
+            self.extended_multiplex
+                .entry(extended.message_id)
+                .or_default()
+                .push(extended.clone());
+        }
+    }
+
+    /// Index attribute values and defaults
+    fn add_attributes(&mut self) {
+        let dbc = self.dbc;
+        for def in &dbc.attribute_definitions {
+            self.attribute_definitions
+                .entry(def.name())
+                .or_default()
+                .push(def);
+        }
+        for default in &dbc.attribute_defaults {
+            self.attribute_defaults
+                .entry(default.name.as_ref())
+                .or_insert(&default.value);
+        }
+        for def in &dbc.relation_attribute_definitions {
+            self.relation_attribute_definitions
+                .entry(def.name())
+                .or_default()
+                .push(def);
+        }
+        for default in &dbc.relation_attribute_defaults {
+            self.relation_attribute_defaults
+                .entry(default.name.as_ref())
+                .or_insert(&default.value);
+        }
+        for value in &dbc.attribute_values_message {
+            self.message_attributes
+                .entry((value.message_id, value.name.as_ref()))
+                .or_insert(&value.value);
+        }
+        for value in &dbc.attribute_values_signal {
+            self.signal_attributes
+                .entry((
+                    value.message_id,
+                    value.signal_name.as_ref(),
+                    value.name.as_ref(),
+                ))
+                .or_insert(&value.value);
+        }
+        for value in &dbc.attribute_values_node {
+            self.node_attributes
+                .entry((value.node_name.as_ref(), value.name.as_ref()))
+                .or_insert(&value.value);
+        }
+    }
+
+    fn message_by_id(&self, message_id: MessageId) -> CodecResult<&'a Message<'a>> {
+        self.message(message_id)
+            .ok_or(CodecError::UnknownMessage(message_id))
+    }
+
+    fn value_type(&self, message_id: MessageId, signal: &Signal) -> SignalExtendedValueType {
+        self.extended_value_type_for_signal(message_id, &signal.name)
+            .copied()
+            .unwrap_or(SignalExtendedValueType::SignedOrUnsignedInteger)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DBC: &str = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU Gateway
+
+BO_ 100 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (0.1,0) [0|6553.5] "km/h" Gateway
+ SG_ Temp : 16|32@1- (1,0) [0|0] "" Gateway
+
+BO_ 200 Status: 1 Gateway
+ SG_ Mode : 0|2@1+ (1,0) [0|3] "" ECU
+
+CM_ BU_ ECU "engine control";
+CM_ BO_ 100 "engine message";
+CM_ SG_ 100 Speed "vehicle speed";
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 65535;
+BA_DEF_DEF_ "GenMsgCycleTime" 100;
+BA_DEF_ SG_ "GenSigStartValue" INT 0 100;
+BA_DEF_DEF_ "GenSigStartValue" 5;
+BA_ "GenMsgCycleTime" BO_ 200 10;
+VAL_ 200 Mode 0 "Off" 1 "On" ;
+SIG_VALTYPE_ 100 Temp : 1;
+"#;
+
+    #[test]
+    fn lookup_test() {
+        let dbc = Dbc::try_from(DBC).unwrap();
+        let index = dbc.index();
+        let engine = MessageId::Standard(100);
+        let status = MessageId::Standard(200);
+
+        assert_eq!(index.message(engine).unwrap().name, "Engine");
+        assert_eq!(index.message_by_name("Status").unwrap().id, status);
+        assert_eq!(
+            index.signal_by_name(engine, "Speed"),
+            dbc.signal_by_name(engine, "Speed")
+        );
+        assert_eq!(index.signal_by_name(status, "Speed"), None);
+        assert_eq!(index.node("Gateway"), Some(&Node("Gateway".into())));
+        let transmitted: Vec<_> = index
+            .messages_transmitted_by("ECU")
+            .iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(transmitted, [engine]);
+        assert!(index.messages_transmitted_by("Unknown").is_empty());
+
+        assert_eq!(index.message_comment(engine), dbc.message_comment(engine));
+        assert_eq!(index.signal_comment(engine, "Speed"), Some("vehicle speed"));
+        assert_eq!(index.node_comment("ECU"), Some("engine control"));
+        assert_eq!(
+            index.value_descriptions_for_signal(status, "Mode"),
+            dbc.value_descriptions_for_signal(status, "Mode")
+        );
+        assert_eq!(
+            index.extended_value_type_for_signal(engine, "Temp"),
+            Some(&SignalExtendedValueType::IEEEfloat32Bit)
+        );
+        assert_eq!(
+            index.resolved_message_attribute(status, "GenMsgCycleTime"),
+            Some(&AttributeValue::Uint(10))
+        );
+        assert_eq!(
+            index.resolved_message_attribute(engine, "GenMsgCycleTime"),
+            Some(&AttributeValue::Uint(100))
+        );
+
+        // defaults only apply to the kind of object the attribute is defined for
+        for name in ["GenMsgCycleTime", "GenSigStartValue"] {
+            assert_eq!(
+                index.resolved_message_attribute(engine, name),
+                dbc.resolved_message_attribute(engine, name)
+            );
+            assert_eq!(
+                index.resolved_signal_attribute(engine, "Speed", name),
+                dbc.resolved_signal_attribute(engine, "Speed", name)
+            );
+            for object in [
+                ObjectRef::Database,
+                ObjectRef::Message(engine),
+                ObjectRef::Signal {
+                    message_id: engine,
+                    signal_name: "Speed".into(),
+                },
+            ] {
+                assert_eq!(
+                    index.attribute_default(&object, name),
+                    dbc.attribute_default(&object, name)
+                );
+            }
+        }
+        assert_eq!(
+            index.resolved_message_attribute(engine, "GenSigStartValue"),
+            None
+        );
+        assert_eq!(
+            index.resolved_signal_attribute(engine, "Speed", "GenSigStartValue"),
+            Some(&AttributeValue::Uint(5))
+        );
+    }
+
+    #[test]
+    fn decode_test() {
+        let dbc = Dbc::try_from(DBC).unwrap();
+        let index = dbc.index();
+        let engine = MessageId::Standard(100);
+
+        let data = index
+            .encode_message(engine, [("Speed", 12.5), ("Temp", -4.5)])
+            .unwrap();
+        assert_eq!(
+            data,
+            dbc.encode_message(engine, [("Speed", 12.5), ("Temp", -4.5)])
+                .unwrap()
+        );
+        assert_eq!(
+            index.decode_message(engine, &data),
+            dbc.decode_message(engine, &data)
+        );
+        assert_eq!(
+            index.decode_multiplexed(engine, &data),
+            dbc.decode_multiplexed(engine, &data)
+        );
+        assert_eq!(
+            index.decode_message(MessageId::Standard(1), &data),
+            Err(CodecError::UnknownMessage(MessageId::Standard(1)))
+        );
+    }
+}
@@ -1,4 +1,7 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 // Re-export of `can_dbc_pest::encodings` to simplify usage
 #[cfg(feature = "encodings")]
@@ -7,16 +10,61 @@ pub use can_dbc_pest::{decode_cp1252, encodings};
 mod ast;
 pub use ast::*;
 
+mod error;
+pub use error::{DbcError, DbcResult, SourceLocation};
+
+#[cfg(feature = "std")]
 mod parser;
-pub use parser::{DbcError, DbcResult};
+#[cfg(feature = "std")]
+pub use parser::{ParseOptions, ParseWarning};
+
+#[cfg(feature = "std")]
+mod statements;
+mod writer;
+
+mod edit;
+pub use edit::{EditError, EditResult};
+
+mod diff;
+pub use diff::{Change, ChangeKind, DbcDiff};
+
+mod merge;
+pub use merge::{merge, Conflict, Merged};
+
+mod attribute;
+pub use attribute::{AttributeError, AttributeResult, TypedAttributeValue};
+
+mod reference;
+pub use reference::Reference;
+
+mod vector;
+pub use vector::{FrameFormat, SendType, SignalSendType};
+
+mod codec;
+pub use codec::{CodecError, CodecResult, DecodedMessage, DecodedSignal, MultiplexBranch};
+
+#[cfg(feature = "std")]
+mod index;
+#[cfg(feature = "std")]
+pub use index::DbcIndex;
+
+#[cfg(feature = "std")]
+mod validation;
+#[cfg(feature = "std")]
+pub use validation::{Diagnostic, DiagnosticCode, DiagnosticLocation, Severity};
+
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+pub use builder::{BuildError, DbcBuilder, MessageBuilder, SignalBuilder};
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test_helpers {
     use std::fmt::Debug;
 
     use can_dbc_pest::*;
 
-    use crate::parser::*;
+    use crate::DbcResult;
 
     /// Helper function to parse a snippet with a specific rule, returning just the rule's inner content already unwrapped
     pub fn parse(input: &str, rule: Rule) -> DbcResult<Pair<'_, Rule>> {
@@ -53,12 +101,12 @@ mod test_helpers {
     fn c_ident_vec_test() {
         use crate::Symbol;
         let def = "FZHL_DUSasb18 ";
-        let val = vec![Symbol(span(def, Rule::ident).to_string())];
-        assert_eq!(val, vec![Symbol("FZHL_DUSasb18".to_string())]);
+        let val = vec![Symbol(span(def, Rule::ident).into())];
+        assert_eq!(val, vec![Symbol("FZHL_DUSasb18".into())]);
 
         let def = "FZHL_DUSasb19,xkask_3298 ";
-        let val = vec![Symbol(span(def, Rule::ident).to_string())];
-        assert_eq!(val, vec![Symbol("FZHL_DUSasb19".to_string())],);
+        let val = vec![Symbol(span(def, Rule::ident).into())];
+        assert_eq!(val, vec![Symbol("FZHL_DUSasb19".into())],);
     }
 
     #[test]
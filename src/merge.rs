@@ -0,0 +1,651 @@
+//!
+//! Three-way merge of databases
+//!
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::ast::{Dbc, Message, MessageId, ObjectRef, ValueDescription};
+use crate::attribute::definition_key;
+use crate::writer::Quoted;
+
+/// Result of [`merge`]: the merged database and the edits that could not be merged
+#[derive(Clone, Debug, PartialEq)]
+pub struct Merged<'a> {
+    /// The merged database. Conflicting parts are taken from `ours`.
+    pub dbc: Dbc<'a>,
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Merged<'_> {
+    /// Whether all changes were merged without conflicts
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// A part of an object that was changed differently on both sides
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    /// The object the conflicting part belongs to
+    pub object: ObjectRef,
+    /// The conflicting part, e.g. `signal`, `comment` or `attribute "GenMsgCycleTime"`
+    pub part: String,
+    /// DBC text of the part in each version, `None` if the part does not exist in that version
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+}
+
+impl fmt::Display for Conflict {
+    /// Write the conflict with the DBC text of each version on its own line
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "conflict in {} of {}", self.part, self.object)?;
+        for (label, text) in [
+            ("base", &self.base),
+            ("ours", &self.ours),
+            ("theirs", &self.theirs),
+        ] {
+            match text {
+                Some(text) => writeln!(f, "  {label}: {}", text.trim_end())?,
+                None => writeln!(f, "  {label}: (none)")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Merge the changes made in `ours` and `theirs`, both derived from `base`.
+///
+/// Messages are matched by id, signals by name within their message, and comments, attribute values and
+/// value descriptions by the object they belong to. A change made on one side only is applied,
+/// an identical change on both sides is applied once. Different changes of the same part,
+/// e.g. the same signal or the comment of the same message, are reported as [`Conflict`]
+/// and the part is taken from `ours`. A message changed on both sides is merged per name, size,
+/// transmitter and signal.
+#[must_use]
+#[expect(
+    clippy::too_many_lines,
+    reason = "one entry per section of the database"
+)]
+pub fn merge<'a>(base: &Dbc<'a>, ours: &Dbc<'a>, theirs: &Dbc<'a>) -> Merged<'a> {
+    let mut merger = Merger {
+        conflicts: Vec::new(),
+    };
+    let database = || ObjectRef::Database;
+    let dbc = Dbc {
+        version: merger.value(
+            [&base.version, &ours.version, &theirs.version],
+            database,
+            "version",
+            text,
+        ),
+        new_symbols: merger.section(
+            [&base.new_symbols, &ours.new_symbols, &theirs.new_symbols],
+            Clone::clone,
+            |_| (database(), "new symbols".into()),
+        ),
+        bit_timing: merger.value(
+            [&base.bit_timing, &ours.bit_timing, &theirs.bit_timing],
+            database,
+            "bit timing",
+            |bit_timing| {
+                let values = bit_timing.as_ref()?.iter().map(ToString::to_string);
+                Some(values.collect::<Vec<_>>().join(", "))
+            },
+        ),
+        nodes: merger.section(
+            [&base.nodes, &ours.nodes, &theirs.nodes],
+            Clone::clone,
+            |node| (ObjectRef::Node(node.0.to_string()), "node".into()),
+        ),
+        value_tables: merger.section(
+            [&base.value_tables, &ours.value_tables, &theirs.value_tables],
+            |table| table.name.clone(),
+            |table| (database(), format!("value table {}", table.name)),
+        ),
+        messages: merger.messages([&base.messages, &ours.messages, &theirs.messages]),
+        message_transmitters: merger.section(
+            [
+                &base.message_transmitters,
+                &ours.message_transmitters,
+                &theirs.message_transmitters,
+            ],
+            |tx| tx.message_id,
+            |tx| (ObjectRef::Message(tx.message_id), "transmitters".into()),
+        ),
+        environment_variables: merger.section(
+            [
+                &base.environment_variables,
+                &ours.environment_variables,
+                &theirs.environment_variables,
+            ],
+            |env| env.name.clone(),
+            |env| {
+                let object = ObjectRef::EnvironmentVariable(env.name.to_string());
+                (object, "definition".into())
+            },
+        ),
+        environment_variable_data: merger.section(
+            [
+                &base.environment_variable_data,
+                &ours.environment_variable_data,
+                &theirs.environment_variable_data,
+            ],
+            |data| data.env_var_name.clone(),
+            |data| {
+                let object = ObjectRef::EnvironmentVariable(data.env_var_name.to_string());
+                (object, "data".into())
+            },
+        ),
+        signal_types: merger.section(
+            [&base.signal_types, &ours.signal_types, &theirs.signal_types],
+            |t| t.name.clone(),
+            |t| (database(), format!("signal type {}", t.name)),
+        ),
+        comments: merger.section(
+            [&base.comments, &ours.comments, &theirs.comments],
+            // plain comments have no key, each text is an entry of its own
+            |comment| match comment.object() {
+                ObjectRef::Database => (ObjectRef::Database, Some(comment.text().to_string())),
+                object => (object, None),
+            },
+            |comment| (comment.object(), "comment".into()),
+        ),
+        attribute_definitions: merger.section(
+            [
+                &base.attribute_definitions,
+                &ours.attribute_definitions,
+                &theirs.attribute_definitions,
+            ],
+            definition_key,
+            |def| (database(), attribute("definition of attribute", def.name())),
+        ),
+        relation_attribute_definitions: merger.section(
+            [
+                &base.relation_attribute_definitions,
+                &ours.relation_attribute_definitions,
+                &theirs.relation_attribute_definitions,
+            ],
+            definition_key,
+            |def| {
+                let part = attribute("definition of relation attribute", def.name());
+                (database(), part)
+            },
+        ),
+        attribute_defaults: merger.section(
+            [
+                &base.attribute_defaults,
+                &ours.attribute_defaults,
+                &theirs.attribute_defaults,
+            ],
+            |default| default.name.clone(),
+            |default| (database(), attribute("default of attribute", &default.name)),
+        ),
+        relation_attribute_defaults: merger.section(
+            [
+                &base.relation_attribute_defaults,
+                &ours.relation_attribute_defaults,
+                &theirs.relation_attribute_defaults,
+            ],
+            |default| default.name.clone(),
+            |default| {
+                let part = attribute("default of relation attribute", &default.name);
+                (database(), part)
+            },
+        ),
+        relation_attribute_values: merger.section(
+            [
+                &base.relation_attribute_values,
+                &ours.relation_attribute_values,
+                &theirs.relation_attribute_values,
+            ],
+            |value| (value.name.clone(), value.details.object()),
+            |value| {
+                let object = value.details.object();
+                (object, attribute("attribute", &value.name))
+            },
+        ),
+        attribute_values_database: merger.section(
+            [
+                &base.attribute_values_database,
+                &ours.attribute_values_database,
+                &theirs.attribute_values_database,
+            ],
+            |value| value.name.clone(),
+            |value| (database(), attribute("attribute", &value.name)),
+        ),
+        attribute_values_node: merger.section(
+            [
+                &base.attribute_values_node,
+                &ours.attribute_values_node,
+                &theirs.attribute_values_node,
+            ],
+            |value| (value.node_name.clone(), value.name.clone()),
+            |value| (value.object(), attribute("attribute", &value.name)),
+        ),
+        attribute_values_message: merger.section(
+            [
+                &base.attribute_values_message,
+                &ours.attribute_values_message,
+                &theirs.attribute_values_message,
+            ],
+            |value| (value.message_id, value.name.clone()),
+            |value| (value.object(), attribute("attribute", &value.name)),
+        ),
+        attribute_values_signal: merger.section(
+            [
+                &base.attribute_values_signal,
+                &ours.attribute_values_signal,
+                &theirs.attribute_values_signal,
+            ],
+            |value| {
+                (
+                    value.message_id,
+                    value.signal_name.clone(),
+                    value.name.clone(),
+                )
+            },
+            |value| (value.object(), attribute("attribute", &value.name)),
+        ),
+        attribute_values_env: merger.section(
+            [
+                &base.attribute_values_env,
+                &ours.attribute_values_env,
+                &theirs.attribute_values_env,
+            ],
+            |value| (value.variable_name.clone(), value.name.clone()),
+            |value| (value.object(), attribute("attribute", &value.name)),
+        ),
+        value_descriptions: merger.section(
+            [
+                &base.value_descriptions,
+                &ours.value_descriptions,
+                &theirs.value_descriptions,
+            ],
+            ValueDescription::object,
+            |description| (description.object(), "value descriptions".into()),
+        ),
+        category_definitions: merger.section(
+            [
+                &base.category_definitions,
+                &ours.category_definitions,
+                &theirs.category_definitions,
+            ],
+            |def| def.id,
+            |def| (database(), format!("category definition {}", def.id)),
+        ),
+        categories: merger.section(
+            [&base.categories, &ours.categories, &theirs.categories],
+            Clone::clone,
+            |category| (category.object.object(), "category".into()),
+        ),
+        filter: merger.section(
+            [&base.filter, &ours.filter, &theirs.filter],
+            Clone::clone,
+            |_| (database(), "filter".into()),
+        ),
+        signal_type_refs: merger.section(
+            [
+                &base.signal_type_refs,
+                &ours.signal_type_refs,
+                &theirs.signal_type_refs,
+            ],
+            |r| (r.message_id, r.signal_name.clone()),
+            |r| (r.object(), "signal type".into()),
+        ),
+        signal_groups: merger.section(
+            [
+                &base.signal_groups,
+                &ours.signal_groups,
+                &theirs.signal_groups,
+            ],
+            |group| (group.message_id, group.name.clone()),
+            |group| {
+                let object = ObjectRef::Message(group.message_id);
+                (object, format!("signal group {}", group.name))
+            },
+        ),
+        signal_extended_value_type_list: merger.section(
+            [
+                &base.signal_extended_value_type_list,
+                &ours.signal_extended_value_type_list,
+                &theirs.signal_extended_value_type_list,
+            ],
+            |ext| (ext.message_id, ext.signal_name.clone()),
+            |ext| (ext.object(), "extended value type".into()),
+        ),
+        extended_multiplex: merger.section(
+            [
+                &base.extended_multiplex,
+                &ours.extended_multiplex,
+                &theirs.extended_multiplex,
+            ],
+            |ext| {
+                let multiplexor = ext.multiplexor_signal_name.clone();
+                (ext.message_id, ext.signal_name.clone(), multiplexor)
+            },
+            |ext| {
+                let object = ext.object();
+                let part = format!("multiplexing by {}", ext.multiplexor_signal_name);
+                (object, part)
+            },
+        ),
+    };
+    Merged {
+        dbc,
+        conflicts: merger.conflicts,
+    }
+}
+
+struct Merger {
+    conflicts: Vec<Conflict>,
+}
+
+impl Merger {
+    /// Merge a single value, reporting a conflict if both sides changed it differently
+    fn value<T: Clone + PartialEq>(
+        &mut self,
+        [base, ours, theirs]: [&T; 3],
+        object: impl Fn() -> ObjectRef,
+        part: &str,
+        text: impl Fn(&T) -> Option<String>,
+    ) -> T {
+        if let Ok(value) = pick(Some(base), Some(ours), Some(theirs)) {
+            value.unwrap_or(ours).clone()
+        } else {
+            self.conflicts.push(Conflict {
+                object: object(),
+                part: part.to_string(),
+                base: text(base),
+                ours: text(ours),
+                theirs: text(theirs),
+            });
+            ours.clone()
+        }
+    }
+
+    /// Merge a section of items identified by `key`. The first item of a key wins, like the lookups of [`Dbc`].
+    ///
+    /// Items keep the order of `ours`, items only added by `theirs` are appended in their order.
+    fn section<T, K>(
+        &mut self,
+        [base, ours, theirs]: [&[T]; 3],
+        key: impl Fn(&T) -> K,
+        describe: impl Fn(&T) -> (ObjectRef, String),
+    ) -> Vec<T>
+    where
+        T: Clone + PartialEq + fmt::Display,
+        K: Ord,
+    {
+        let maps = [base, ours, theirs].map(|items| by_key(items, &key));
+        let mut done = BTreeSet::new();
+        let mut merged = Vec::with_capacity(maps[1].len().max(maps[2].len()));
+        for item_key in ours.iter().chain(theirs).map(&key) {
+            let [b, o, t] = maps.each_ref().map(|map| map.get(&item_key).copied());
+            if !done.insert(item_key) {
+                continue;
+            }
+            if let Ok(item) = pick(b, o, t) {
+                merged.extend(item.cloned());
+            } else {
+                self.conflict([b, o, t], &describe);
+                merged.extend(o.cloned());
+            }
+        }
+        merged
+    }
+
+    fn messages<'a>(&mut self, [base, ours, theirs]: [&[Message<'a>]; 3]) -> Vec<Message<'a>> {
+        let key = |message: &Message| message.id;
+        let maps = [base, ours, theirs].map(|messages| by_key(messages, key));
+        let mut done = BTreeSet::new();
+        let mut merged = Vec::with_capacity(maps[1].len().max(maps[2].len()));
+        for id in ours.iter().chain(theirs).map(key) {
+            if !done.insert(id) {
+                continue;
+            }
+            let [b, o, t] = maps.each_ref().map(|map| map.get(&id).copied());
+            match (pick(b, o, t), b, o, t) {
+                (Ok(message), ..) => merged.extend(message.cloned()),
+                (Err(()), Some(b), Some(o), Some(t)) => merged.push(self.message(b, o, t)),
+                (Err(()), b, o, t) => {
+                    self.conflict([b, o, t], |_| (ObjectRef::Message(id), "definition".into()));
+                    merged.extend(o.cloned());
+                }
+            }
+        }
+        merged
+    }
+
+    /// Merge a message changed on both sides
+    fn message<'a>(
+        &mut self,
+        base: &Message<'a>,
+        ours: &Message<'a>,
+        theirs: &Message<'a>,
+    ) -> Message<'a> {
+        let object = || ObjectRef::Message(ours.id);
+        Message {
+            id: ours.id,
+            name: self.value([&base.name, &ours.name, &theirs.name], object, "name", text),
+            size: self.value([&base.size, &ours.size, &theirs.size], object, "size", text),
+            transmitter: self.value(
+                [&base.transmitter, &ours.transmitter, &theirs.transmitter],
+                object,
+                "transmitter",
+                |transmitter| transmitter.as_ref().map(ToString::to_string),
+            ),
+            signals: self.section(
+                [&base.signals, &ours.signals, &theirs.signals],
+                |s| s.name.clone(),
+                |s| (signal(ours.id, &s.name), "definition".into()),
+            ),
+        }
+    }
+
+    fn conflict<T: fmt::Display>(
+        &mut self,
+        [base, ours, theirs]: [Option<&T>; 3],
+        describe: impl Fn(&T) -> (ObjectRef, String),
+    ) {
+        let Some((object, part)) = ours.or(theirs).or(base).map(describe) else {
+            return;
+        };
+        let text = |item: Option<&T>| item.map(ToString::to_string);
+        self.conflicts.push(Conflict {
+            object,
+            part,
+            base: text(base),
+            ours: text(ours),
+            theirs: text(theirs),
+        });
+    }
+}
+
+/// Three-way merge of a single part: the side that changed wins, `Err` if both changed it differently
+fn pick<'t, T: PartialEq>(
+    base: Option<&'t T>,
+    ours: Option<&'t T>,
+    theirs: Option<&'t T>,
+) -> Result<Option<&'t T>, ()> {
+    if ours == theirs || theirs == base {
+        Ok(ours)
+    } else if ours == base {
+        Ok(theirs)
+    } else {
+        Err(())
+    }
+}
+
+#[expect(clippy::unnecessary_wraps)]
+fn text<T: fmt::Display>(value: &T) -> Option<String> {
+    Some(value.to_string())
+}
+
+/// Items by their key, the first item of a key wins
+fn by_key<T, K: Ord>(items: &[T], key: impl Fn(&T) -> K) -> BTreeMap<K, &T> {
+    let mut map = BTreeMap::new();
+    for item in items {
+        map.entry(key(item)).or_insert(item);
+    }
+    map
+}
+
+fn signal(message_id: MessageId, name: &str) -> ObjectRef {
+    ObjectRef::Signal {
+        message_id,
+        signal_name: name.to_string(),
+    }
+}
+
+fn attribute(label: &str, name: &str) -> String {
+    format!("{label} {}", Quoted(name))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    const BASE: &str = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU Dashboard
+
+BO_ 100 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (0.1,0) [0|6553.5] "rpm" Dashboard
+ SG_ State : 16|2@1+ (1,0) [0|3] "" Dashboard
+
+BO_ 200 Brake: 2 ECU
+ SG_ Pressure : 0|16@1+ (1,0) [0|0] "bar" Dashboard
+
+CM_ BO_ 100 "engine status";
+
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 10000;
+BA_ "GenMsgCycleTime" BO_ 100 100;
+"#;
+
+    fn parse(text: &str) -> Dbc<'_> {
+        Dbc::try_from(text).unwrap()
+    }
+
+    #[test]
+    fn clean_merge_test() {
+        let ours = BASE
+            .replace("BU_: ECU Dashboard", "BU_: ECU Dashboard Logger")
+            .replace(
+                r#"SG_ Speed : 0|16@1+ (0.1,0) [0|6553.5] "rpm" Dashboard"#,
+                r#"SG_ Speed : 0|16@1+ (0.1,0) [0|6553.5] "rpm" Dashboard,Logger"#,
+            )
+            .replace("BO_ 100 100;", "BO_ 100 50;");
+        let theirs = BASE
+            .replace(
+                r#" SG_ State : 16|2@1+ (1,0) [0|3] "" Dashboard"#,
+                r#" SG_ State : 16|2@1+ (1,0) [0|3] "" Dashboard
+ SG_ Load : 24|8@1+ (1,0) [0|100] "%" Dashboard"#,
+            )
+            .replace(
+                "\nBO_ 200 Brake: 2 ECU\n SG_ Pressure : 0|16@1+ (1,0) [0|0] \"bar\" Dashboard\n",
+                "",
+            )
+            .replace("engine status", "engine state");
+        let expected = ours
+            .replace(
+                r#" SG_ State : 16|2@1+ (1,0) [0|3] "" Dashboard"#,
+                r#" SG_ State : 16|2@1+ (1,0) [0|3] "" Dashboard
+ SG_ Load : 24|8@1+ (1,0) [0|100] "%" Dashboard"#,
+            )
+            .replace(
+                "\nBO_ 200 Brake: 2 ECU\n SG_ Pressure : 0|16@1+ (1,0) [0|0] \"bar\" Dashboard\n",
+                "",
+            )
+            .replace("engine status", "engine state");
+
+        let merged = merge(&parse(BASE), &parse(&ours), &parse(&theirs));
+        assert!(merged.is_clean(), "{:?}", merged.conflicts);
+        assert_eq!(merged.dbc, parse(&expected));
+    }
+
+    #[test]
+    fn identical_changes_test() {
+        let changed = BASE.replace("BO_ 100 100;", "BO_ 100 20;");
+        let merged = merge(&parse(BASE), &parse(&changed), &parse(&changed));
+        assert!(merged.is_clean());
+        assert_eq!(merged.dbc, parse(&changed));
+    }
+
+    #[test]
+    fn attribute_definition_test() {
+        let ours = BASE.replace("INT 0 10000", "INT 0 20000");
+        let theirs = BASE.replace("INT 0 10000", "INT 0 30000");
+        let merged = merge(&parse(BASE), &parse(&ours), &parse(&theirs));
+        assert_eq!(merged.dbc, parse(&ours));
+        assert_eq!(
+            merged.conflicts,
+            [Conflict {
+                object: ObjectRef::Database,
+                part: r#"definition of attribute "GenMsgCycleTime""#.into(),
+                base: Some(r#"BA_DEF_ BO_ "GenMsgCycleTime" INT 0 10000;"#.into()),
+                ours: Some(r#"BA_DEF_ BO_ "GenMsgCycleTime" INT 0 20000;"#.into()),
+                theirs: Some(r#"BA_DEF_ BO_ "GenMsgCycleTime" INT 0 30000;"#.into()),
+            }]
+        );
+
+        // a change on one side only is applied
+        let merged = merge(&parse(BASE), &parse(BASE), &parse(&theirs));
+        assert!(merged.is_clean());
+        assert_eq!(merged.dbc, parse(&theirs));
+    }
+
+    #[test]
+    fn conflicts_test() {
+        let ours = BASE
+            .replace("0|16@1+ (0.1,0)", "0|16@1+ (0.2,0)")
+            .replace("engine status", "engine status, ours")
+            .replace("Engine: 8 ECU", "EngineOurs: 8 ECU")
+            .replace("BO_ 200 Brake: 2", "BO_ 200 Brake: 4");
+        let theirs = BASE
+            .replace("0|16@1+ (0.1,0)", "0|16@1+ (0.5,0)")
+            .replace("engine status", "engine status, theirs")
+            .replace("Engine: 8 ECU", "EngineTheirs: 8 ECU")
+            .replace(
+                "\nBO_ 200 Brake: 2 ECU\n SG_ Pressure : 0|16@1+ (1,0) [0|0] \"bar\" Dashboard\n",
+                "",
+            );
+
+        let merged = merge(&parse(BASE), &parse(&ours), &parse(&theirs));
+        let conflicts: Vec<_> = merged
+            .conflicts
+            .iter()
+            .map(|c| (c.object.to_string(), c.part.as_str()))
+            .collect();
+        assert_eq!(
+            conflicts,
+            [
+                ("message 100".to_string(), "name"),
+                ("signal Speed of message 100".to_string(), "definition"),
+                ("message 200".to_string(), "definition"),
+                ("message 100".to_string(), "comment"),
+            ]
+        );
+        // conflicting parts are taken from ours
+        assert_eq!(merged.dbc, parse(&ours));
+        assert_eq!(
+            merged.conflicts[1].to_string(),
+            r#"conflict in definition of signal Speed of message 100
+  base: SG_ Speed : 0|16@1+ (0.1,0) [0|6553.5] "rpm" Dashboard
+  ours: SG_ Speed : 0|16@1+ (0.2,0) [0|6553.5] "rpm" Dashboard
+  theirs: SG_ Speed : 0|16@1+ (0.5,0) [0|6553.5] "rpm" Dashboard
+"#
+        );
+        assert_eq!(merged.conflicts[2].theirs, None);
+    }
+}
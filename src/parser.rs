@@ -2,47 +2,32 @@
 //! Parser module for DBC files using pest
 //!
 
+use std::borrow::Cow;
+use std::fmt;
+
 use can_dbc_pest::{Error as PestError, Pair, Pairs, Rule};
+use pest::error::InputLocation;
 
 use crate::ast::NumericValue;
+use crate::error::{DbcError, DbcResult, SourceLocation};
+
+impl DbcError {
+    /// Attach the location of `pair`, unless the error already has a location
+    pub(crate) fn at(self, pair: &Pair<'_, Rule>) -> Self {
+        match self {
+            Self::Located { .. } | Self::Pest(_) => self,
+            error => error.at_location(SourceLocation::of(pair)),
+        }
+    }
+}
 
-pub type DbcResult<T> = Result<T, DbcError>;
-
-/// Error type for DBC parsing operations
-#[derive(Debug, Clone, PartialEq, thiserror::Error)]
-pub enum DbcError {
-    #[error("No more rules expected, but found: {0:?}")]
-    ExpectedEmpty(Rule),
-    #[error("Expected rule: {0:?}, found: {1:?}")]
-    ExpectedRule(Rule, Rule),
-    #[error("Expected one of these rules: {0:?}, found: {1:?}")]
-    ExpectedOneOfRules(Vec<Rule>, Rule),
-    #[error("Expected a quoted string or a number, found: {0:?}")]
-    ExpectedStrNumber(Rule),
-    #[error("Invalid Float value: '{0}'")]
-    InvalidFloat(String),
-    #[error("Invalid Int value: '{0}'")]
-    InvalidInt(String),
-    #[error("Invalid Uint value: '{0}'")]
-    InvalidUint(String),
-    #[error("Message ID out of range: {0}")]
-    MessageIdOutOfRange(u64),
-    #[error("Multiple multiplexors defined for a message")]
-    MultipleMultiplexors,
-    #[error("No more parsing rules available")]
-    NoMoreRules,
-    #[error("Feature not implemented: {0}")]
-    NotImplemented(&'static str),
-    #[error(transparent)]
-    Pest(Box<PestError<Rule>>),
-    #[error("Signal defined without an associated message")]
-    SignalWithoutMessage,
-    #[error("Unknown multiplex indicator: {0}")]
-    UnknownMultiplexIndicator(String),
-    #[error("Unknown rule: {0:?}")]
-    UnknownRule(Rule),
-    #[error("Invalid numeric value: '{0}'")]
-    InvalidNumericValue(String),
+impl SourceLocation {
+    pub(crate) fn of(pair: &Pair<'_, Rule>) -> Self {
+        let span = pair.as_span();
+        // point at the element itself, many rules include leading newlines
+        let text = span.as_str().trim_start();
+        Self::at(pair.get_input(), span.end() - text.len(), text.trim_end())
+    }
 }
 
 impl From<PestError<Rule>> for DbcError {
@@ -51,29 +36,96 @@ impl From<PestError<Rule>> for DbcError {
     }
 }
 
+/// Options controlling how a DBC file is parsed, see [`Dbc::parse_with_options`](crate::Dbc::parse_with_options)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    lenient: bool,
+}
+
+impl ParseOptions {
+    /// In lenient mode, statements that can't be parsed are skipped and reported as [`ParseWarning`]s
+    /// instead of failing the whole file. Lines with syntax errors are skipped as well.
+    #[must_use]
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    #[must_use]
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+}
+
+/// A statement or line skipped while parsing in lenient mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseWarning {
+    /// Location of the skipped statement or line
+    pub location: SourceLocation,
+    /// The error that caused the statement to be skipped
+    pub reason: DbcError,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Skipped statement at line {}, column {}: {}",
+            self.location.line,
+            self.location.column,
+            self.reason.inner()
+        )
+    }
+}
+
+/// Replace the source line containing a syntax error with spaces, keeping the byte offsets of all other lines intact.
+/// Returns the location of the skipped line and the new input, or `None` if the line is empty and can't be skipped.
+pub(crate) fn skip_error_line(
+    input: &str,
+    error: &PestError<Rule>,
+) -> Option<(SourceLocation, String)> {
+    let pos = match error.location {
+        InputLocation::Pos(pos) | InputLocation::Span((pos, _)) => pos,
+    };
+    let line_start = input[..pos].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = input[pos..].find('\n').map_or(input.len(), |idx| pos + idx);
+    let line = &input[line_start..line_end];
+    let text = line.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let location = SourceLocation::at(input, line_start + line.find(text)?, text);
+
+    let mut skipped = String::with_capacity(input.len());
+    skipped.push_str(&input[..line_start]);
+    skipped.extend(line.bytes().map(|_| ' '));
+    skipped.push_str(&input[line_end..]);
+    Some((location, skipped))
+}
+
 /// Helper function to get the next pair and validate its rule
-pub(crate) fn next<'a>(iter: &'a mut Pairs<Rule>) -> DbcResult<Pair<'a, Rule>> {
+pub(crate) fn next<'i>(iter: &mut Pairs<'i, Rule>) -> DbcResult<Pair<'i, Rule>> {
     iter.next().ok_or(DbcError::NoMoreRules)
 }
 
 /// Helper function to get the next pair and validate its rule
-pub(crate) fn next_rule<'a>(
-    iter: &'a mut Pairs<Rule>,
+pub(crate) fn next_rule<'i>(
+    iter: &mut Pairs<'i, Rule>,
     expected: Rule,
-) -> DbcResult<Pair<'a, Rule>> {
+) -> DbcResult<Pair<'i, Rule>> {
     next(iter).and_then(|pair| {
         if pair.as_rule() == expected {
             Ok(pair)
         } else {
-            Err(DbcError::ExpectedRule(expected, pair.as_rule()))
+            Err(DbcError::ExpectedRule(expected, pair.as_rule()).at(&pair))
         }
     })
 }
 
-pub(crate) fn next_optional_rule<'a>(
-    iter: &'a mut Pairs<Rule>,
+pub(crate) fn next_optional_rule<'i>(
+    iter: &mut Pairs<'i, Rule>,
     expected: Rule,
-) -> Option<Pair<'a, Rule>> {
+) -> Option<Pair<'i, Rule>> {
     if let Some(pair) = iter.peek() {
         if pair.as_rule() == expected {
             return iter.next();
@@ -82,30 +134,33 @@ pub(crate) fn next_optional_rule<'a>(
     None
 }
 
-/// Helper function to get the next pair, ensure it matches the expected rule, and convert to string
-pub(crate) fn next_string(iter: &mut Pairs<Rule>, expected: Rule) -> DbcResult<String> {
-    Ok(next_rule(iter, expected)?.as_str().to_string())
+/// Helper function to get the next pair, ensure it matches the expected rule, and borrow its text
+pub(crate) fn next_string<'i>(
+    iter: &mut Pairs<'i, Rule>,
+    expected: Rule,
+) -> DbcResult<Cow<'i, str>> {
+    Ok(Cow::Borrowed(next_rule(iter, expected)?.as_str()))
 }
 
 /// Helper function to get a single pair and validate its rule
-pub(crate) fn single_inner(pair: Pair<Rule>, expected: Rule) -> DbcResult<Pair<Rule>> {
+pub(crate) fn single_inner(pair: Pair<'_, Rule>, expected: Rule) -> DbcResult<Pair<'_, Rule>> {
     let mut iter = pair.into_inner();
     let pair = iter.next().ok_or(DbcError::NoMoreRules)?;
     if pair.as_rule() != expected {
-        Err(DbcError::ExpectedRule(expected, pair.as_rule()))
+        Err(DbcError::ExpectedRule(expected, pair.as_rule()).at(&pair))
     } else if let Some(next) = iter.next() {
-        Err(DbcError::ExpectedEmpty(next.as_rule()))
+        Err(DbcError::ExpectedEmpty(next.as_rule()).at(&next))
     } else {
         Ok(pair)
     }
 }
 
 /// Helper function to validate a pair's rule matches the expected rule
-pub(crate) fn validated(pair: Pair<Rule>, expected: Rule) -> DbcResult<Pair<Rule>> {
+pub(crate) fn validated(pair: Pair<'_, Rule>, expected: Rule) -> DbcResult<Pair<'_, Rule>> {
     if pair.as_rule() == expected {
         Ok(pair)
     } else {
-        Err(DbcError::ExpectedRule(expected, pair.as_rule()))
+        Err(DbcError::ExpectedRule(expected, pair.as_rule()).at(&pair))
     }
 }
 
@@ -113,9 +168,9 @@ pub(crate) fn validated_inner(pair: Pair<'_, Rule>, expected: Rule) -> DbcResult
     Ok(validated(pair, expected)?.into_inner())
 }
 
-/// Helper function to get a single pair, validate its rule, and convert to string
-pub(crate) fn single_inner_str(pair: Pair<Rule>, expected: Rule) -> DbcResult<String> {
-    Ok(single_inner(pair, expected)?.as_str().to_string())
+/// Helper function to get a single pair, validate its rule, and borrow its text
+pub(crate) fn single_inner_str(pair: Pair<'_, Rule>, expected: Rule) -> DbcResult<Cow<'_, str>> {
+    Ok(Cow::Borrowed(single_inner(pair, expected)?.as_str()))
 }
 
 /// Helper function to collect all remaining pairs of a specific rule type
@@ -134,19 +189,22 @@ pub(crate) fn collect_expected<'a, T: TryFrom<Pair<'a, Rule>, Error = DbcError>>
         if pair.as_rule() == expected {
             pair.try_into()
         } else {
-            Err(DbcError::ExpectedRule(expected, pair.as_rule()))
+            Err(DbcError::ExpectedRule(expected, pair.as_rule()).at(&pair))
         }
     })
     .collect()
 }
 
-/// Helper function to collect all remaining pairs of a specific rule type and convert to strings
-pub(crate) fn collect_strings(iter: &mut Pairs<Rule>, expected: Rule) -> DbcResult<Vec<String>> {
+/// Helper function to collect all remaining pairs of a specific rule type and borrow their text
+pub(crate) fn collect_strings<'i>(
+    iter: &mut Pairs<'i, Rule>,
+    expected: Rule,
+) -> DbcResult<Vec<Cow<'i, str>>> {
     iter.map(|pair| {
         if pair.as_rule() == expected {
-            Ok(pair.as_str().to_string())
+            Ok(Cow::Borrowed(pair.as_str()))
         } else {
-            Err(DbcError::ExpectedRule(expected, pair.as_rule()))
+            Err(DbcError::ExpectedRule(expected, pair.as_rule()).at(&pair))
         }
     })
     .collect()
@@ -156,7 +214,7 @@ pub(crate) fn is_vector_placeholder(value: &str) -> bool {
     matches!(value, "" | "Vector__XXX" | "VectorXXX" | "VECTOR__XXX")
 }
 
-pub(crate) fn node_name_or_none(value: String) -> Option<String> {
+pub(crate) fn node_name_or_none(value: Cow<'_, str>) -> Option<Cow<'_, str>> {
     if is_vector_placeholder(&value) {
         None
     } else {
@@ -164,23 +222,52 @@ pub(crate) fn node_name_or_none(value: String) -> Option<String> {
     }
 }
 
-pub(crate) fn collect_node_names(iter: &mut Pairs<Rule>, expected: Rule) -> DbcResult<Vec<String>> {
+pub(crate) fn collect_node_names<'i>(
+    iter: &mut Pairs<'i, Rule>,
+    expected: Rule,
+) -> DbcResult<Vec<Cow<'i, str>>> {
     collect_strings(iter, expected).map(|v| v.into_iter().filter_map(node_name_or_none).collect())
 }
 
 /// Helper function to ensure the iterator is empty (no more items)
 pub(crate) fn expect_empty(iter: &Pairs<Rule>) -> DbcResult<()> {
     iter.peek()
-        .map_or(Ok(()), |v| Err(DbcError::ExpectedEmpty(v.as_rule())))
+        .map_or(Ok(()), |v| Err(DbcError::ExpectedEmpty(v.as_rule()).at(&v)))
 }
 
-/// Helper function to extract string content from `quoted_str` rule
-pub(crate) fn inner_str(pair: Pair<Rule>) -> String {
+/// Helper function to extract the unescaped string content from `quoted_str` rule
+pub(crate) fn inner_str(pair: Pair<'_, Rule>) -> Cow<'_, str> {
     // panics because pest grammar ensures this
-    next_rule(&mut pair.into_inner(), Rule::string)
-        .expect("string")
-        .as_str()
-        .to_string()
+    unescape(
+        next_rule(&mut pair.into_inner(), Rule::string)
+            .expect("string")
+            .as_str(),
+    )
+}
+
+/// Unescape the content of a quoted string: `\"` becomes `"` and `\\` becomes `\`.
+/// Other characters, including line breaks and unknown escape sequences, are kept as is.
+/// The value is only copied if it contains escape sequences.
+///
+/// A trailing unescaped `"` is dropped: it is written after a trailing backslash,
+/// which the grammar would otherwise read as an escaped closing quote, see [`Quoted`](crate::writer::Quoted).
+pub(crate) fn unescape(value: &str) -> Cow<'_, str> {
+    if !value.contains('\\') {
+        return Cow::Borrowed(value);
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(&escaped @ ('"' | '\\'))) => {
+                result.push(escaped);
+                chars.next();
+            }
+            ('"', None) => {}
+            _ => result.push(c),
+        }
+    }
+    Cow::Owned(result)
 }
 
 /// Helper function to parse an integer from a pest pair
@@ -188,7 +275,7 @@ pub(crate) fn parse_int(pair: &Pair<Rule>) -> DbcResult<i64> {
     let value = pair.as_str();
     value
         .parse::<i64>()
-        .map_err(|_| DbcError::InvalidInt(value.to_string()))
+        .map_err(|_| DbcError::InvalidInt(value.to_string()).at(pair))
 }
 
 /// Helper function to parse an unsigned integer from a pest pair
@@ -196,7 +283,7 @@ pub(crate) fn parse_uint(pair: &Pair<Rule>) -> DbcResult<u64> {
     let value = pair.as_str();
     value
         .parse::<u64>()
-        .map_err(|_| DbcError::InvalidUint(value.to_string()))
+        .map_err(|_| DbcError::InvalidUint(value.to_string()).at(pair))
 }
 
 /// Helper function to parse a float from a pest pair
@@ -204,7 +291,14 @@ pub(crate) fn parse_float(pair: &Pair<Rule>) -> DbcResult<f64> {
     let value = pair.as_str();
     value
         .parse::<f64>()
-        .map_err(|_| DbcError::InvalidFloat(value.to_string()))
+        .map_err(|_| DbcError::InvalidFloat(value.to_string()).at(pair))
+}
+
+/// Helper function to parse a `NumericValue` from a pest pair
+pub(crate) fn parse_numeric(pair: &Pair<Rule>) -> DbcResult<NumericValue> {
+    pair.as_str()
+        .parse::<NumericValue>()
+        .map_err(|e| e.at(pair))
 }
 
 /// Helper function to parse the next uint from the iterator
@@ -223,7 +317,10 @@ pub(crate) fn parse_next_float(iter: &mut Pairs<Rule>, expected: Rule) -> DbcRes
 }
 
 /// Helper function to parse the next string from the iterator
-pub(crate) fn parse_next_inner_str(iter: &mut Pairs<Rule>, expected: Rule) -> DbcResult<String> {
+pub(crate) fn parse_next_inner_str<'i>(
+    iter: &mut Pairs<'i, Rule>,
+    expected: Rule,
+) -> DbcResult<Cow<'i, str>> {
     Ok(inner_str(next_rule(iter, expected)?))
 }
 
@@ -243,12 +340,8 @@ pub(crate) fn parse_min_max_int(pair: Pair<Rule>) -> DbcResult<(i64, i64)> {
 pub(crate) fn parse_min_max_numeric(pair: Pair<Rule>) -> DbcResult<(NumericValue, NumericValue)> {
     let mut pairs = pair.into_inner();
 
-    let min_val = next_rule(&mut pairs, Rule::minimum)?
-        .as_str()
-        .parse::<NumericValue>()?;
-    let max_val = next_rule(&mut pairs, Rule::maximum)?
-        .as_str()
-        .parse::<NumericValue>()?;
+    let min_val = parse_numeric(&next_rule(&mut pairs, Rule::minimum)?)?;
+    let max_val = parse_numeric(&next_rule(&mut pairs, Rule::maximum)?)?;
     expect_empty(&pairs).expect("pest grammar ensures no extra items");
 
     Ok((min_val, max_val))
@@ -0,0 +1,401 @@
+//!
+//! Uniform lookups of the statements referring to an [`ObjectRef`]
+//!
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::ast::{AttributeValue, Comment, Dbc, MessageId, ObjectKey, ObjectRef};
+use crate::writer::Quoted;
+
+/// A statement referring to an object, see [`Dbc::references_to`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reference {
+    /// `CM_` comment of the object
+    Comment,
+    /// `BA_` or `BA_REL_` value of the named attribute assigned to the object
+    AttributeValue(String),
+    /// `VAL_` value descriptions of the signal or environment variable
+    ValueDescriptions,
+    /// `SIG_VALTYPE_` extended value type of the signal
+    ExtendedValueType,
+    /// `SIG_TYPE_REF_` signal type of the signal
+    SignalType,
+    /// `SIG_GROUP_` containing the signal
+    SignalGroup(String),
+    /// `SG_MUL_VAL_` entry the signal is part of, either as multiplexed signal or as multiplexor
+    ExtendedMultiplex {
+        signal_name: String,
+        multiplexor_signal_name: String,
+    },
+    /// `CAT_` assignment of the category with the given id
+    Category(u64),
+    /// The node transmits the message, by `BO_` or `BO_TX_BU_`
+    Transmitter(MessageId),
+    /// `BO_TX_BU_` list of additional transmitters of the message
+    Transmitters,
+    /// `ENVVAR_DATA_` data size of the environment variable
+    EnvironmentVariableData,
+    /// The node receives the signal
+    Receiver(ObjectRef),
+    /// The node accesses the environment variable
+    AccessNode(String),
+    /// Relation of a node to the object, or of the node to another object, with attribute values
+    Relation(ObjectRef),
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Comment => f.write_str("comment"),
+            Self::AttributeValue(name) => write!(f, "value of attribute {}", Quoted(name)),
+            Self::ValueDescriptions => f.write_str("value descriptions"),
+            Self::ExtendedValueType => f.write_str("extended value type"),
+            Self::SignalType => f.write_str("signal type reference"),
+            Self::SignalGroup(name) => write!(f, "signal group {name}"),
+            Self::ExtendedMultiplex {
+                signal_name,
+                multiplexor_signal_name,
+            } => write!(
+                f,
+                "multiplexing of {signal_name} by {multiplexor_signal_name}"
+            ),
+            Self::Category(id) => write!(f, "category {id}"),
+            Self::Transmitter(id) => write!(f, "transmitter of message {id}"),
+            Self::Transmitters => f.write_str("additional transmitters"),
+            Self::EnvironmentVariableData => f.write_str("environment variable data"),
+            Self::Receiver(object) => write!(f, "receiver of {object}"),
+            Self::AccessNode(name) => write!(f, "access node of environment variable {name}"),
+            Self::Relation(object) => write!(f, "{object}"),
+        }
+    }
+}
+
+impl<'a> Dbc<'a> {
+    /// Lookup the comment of an object. For the database, this is the first comment without object.
+    #[must_use]
+    pub fn comment_for(&self, object: &ObjectRef) -> Option<&str> {
+        self.comments
+            .iter()
+            .find(|comment| comment.key() == object.key())
+            .map(Comment::text)
+    }
+
+    /// All attribute values assigned to an object by `BA_`, or by `BA_REL_` for relations, as name and value.
+    /// Defaults are not included, use [`Dbc::resolved_attribute`] to fall back to them.
+    #[must_use]
+    pub fn attributes_for(&self, object: &ObjectRef) -> Vec<(&str, &AttributeValue<'a>)> {
+        self.attribute_values()
+            .filter(|(o, ..)| *o == object.key())
+            .map(|(_, name, value)| (name, value))
+            .collect()
+    }
+
+    /// All statements referring to an object, e.g. to find the usages of a node or signal
+    /// before removing or renaming it.
+    ///
+    /// Besides the statements attached to the object itself, such as comments, attribute values and
+    /// value descriptions, this includes references by other objects: messages transmitted by a node,
+    /// signals received by it, `BO_TX_BU_` and `SIG_GROUP_` entries of a message, `ENVVAR_DATA_` of an
+    /// environment variable, and relations of nodes to the object.
+    #[must_use]
+    #[expect(clippy::too_many_lines)]
+    pub fn references_to(&self, object: &ObjectRef) -> Vec<Reference> {
+        let mut references = Vec::new();
+        let mut push = |reference| {
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+        };
+        if self.comment_for(object).is_some() {
+            push(Reference::Comment);
+        }
+        for (name, _) in self.attributes_for(object) {
+            push(Reference::AttributeValue(name.to_string()));
+        }
+        if self
+            .value_descriptions
+            .iter()
+            .any(|v| v.key() == object.key())
+        {
+            push(Reference::ValueDescriptions);
+        }
+        for category in &self.categories {
+            if category.object.key() == object.key() {
+                push(Reference::Category(category.category_id));
+            }
+        }
+        for value in &self.relation_attribute_values {
+            let relation = value.details.key();
+            if related(relation, object.key()) {
+                push(Reference::Relation(relation.into()));
+            }
+        }
+        match object {
+            ObjectRef::Node(node_name) => {
+                for message in &self.messages {
+                    if message.transmitter.as_deref() == Some(node_name) {
+                        push(Reference::Transmitter(message.id));
+                    }
+                    for signal in &message.signals {
+                        if signal.receivers.iter().any(|r| r == node_name) {
+                            push(Reference::Receiver(ObjectRef::Signal {
+                                message_id: message.id,
+                                signal_name: signal.name.to_string(),
+                            }));
+                        }
+                    }
+                }
+                for tx in &self.message_transmitters {
+                    if tx.transmitter.iter().any(|t| t == node_name) {
+                        push(Reference::Transmitter(tx.message_id));
+                    }
+                }
+                for env in &self.environment_variables {
+                    if env.access_nodes.iter().any(|n| n == node_name) {
+                        push(Reference::AccessNode(env.name.to_string()));
+                    }
+                }
+            }
+            ObjectRef::Message(message_id) => {
+                if self
+                    .message_transmitters
+                    .iter()
+                    .any(|tx| tx.message_id == *message_id)
+                {
+                    push(Reference::Transmitters);
+                }
+                for group in &self.signal_groups {
+                    if group.message_id == *message_id {
+                        push(Reference::SignalGroup(group.name.to_string()));
+                    }
+                }
+            }
+            ObjectRef::EnvironmentVariable(name)
+                if self
+                    .environment_variable_data
+                    .iter()
+                    .any(|data| data.env_var_name == *name) =>
+            {
+                push(Reference::EnvironmentVariableData);
+            }
+            ObjectRef::Signal {
+                message_id,
+                signal_name,
+            } => {
+                if self
+                    .signal_extended_value_type_list
+                    .iter()
+                    .any(|ext| ext.key() == object.key())
+                {
+                    push(Reference::ExtendedValueType);
+                }
+                if self
+                    .signal_type_refs
+                    .iter()
+                    .any(|r| r.key() == object.key())
+                {
+                    push(Reference::SignalType);
+                }
+                for group in &self.signal_groups {
+                    if group.message_id == *message_id
+                        && group.signal_names.iter().any(|s| s == signal_name)
+                    {
+                        push(Reference::SignalGroup(group.name.to_string()));
+                    }
+                }
+                for ext in &self.extended_multiplex {
+                    if ext.message_id == *message_id
+                        && (ext.signal_name == *signal_name
+                            || ext.multiplexor_signal_name == *signal_name)
+                    {
+                        push(Reference::ExtendedMultiplex {
+                            signal_name: ext.signal_name.to_string(),
+                            multiplexor_signal_name: ext.multiplexor_signal_name.to_string(),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+        references
+    }
+
+    /// All `BA_` and `BA_REL_` values with the object they are assigned to
+    pub(crate) fn attribute_values(
+        &self,
+    ) -> impl Iterator<Item = (ObjectKey<'_>, &str, &AttributeValue<'a>)> {
+        let database = self
+            .attribute_values_database
+            .iter()
+            .map(|a| (ObjectKey::Database, a.name.as_ref(), &a.value));
+        let nodes = self
+            .attribute_values_node
+            .iter()
+            .map(|a| (a.key(), a.name.as_ref(), &a.value));
+        let messages = self
+            .attribute_values_message
+            .iter()
+            .map(|a| (a.key(), a.name.as_ref(), &a.value));
+        let signals = self
+            .attribute_values_signal
+            .iter()
+            .map(|a| (a.key(), a.name.as_ref(), &a.value));
+        let env = self
+            .attribute_values_env
+            .iter()
+            .map(|a| (a.key(), a.name.as_ref(), &a.value));
+        let relations = self
+            .relation_attribute_values
+            .iter()
+            .map(|a| (a.details.key(), a.name.as_ref(), a.details.value()));
+        database
+            .chain(nodes)
+            .chain(messages)
+            .chain(signals)
+            .chain(env)
+            .chain(relations)
+    }
+}
+
+/// Whether `relation` starts at or points to `object`
+fn related(relation: ObjectKey<'_>, object: ObjectKey<'_>) -> bool {
+    let (node_name, target) = match relation {
+        ObjectKey::NodeToSignal(node_name, message_id, signal_name) => {
+            (node_name, ObjectKey::Signal(message_id, signal_name))
+        }
+        ObjectKey::NodeToMessage(node_name, message_id) => {
+            (node_name, ObjectKey::Message(message_id))
+        }
+        ObjectKey::NodeToEnvironmentVariable(node_name, variable_name) => {
+            (node_name, ObjectKey::EnvironmentVariable(variable_name))
+        }
+        _ => return false,
+    };
+    object == ObjectKey::Node(node_name) || object == target
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    const DBC: &str = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU Gateway
+
+BO_ 100 Engine: 8 ECU
+ SG_ Mode M : 0|2@1+ (1,0) [0|3] "" Gateway
+ SG_ Speed m1 : 8|16@1+ (1,0) [0|0] "rpm" Gateway
+
+BO_TX_BU_ 100 : ECU,Gateway;
+
+EV_ Ignition: 0 [0|1] "" 0 1 DUMMY_NODE_VECTOR0 Gateway;
+
+ENVVAR_DATA_ Ignition: 4;
+
+CM_ "database comment";
+CM_ BU_ Gateway "gateway";
+CM_ SG_ 100 Speed "engine speed";
+
+BA_DEF_ SG_ "GenSigStartValue" INT 0 0;
+BA_DEF_REL_ BU_SG_REL_ "SgRel" INT 0 10;
+BA_ "GenSigStartValue" SG_ 100 Speed 0;
+BA_REL_ "SgRel" BU_SG_REL_ Gateway SG_ 100 Speed 1;
+
+VAL_ 100 Mode 0 "off" 1 "on" ;
+
+SIG_GROUP_ 100 Group 1 : Mode Speed;
+
+SG_MUL_VAL_ 100 Speed Mode 1-1;
+"#;
+
+    fn speed() -> ObjectRef {
+        ObjectRef::Signal {
+            message_id: MessageId::Standard(100),
+            signal_name: "Speed".into(),
+        }
+    }
+
+    #[test]
+    fn comment_and_attributes_test() {
+        let dbc = Dbc::try_from(DBC).unwrap();
+        assert_eq!(
+            dbc.comment_for(&ObjectRef::Database),
+            Some("database comment")
+        );
+        assert_eq!(dbc.comment_for(&speed()), Some("engine speed"));
+        assert_eq!(dbc.comment_for(&ObjectRef::Node("ECU".into())), None);
+        assert_eq!(
+            dbc.attributes_for(&speed()),
+            [("GenSigStartValue", &AttributeValue::Uint(0))]
+        );
+        let relation = ObjectRef::NodeToSignal {
+            node_name: "Gateway".into(),
+            message_id: MessageId::Standard(100),
+            signal_name: "Speed".into(),
+        };
+        assert_eq!(
+            dbc.attributes_for(&relation),
+            [("SgRel", &AttributeValue::Uint(1))]
+        );
+    }
+
+    #[test]
+    fn references_test() {
+        let dbc = Dbc::try_from(DBC).unwrap();
+        let relation = ObjectRef::NodeToSignal {
+            node_name: "Gateway".into(),
+            message_id: MessageId::Standard(100),
+            signal_name: "Speed".into(),
+        };
+        let references = dbc.references_to(&ObjectRef::Node("Gateway".into()));
+        assert_eq!(
+            references,
+            [
+                Reference::Comment,
+                Reference::Relation(relation.clone()),
+                Reference::Receiver(ObjectRef::Signal {
+                    message_id: MessageId::Standard(100),
+                    signal_name: "Mode".into(),
+                }),
+                Reference::Receiver(speed()),
+                Reference::Transmitter(MessageId::Standard(100)),
+                Reference::AccessNode("Ignition".into()),
+            ]
+        );
+
+        let references: Vec<_> = dbc
+            .references_to(&speed())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            references,
+            [
+                "comment",
+                r#"value of attribute "GenSigStartValue""#,
+                "relation of node Gateway to signal Speed of message 100",
+                "signal group Group",
+                "multiplexing of Speed by Mode",
+            ]
+        );
+
+        assert_eq!(
+            dbc.references_to(&ObjectRef::Message(MessageId::Standard(100))),
+            [
+                Reference::Transmitters,
+                Reference::SignalGroup("Group".into()),
+            ]
+        );
+        assert_eq!(
+            dbc.references_to(&ObjectRef::EnvironmentVariable("Ignition".into())),
+            [Reference::EnvironmentVariableData]
+        );
+    }
+}
@@ -0,0 +1,590 @@
+//!
+//! Hand-written parsing of statements that are not covered by the pest grammar
+//!
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::ast::{
+    ByteOrder, Category, CategoryDefinition, CategoryObject, Dbc, Filter, MessageId, SignalType,
+    SignalTypeRef, ValueType,
+};
+use crate::error::{DbcError, DbcResult, SourceLocation};
+use crate::parser::unescape;
+
+/// A statement parsed outside of the pest grammar
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Statement<'a> {
+    SignalType(SignalType<'a>),
+    SignalTypeRef(SignalTypeRef<'a>),
+    CategoryDefinition(CategoryDefinition<'a>),
+    Category(Category<'a>),
+    Filter(Filter<'a>),
+}
+
+impl<'a> Statement<'a> {
+    /// Add the statement to the database
+    pub(crate) fn apply(self, dbc: &mut Dbc<'a>) {
+        match self {
+            Self::SignalType(v) => dbc.signal_types.push(v),
+            Self::SignalTypeRef(v) => dbc.signal_type_refs.push(v),
+            Self::CategoryDefinition(v) => dbc.category_definitions.push(v),
+            Self::Category(v) => dbc.categories.push(v),
+            Self::Filter(v) => dbc.filter.push(v),
+        }
+    }
+}
+
+const SIGNAL_TYPE: &str = "SGTYPE_";
+const SIGNAL_TYPE_REF: &str = "SIG_TYPE_REF_";
+const CATEGORY_DEFINITION: &str = "CAT_DEF_";
+const CATEGORY: &str = "CAT_";
+const FILTER: &str = "FILTER";
+const KEYWORDS: [&str; 5] = [
+    SIGNAL_TYPE,
+    SIGNAL_TYPE_REF,
+    CATEGORY_DEFINITION,
+    CATEGORY,
+    FILTER,
+];
+
+/// Keywords starting the statements of a DBC file, used to find the end of a statement without `;`
+const DBC_KEYWORDS: [&str; 29] = [
+    "VERSION",
+    "NS_",
+    "BS_",
+    "BU_",
+    "VAL_TABLE_",
+    "BO_",
+    "SG_",
+    "BO_TX_BU_",
+    "EV_",
+    "ENVVAR_DATA_",
+    "SGTYPE_",
+    "SGTYPE_VAL_",
+    "SIG_TYPE_REF_",
+    "CM_",
+    "BA_DEF_",
+    "BA_DEF_SGTYPE_",
+    "BA_DEF_REL_",
+    "BA_DEF_DEF_",
+    "BA_DEF_DEF_REL_",
+    "BA_",
+    "BA_SGTYPE_",
+    "BA_REL_",
+    "VAL_",
+    "SIG_GROUP_",
+    "SIG_VALTYPE_",
+    "SG_MUL_VAL_",
+    "CAT_DEF_",
+    "CAT_",
+    "FILTER",
+];
+
+/// Extract all statements handled by this module from the input.
+///
+/// The extracted statements are replaced by whitespace, so pest can parse the remaining input
+/// with all other statements at their original byte offset, line and column.
+/// Every statement is returned with its location, errors are located as well.
+pub(crate) fn extract_statements(
+    input: &str,
+) -> (
+    Cow<'_, str>,
+    Vec<(SourceLocation, DbcResult<Statement<'_>>)>,
+) {
+    let bytes = input.as_bytes();
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut statements = Vec::new();
+    let mut line_start = true;
+    let mut pos = 0;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\n' => line_start = true,
+            b' ' | b'\t' | b'\r' => {}
+            b'"' => {
+                line_start = false;
+                pos = string_end(bytes, pos);
+                continue;
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'/') => {
+                // comment until the end of the line
+                pos = input[pos..].find('\n').map_or(bytes.len(), |idx| pos + idx);
+                continue;
+            }
+            _ if line_start => {
+                line_start = false;
+                if let Some(keyword) = statement_keyword(&input[pos..]) {
+                    let end = statement_end(input, pos);
+                    let text = &input[pos..end];
+                    let location = SourceLocation::at(input, pos, text.trim_end());
+                    let statement = parse_statement(keyword, &text[keyword.len()..])
+                        .map_err(|error| error.at_location(location.clone()));
+                    statements.push((location, statement));
+                    ranges.push(pos..end);
+                    pos = end;
+                    continue;
+                }
+            }
+            _ => line_start = false,
+        }
+        pos += 1;
+    }
+
+    if ranges.is_empty() {
+        return (Cow::Borrowed(input), statements);
+    }
+    let mut output = String::with_capacity(input.len());
+    let mut last = 0;
+    for range in ranges {
+        output.push_str(&input[last..range.start]);
+        // keep line breaks and byte offsets so that locations don't change
+        output.extend(input.as_bytes()[range.clone()].iter().map(|&b| {
+            if b == b'\n' {
+                '\n'
+            } else {
+                ' '
+            }
+        }));
+        last = range.end;
+    }
+    output.push_str(&input[last..]);
+    (Cow::Owned(output), statements)
+}
+
+/// The keyword if `text` starts with a statement handled by this module.
+/// The keyword must be followed by more text on the same line,
+/// otherwise it is a symbol listed in `NS_`.
+fn statement_keyword(text: &str) -> Option<&'static str> {
+    KEYWORDS.into_iter().find(|keyword| {
+        text.strip_prefix(keyword).is_some_and(|rest| {
+            rest.starts_with([' ', '\t'])
+                && rest
+                    .lines()
+                    .next()
+                    .is_some_and(|line| !line.trim().is_empty())
+        })
+    })
+}
+
+/// Position after the closing quote of the string starting at `start`
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut pos = start + 1;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\\' if bytes.get(pos + 1) == Some(&b'"') => pos += 2,
+            b'"' => return pos + 1,
+            _ => pos += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Position after the `;` terminating the statement starting at `start`.
+///
+/// Without `;`, the statement ends before the next line starting with a keyword,
+/// so a missing terminator is reported by the statement parser instead of swallowing the following statements.
+fn statement_end(input: &str, start: usize) -> usize {
+    let bytes = input.as_bytes();
+    let mut pos = start;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'"' => pos = string_end(bytes, pos),
+            b';' => return pos + 1,
+            b'\n' if starts_with_keyword(&input[pos + 1..]) => return pos,
+            _ => pos += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Whether the line starts with a DBC keyword, ignoring indentation
+fn starts_with_keyword(line: &str) -> bool {
+    let line = line.trim_start_matches([' ', '\t']);
+    let word = line
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .next()
+        .unwrap_or_default();
+    DBC_KEYWORDS.contains(&word)
+}
+
+fn parse_statement<'a>(keyword: &'static str, text: &'a str) -> DbcResult<Statement<'a>> {
+    let mut tokens = Tokens { text, keyword };
+    let statement = match keyword {
+        SIGNAL_TYPE => parse_signal_type(&mut tokens)?,
+        SIGNAL_TYPE_REF => Statement::SignalTypeRef(parse_signal_type_ref(&mut tokens)?),
+        CATEGORY_DEFINITION => {
+            Statement::CategoryDefinition(parse_category_definition(&mut tokens)?)
+        }
+        CATEGORY => Statement::Category(parse_category(&mut tokens)?),
+        FILTER => return parse_filter(&tokens).map(Statement::Filter),
+        _ => unreachable!("unknown keyword {keyword}"),
+    };
+    tokens.end()?;
+    Ok(statement)
+}
+
+/// Parse signal type: `SGTYPE_ name : size@byte_order value_type (factor,offset) [min|max] "unit" default_value, value_table;`
+///
+/// The DBC specification also uses `SGTYPE_` for signal type references:
+/// `SGTYPE_ message_id signal_name : signal_type_name;`
+const BYTE_ORDER: &str = "byte order and value type, e.g. 1+";
+
+fn parse_signal_type<'a>(tokens: &mut Tokens<'a>) -> DbcResult<Statement<'a>> {
+    let name = tokens.word("a signal type name")?;
+    if tokens.peek_word() {
+        let message_id = name
+            .parse()
+            .map_err(|_| DbcError::InvalidUint(name.to_string()))?;
+        return Ok(Statement::SignalTypeRef(signal_type_ref(
+            tokens, message_id,
+        )?));
+    }
+    let name = name.into();
+    tokens.punct(':', "':'")?;
+    let signal_size = tokens.uint("a signal size")?;
+    tokens.punct('@', "'@'")?;
+    let (byte_order, value_type) = match tokens.word(BYTE_ORDER)? {
+        "0+" => (ByteOrder::BigEndian, ValueType::Unsigned),
+        "0-" => (ByteOrder::BigEndian, ValueType::Signed),
+        "1+" => (ByteOrder::LittleEndian, ValueType::Unsigned),
+        "1-" => (ByteOrder::LittleEndian, ValueType::Signed),
+        _ => return Err(tokens.error(BYTE_ORDER)),
+    };
+    tokens.punct('(', "'('")?;
+    let factor = tokens.float("a factor")?;
+    tokens.punct(',', "','")?;
+    let offset = tokens.float("an offset")?;
+    tokens.punct(')', "')'")?;
+    tokens.punct('[', "'['")?;
+    let min = tokens.float("a minimum")?;
+    tokens.punct('|', "'|'")?;
+    let max = tokens.float("a maximum")?;
+    tokens.punct(']', "']'")?;
+    let unit = tokens.string("a unit")?;
+    let default_value = tokens.float("a default value")?;
+    tokens.punct(',', "','")?;
+    let value_table = if tokens.peek_word() {
+        tokens.word("a value table")?.into()
+    } else {
+        Cow::Borrowed("")
+    };
+    Ok(Statement::SignalType(SignalType {
+        name,
+        signal_size,
+        byte_order,
+        value_type,
+        factor,
+        offset,
+        min,
+        max,
+        unit,
+        default_value,
+        value_table,
+    }))
+}
+
+/// Parse signal type reference: `SIG_TYPE_REF_ message_id signal_name : signal_type_name;`
+fn parse_signal_type_ref<'a>(tokens: &mut Tokens<'a>) -> DbcResult<SignalTypeRef<'a>> {
+    let message_id = tokens.uint("a message id")?;
+    signal_type_ref(tokens, message_id)
+}
+
+fn signal_type_ref<'a>(tokens: &mut Tokens<'a>, message_id: u64) -> DbcResult<SignalTypeRef<'a>> {
+    let message_id = MessageId::try_from(message_id)?;
+    let signal_name = tokens.word("a signal name")?.into();
+    tokens.punct(':', "':'")?;
+    let signal_type_name = tokens.word("a signal type name")?.into();
+    Ok(SignalTypeRef {
+        message_id,
+        signal_name,
+        signal_type_name,
+    })
+}
+
+/// Parse category definition: `CAT_DEF_ category_id category_name value;`
+fn parse_category_definition<'a>(tokens: &mut Tokens<'a>) -> DbcResult<CategoryDefinition<'a>> {
+    let id = tokens.uint("a category id")?;
+    let name = tokens.word("a category name")?.into();
+    let value = tokens.uint("a value")?;
+    Ok(CategoryDefinition { id, name, value })
+}
+
+/// Parse category: `CAT_ BU_ node_name category_id;`, `CAT_ BO_ message_id category_id;`
+/// or `CAT_ EV_ env_var_name category_id;`
+fn parse_category<'a>(tokens: &mut Tokens<'a>) -> DbcResult<Category<'a>> {
+    let object = match tokens.word("BU_, BO_ or EV_")? {
+        "BU_" => CategoryObject::Node(tokens.word("a node name")?.into()),
+        "BO_" => CategoryObject::Message(MessageId::try_from(tokens.uint("a message id")?)?),
+        "EV_" => {
+            CategoryObject::EnvironmentVariable(tokens.word("an environment variable")?.into())
+        }
+        _ => return Err(tokens.error("BU_, BO_ or EV_")),
+    };
+    let category_id = tokens.uint("a category id")?;
+    Ok(Category {
+        object,
+        category_id,
+    })
+}
+
+/// Parse filter: `FILTER definition;`, keeping the undocumented definition verbatim
+fn parse_filter<'a>(tokens: &Tokens<'a>) -> DbcResult<Filter<'a>> {
+    let definition = tokens
+        .text
+        .trim_end()
+        .strip_suffix(';')
+        .ok_or_else(|| tokens.error("';'"))?;
+    Ok(Filter {
+        definition: definition.trim().into(),
+    })
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Word(&'a str),
+    /// Quoted string, without the quotes
+    Str(&'a str),
+    Punct(char),
+}
+
+const PUNCTUATION: &[char] = &[':', ';', '@', '(', ')', ',', '[', ']', '|'];
+
+/// A minimal tokenizer for statements that are not covered by the pest grammar
+struct Tokens<'a> {
+    text: &'a str,
+    keyword: &'static str,
+}
+
+impl<'a> Tokens<'a> {
+    fn next_token(&mut self) -> Option<Token<'a>> {
+        let text = self.text.trim_start();
+        let first = text.chars().next()?;
+        let (token, len) = if first == '"' {
+            let len = string_end(text.as_bytes(), 0);
+            let inner = text[1..len].strip_suffix('"').unwrap_or(&text[1..len]);
+            (Token::Str(inner), len)
+        } else if PUNCTUATION.contains(&first) {
+            (Token::Punct(first), first.len_utf8())
+        } else {
+            let len = text
+                .find(|c: char| c.is_whitespace() || c == '"' || PUNCTUATION.contains(&c))
+                .unwrap_or(text.len());
+            (Token::Word(&text[..len]), len)
+        };
+        self.text = &text[len..];
+        Some(token)
+    }
+
+    fn peek(&self) -> Option<Token<'a>> {
+        Tokens { ..*self }.next_token()
+    }
+
+    fn peek_word(&self) -> bool {
+        matches!(self.peek(), Some(Token::Word(_)))
+    }
+
+    fn error(&self, expected: &'static str) -> DbcError {
+        DbcError::InvalidStatement {
+            keyword: self.keyword,
+            expected,
+        }
+    }
+
+    fn punct(&mut self, punct: char, expected: &'static str) -> DbcResult<()> {
+        match self.next_token() {
+            Some(Token::Punct(c)) if c == punct => Ok(()),
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    fn word(&mut self, expected: &'static str) -> DbcResult<&'a str> {
+        match self.next_token() {
+            Some(Token::Word(word)) => Ok(word),
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    /// Next quoted string, unescaped
+    fn string(&mut self, expected: &'static str) -> DbcResult<Cow<'a, str>> {
+        match self.next_token() {
+            Some(Token::Str(value)) => Ok(unescape(value)),
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    fn uint(&mut self, expected: &'static str) -> DbcResult<u64> {
+        let word = self.word(expected)?;
+        word.parse()
+            .map_err(|_| DbcError::InvalidUint(word.to_string()))
+    }
+
+    fn float(&mut self, expected: &'static str) -> DbcResult<f64> {
+        let word = self.word(expected)?;
+        word.parse()
+            .map_err(|_| DbcError::InvalidFloat(word.to_string()))
+    }
+
+    /// Expect the terminating `;` and nothing after it
+    fn end(&mut self) -> DbcResult<()> {
+        self.punct(';', "';'")?;
+        match self.next_token() {
+            None => Ok(()),
+            Some(_) => Err(self.error("the end of the statement")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> DbcResult<Statement<'_>> {
+        let keyword = statement_keyword(text).unwrap();
+        parse_statement(keyword, &text[keyword.len()..])
+    }
+
+    #[test]
+    fn extract_test() {
+        let statement = "SGTYPE_ Type : 8@1+ (1,0) [0|255] \"\" 0, ;";
+        let input = format!(
+            "NS_ :\n    SGTYPE_\n\nCM_ \"text\nSGTYPE_ not a statement\";\n{statement}\nBU_: ECU\n"
+        );
+        let (output, statements) = extract_statements(&input);
+        assert_eq!(output.len(), input.len());
+        assert_eq!(output.lines().count(), input.lines().count());
+        assert!(output.contains("    SGTYPE_\n"));
+        assert!(output.contains("SGTYPE_ not a statement"));
+        let blank = " ".repeat(statement.len());
+        assert!(output.contains(&format!("\n{blank}\nBU_: ECU\n")));
+
+        assert_eq!(statements.len(), 1);
+        let (location, statement) = &statements[0];
+        assert_eq!((location.line, location.column), (6, 1));
+        assert!(matches!(statement, Ok(Statement::SignalType(_))));
+
+        let (output, statements) = extract_statements("BU_: ECU\n");
+        assert!(matches!(output, Cow::Borrowed(_)));
+        assert!(statements.is_empty());
+    }
+
+    #[test]
+    fn missing_terminator_test() {
+        let input = "CAT_ BU_ ECU 1\nCM_ BU_ ECU \"comment\";\n";
+        let (output, statements) = extract_statements(input);
+        assert_eq!(
+            output,
+            format!("{}\nCM_ BU_ ECU \"comment\";\n", " ".repeat(14))
+        );
+        assert_eq!(statements.len(), 1);
+        let (location, statement) = &statements[0];
+        assert_eq!((location.line, location.column), (1, 1));
+        assert_eq!(location.snippet, "CAT_ BU_ ECU 1");
+        assert_eq!(
+            statement,
+            &Err(DbcError::InvalidStatement {
+                keyword: "CAT_",
+                expected: "';'",
+            }
+            .at_location(location.clone()))
+        );
+    }
+
+    #[test]
+    fn missing_terminator_before_relation_test() {
+        let rel = "BA_DEF_REL_ BU_SG_REL_ \"SgRel\" INT 0 10;\n";
+        for statement in ["CAT_ BU_ ECU 1", "FILTER 0 \"Engine\" BO_ 100"] {
+            let input = format!("{statement}\n{rel}");
+            let (output, statements) = extract_statements(&input);
+            assert_eq!(output, format!("{}\n{rel}", " ".repeat(statement.len())));
+            assert_eq!(statements.len(), 1);
+            assert_eq!(statements[0].0.snippet, statement);
+        }
+    }
+
+    #[test]
+    fn signal_type_test() {
+        let def = r#"SGTYPE_ Temperature : 12@0- (0.5,-40) [-40|87.5] "degC" 0, Temperatures;"#;
+        let exp = SignalType {
+            name: "Temperature".into(),
+            signal_size: 12,
+            byte_order: ByteOrder::BigEndian,
+            value_type: ValueType::Signed,
+            factor: 0.5,
+            offset: -40.0,
+            min: -40.0,
+            max: 87.5,
+            unit: "degC".into(),
+            default_value: 0.0,
+            value_table: "Temperatures".into(),
+        };
+        assert_eq!(parse(def), Ok(Statement::SignalType(exp)));
+    }
+
+    #[test]
+    fn signal_type_ref_test() {
+        let exp = SignalTypeRef {
+            message_id: MessageId::Standard(100),
+            signal_name: "Engine_Temp".into(),
+            signal_type_name: "Temperature".into(),
+        };
+        assert_eq!(
+            parse("SIG_TYPE_REF_ 100 Engine_Temp : Temperature;"),
+            Ok(Statement::SignalTypeRef(exp.clone()))
+        );
+        // variant used by the DBC specification
+        assert_eq!(
+            parse("SGTYPE_ 100 Engine_Temp : Temperature;"),
+            Ok(Statement::SignalTypeRef(exp))
+        );
+    }
+
+    #[test]
+    fn category_test() {
+        assert_eq!(
+            parse("CAT_DEF_ 1 Powertrain 0 ;"),
+            Ok(Statement::CategoryDefinition(CategoryDefinition {
+                id: 1,
+                name: "Powertrain".into(),
+                value: 0,
+            }))
+        );
+        assert_eq!(
+            parse("CAT_ BO_ 2147483748 1;"),
+            Ok(Statement::Category(Category {
+                object: CategoryObject::Message(MessageId::Extended(100)),
+                category_id: 1,
+            }))
+        );
+        assert_eq!(
+            parse("CAT_ BU_ Gateway 1;"),
+            Ok(Statement::Category(Category {
+                object: CategoryObject::Node("Gateway".into()),
+                category_id: 1,
+            }))
+        );
+        assert_eq!(
+            parse("FILTER 0 \"Engine\" BO_ 100 ;"),
+            Ok(Statement::Filter(Filter {
+                definition: "0 \"Engine\" BO_ 100".into(),
+            }))
+        );
+    }
+
+    #[test]
+    fn invalid_statement_test() {
+        assert_eq!(
+            parse("SIG_TYPE_REF_ 100 Engine_Temp Temperature;"),
+            Err(DbcError::InvalidStatement {
+                keyword: "SIG_TYPE_REF_",
+                expected: "':'",
+            })
+        );
+        assert_eq!(
+            parse("SGTYPE_ Type : 8@2+ (1,0) [0|255] \"\" 0, ;"),
+            Err(DbcError::InvalidStatement {
+                keyword: "SGTYPE_",
+                expected: "byte order and value type, e.g. 1+",
+            })
+        );
+    }
+}
@@ -0,0 +1,1031 @@
+//!
+//! Semantic validation of a parsed database
+//!
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::ast::{
+    AttributeDefault, AttributeDefinition, AttributeValue, AttributeValueForRelationType, Comment,
+    Dbc, Message, MessageId, MuxSelector, ObjectRef, SignalTypeRef, ValueDescription,
+};
+use crate::codec::bit_positions;
+use crate::writer::Quoted;
+use crate::FrameFormat;
+
+/// Severity of a validation diagnostic
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The database is usable, but likely contains a mistake, e.g. a dangling reference
+    Warning,
+    /// The database is inconsistent, e.g. signals can't be decoded reliably
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// Stable identifier of the check that produced a diagnostic
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DiagnosticCode {
+    /// Signal bits exceed the message size
+    SignalOutOfBounds,
+    /// Two signals that can be active at the same time share bits
+    OverlappingSignals,
+    /// Multiple messages with the same id
+    DuplicateMessageId,
+    /// Multiple signals with the same name within a message
+    DuplicateSignalName,
+    /// Reference to a message that is not defined
+    UnknownMessage,
+    /// Reference to a signal that is not defined
+    UnknownSignal,
+    /// Reference to a node that is not listed in `BU_`
+    UnknownNode,
+    /// Reference to an environment variable that is not defined
+    UnknownEnvironmentVariable,
+    /// `SIG_TYPE_REF_` names a signal type that is not defined by `SGTYPE_`
+    UnknownSignalType,
+    /// `SG_MUL_VAL_` names a signal that is not a multiplexor switch of the message
+    UnknownMultiplexor,
+    /// Multiplexed signal without `SG_MUL_VAL_` in a message without exactly one multiplexor switch
+    UnresolvedMultiplexor,
+    /// Message longer than 8 bytes that is not a CAN FD frame, or with a length no CAN FD frame has
+    InvalidMessageSize,
+    /// `BS_` with multiple entries, a zero baudrate or BTR values that don't fit into 8-bit registers
+    InvalidBitTiming,
+    /// Attribute value or default for an attribute without `BA_DEF_` or `BA_DEF_REL_`
+    UndefinedAttribute,
+    /// Attribute value or default of the wrong kind or outside the range of its definition
+    InvalidAttributeValue,
+}
+
+impl DiagnosticCode {
+    /// Stable textual representation of the code, e.g. `signal-out-of-bounds`
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SignalOutOfBounds => "signal-out-of-bounds",
+            Self::OverlappingSignals => "overlapping-signals",
+            Self::DuplicateMessageId => "duplicate-message-id",
+            Self::DuplicateSignalName => "duplicate-signal-name",
+            Self::UnknownMessage => "unknown-message",
+            Self::UnknownSignal => "unknown-signal",
+            Self::UnknownNode => "unknown-node",
+            Self::UnknownEnvironmentVariable => "unknown-environment-variable",
+            Self::UnknownSignalType => "unknown-signal-type",
+            Self::UnknownMultiplexor => "unknown-multiplexor",
+            Self::UnresolvedMultiplexor => "unresolved-multiplexor",
+            Self::InvalidMessageSize => "invalid-message-size",
+            Self::InvalidBitTiming => "invalid-bit-timing",
+            Self::UndefinedAttribute => "undefined-attribute",
+            Self::InvalidAttributeValue => "invalid-attribute-value",
+        }
+    }
+
+    /// Severity of diagnostics with this code
+    #[must_use]
+    pub fn severity(self) -> Severity {
+        match self {
+            Self::SignalOutOfBounds
+            | Self::OverlappingSignals
+            | Self::DuplicateMessageId
+            | Self::DuplicateSignalName
+            | Self::UnknownMultiplexor
+            | Self::UnresolvedMultiplexor
+            | Self::InvalidMessageSize
+            | Self::InvalidBitTiming
+            | Self::InvalidAttributeValue => Severity::Error,
+            Self::UnknownMessage
+            | Self::UnknownSignal
+            | Self::UnknownNode
+            | Self::UnknownEnvironmentVariable
+            | Self::UnknownSignalType
+            | Self::UndefinedAttribute => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a diagnostic refers to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiagnosticLocation {
+    /// An object of the database
+    Object(ObjectRef),
+    /// The default (`BA_DEF_DEF_` or `BA_DEF_DEF_REL_`) of an attribute, which applies to all objects
+    /// of the kinds the attribute is defined for
+    AttributeDefault {
+        name: String,
+        /// Object type of the definition the default was checked against, e.g. `BO_` or `BU_SG_REL_`,
+        /// empty for database attributes and `None` if the attribute is not defined
+        object_type: Option<&'static str>,
+    },
+}
+
+impl From<ObjectRef> for DiagnosticLocation {
+    fn from(object: ObjectRef) -> Self {
+        Self::Object(object)
+    }
+}
+
+impl fmt::Display for DiagnosticLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Object(object) => write!(f, "{object}"),
+            Self::AttributeDefault { name, object_type } => {
+                write!(f, "default of attribute {}", Quoted(name))?;
+                match object_type {
+                    Some("") => f.write_str(" for the database"),
+                    Some(object_type) => write!(f, " for {object_type}"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// A problem found by [`Dbc::validate`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: DiagnosticCode,
+    /// The object or definition the diagnostic refers to
+    pub location: DiagnosticLocation,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    /// Write the diagnostic as `severity[code] location: message`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}] {}: {}",
+            self.severity, self.code, self.location, self.message
+        )
+    }
+}
+
+impl Dbc<'_> {
+    /// Check the database for semantic problems that are not detected while parsing.
+    ///
+    /// This covers the size and signal layout of each message (CAN FD lengths, bounds and overlaps within a multiplexor branch),
+    /// duplicate message ids, multiplexing definitions, attribute values that don't match their definitions,
+    /// and references to undefined messages, signals, nodes and environment variables.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut validator = Validator {
+            dbc: self,
+            diagnostics: Vec::new(),
+        };
+        validator.check_bit_timing();
+        validator.check_messages();
+        validator.check_extended_multiplex();
+        validator.check_references();
+        validator.check_attribute_values();
+        validator.diagnostics
+    }
+}
+
+struct Validator<'a> {
+    dbc: &'a Dbc<'a>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator<'_> {
+    fn push(
+        &mut self,
+        code: DiagnosticCode,
+        location: impl Into<DiagnosticLocation>,
+        message: String,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity: code.severity(),
+            code,
+            location: location.into(),
+            message,
+        });
+    }
+
+    fn check_bit_timing(&mut self) {
+        let Some(bit_timing) = &self.dbc.bit_timing else {
+            return;
+        };
+        if bit_timing.len() > 1 {
+            self.push(
+                DiagnosticCode::InvalidBitTiming,
+                ObjectRef::Database,
+                format!(
+                    "BS_ defines {} baudrates, at most one is allowed",
+                    bit_timing.len()
+                ),
+            );
+        }
+        for timing in bit_timing {
+            if timing.baudrate == 0 {
+                self.push(
+                    DiagnosticCode::InvalidBitTiming,
+                    ObjectRef::Database,
+                    "BS_ defines a baudrate of 0".to_string(),
+                );
+            }
+            for (name, value) in [("BTR1", timing.btr1), ("BTR2", timing.btr2)] {
+                if value > 0xFF {
+                    self.push(
+                        DiagnosticCode::InvalidBitTiming,
+                        ObjectRef::Database,
+                        format!("BS_ value {value} of {name} does not fit into an 8-bit register"),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_messages(&mut self) {
+        let mut ids = HashSet::new();
+        for message in &self.dbc.messages {
+            if !ids.insert(message.id) {
+                self.push(
+                    DiagnosticCode::DuplicateMessageId,
+                    ObjectRef::Message(message.id),
+                    format!("Message {} reuses the id of another message", message.name),
+                );
+            }
+            self.check_message_size(message);
+            self.check_signal_layout(message);
+        }
+    }
+
+    fn check_message_size(&mut self, message: &Message) {
+        if message.size <= 8 {
+            return;
+        }
+        let format = self.dbc.message_frame_format(message.id);
+        // J1939 parameter groups longer than 8 bytes are sent with the transport protocol
+        if format == Some(FrameFormat::J1939Pg) {
+            return;
+        }
+        if !self.dbc.message_is_fd(message.id) {
+            self.push(
+                DiagnosticCode::InvalidMessageSize,
+                ObjectRef::Message(message.id),
+                format!(
+                    "Message {} has {} bytes, but only CAN FD frames can be longer than 8 bytes",
+                    message.name, message.size
+                ),
+            );
+        } else if message.dlc().is_none() {
+            self.push(
+                DiagnosticCode::InvalidMessageSize,
+                ObjectRef::Message(message.id),
+                format!(
+                    "Message {} has {} bytes, which is not a valid CAN FD frame length",
+                    message.name, message.size
+                ),
+            );
+        }
+    }
+
+    fn check_signal_layout(&mut self, message: &Message) {
+        let payload_bits = message.size.saturating_mul(8);
+        let selectors = message.multiplex_selectors(&self.dbc.extended_multiplex);
+        let mut bits = Vec::with_capacity(message.signals.len());
+        let mut names = HashSet::new();
+
+        for (idx, signal) in message.signals.iter().enumerate() {
+            let signal_ref = || ObjectRef::Signal {
+                message_id: message.id,
+                signal_name: signal.name.to_string(),
+            };
+            if !names.insert(&signal.name) {
+                self.push(
+                    DiagnosticCode::DuplicateSignalName,
+                    signal_ref(),
+                    format!(
+                        "Signal {} is defined multiple times in message {}",
+                        signal.name, message.name
+                    ),
+                );
+            }
+            let positions = bit_positions(signal.start_bit, signal.size, signal.byte_order);
+            let mut in_bounds = HashSet::new();
+            for pos in positions {
+                let Some(pos) = pos.filter(|&pos| pos < payload_bits) else {
+                    self.push(
+                        DiagnosticCode::SignalOutOfBounds,
+                        signal_ref(),
+                        format!(
+                            "Signal {} exceeds the message size of {} bytes",
+                            signal.name, message.size
+                        ),
+                    );
+                    break;
+                };
+                in_bounds.insert(pos);
+            }
+
+            let has_extended = self
+                .dbc
+                .extended_multiplex
+                .iter()
+                .any(|e| e.message_id == message.id && e.signal_name == signal.name);
+            if selectors[idx].is_none() && signal.is_multiplexed() && !has_extended {
+                self.push(
+                    DiagnosticCode::UnresolvedMultiplexor,
+                    signal_ref(),
+                    format!(
+                        "Signal {} is multiplexed, but message {} has no unique multiplexor switch",
+                        signal.name, message.name
+                    ),
+                );
+            }
+
+            for (other, other_bits) in bits.iter().enumerate() {
+                if !in_bounds.is_disjoint(other_bits) && !mutually_exclusive(&selectors, idx, other)
+                {
+                    self.push(
+                        DiagnosticCode::OverlappingSignals,
+                        signal_ref(),
+                        format!(
+                            "Signal {} overlaps signal {}",
+                            signal.name, message.signals[other].name
+                        ),
+                    );
+                }
+            }
+            bits.push(in_bounds);
+        }
+    }
+
+    fn check_extended_multiplex(&mut self) {
+        for ext in &self.dbc.extended_multiplex {
+            let Some(message) = self.message(ext.message_id) else {
+                continue; // reported as a dangling reference
+            };
+            let is_switch = message
+                .signals
+                .iter()
+                .any(|s| s.name == ext.multiplexor_signal_name && s.is_multiplexor());
+            if !is_switch {
+                self.push(
+                    DiagnosticCode::UnknownMultiplexor,
+                    ObjectRef::Signal {
+                        message_id: ext.message_id,
+                        signal_name: ext.signal_name.to_string(),
+                    },
+                    format!(
+                        "SG_MUL_VAL_ refers to {}, which is not a multiplexor of message {}",
+                        ext.multiplexor_signal_name, message.name
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Check that all objects referenced by other statements are defined
+    fn check_references(&mut self) {
+        let dbc = self.dbc;
+        for message in &dbc.messages {
+            if let Some(transmitter) = &message.transmitter {
+                self.check_node(transmitter, "Transmitter of message");
+            }
+            for signal in &message.signals {
+                for receiver in &signal.receivers {
+                    self.check_node(receiver, "Receiver of signal");
+                }
+            }
+        }
+        for tx in &dbc.message_transmitters {
+            self.check_message(tx.message_id, "BO_TX_BU_");
+            for transmitter in &tx.transmitter {
+                self.check_node(transmitter, "BO_TX_BU_");
+            }
+        }
+        for env in &dbc.environment_variables {
+            // Vector uses DUMMY_NODE_VECTORx to encode the access type, it is not a real node
+            for node in &env.access_nodes {
+                if node.starts_with("DUMMY_NODE_VECTOR") {
+                    continue;
+                }
+                self.check_node(node, "Access node of environment variable");
+            }
+        }
+        for comment in &dbc.comments {
+            match comment {
+                Comment::Node { name, .. } => self.check_node(name, "Comment"),
+                Comment::Message { id, .. } => self.check_message(*id, "Comment"),
+                Comment::Signal {
+                    message_id, name, ..
+                } => self.check_signal(*message_id, name, "Comment"),
+                Comment::EnvVar { name, .. } => self.check_env(name, "Comment"),
+                Comment::Plain { .. } => {}
+            }
+        }
+        for value in &dbc.attribute_values_node {
+            self.check_node(&value.node_name, "Attribute value");
+        }
+        for value in &dbc.attribute_values_message {
+            self.check_message(value.message_id, "Attribute value");
+        }
+        for value in &dbc.attribute_values_signal {
+            self.check_signal(value.message_id, &value.signal_name, "Attribute value");
+        }
+        for value in &dbc.attribute_values_env {
+            self.check_env(&value.variable_name, "Attribute value");
+        }
+        for value in &dbc.relation_attribute_values {
+            self.check_relation(&value.details);
+        }
+        for description in &dbc.value_descriptions {
+            match description {
+                ValueDescription::Signal {
+                    message_id, name, ..
+                } => self.check_signal(*message_id, name, "Value description"),
+                ValueDescription::EnvironmentVariable { name, .. } => {
+                    self.check_env(name, "Value description");
+                }
+            }
+        }
+        for reference in &dbc.signal_type_refs {
+            self.check_signal_type_ref(reference);
+        }
+        for group in &dbc.signal_groups {
+            for signal_name in &group.signal_names {
+                self.check_signal(group.message_id, signal_name, "Signal group");
+            }
+        }
+        for ext in &dbc.signal_extended_value_type_list {
+            self.check_signal(ext.message_id, &ext.signal_name, "SIG_VALTYPE_");
+        }
+        for ext in &dbc.extended_multiplex {
+            self.check_signal(ext.message_id, &ext.signal_name, "SG_MUL_VAL_");
+        }
+    }
+
+    fn check_attribute_values(&mut self) {
+        let dbc = self.dbc;
+        for default in &dbc.attribute_defaults {
+            self.check_attribute_default(&dbc.attribute_definitions, default, false);
+        }
+        for default in &dbc.relation_attribute_defaults {
+            self.check_attribute_default(&dbc.relation_attribute_definitions, default, true);
+        }
+        for value in &dbc.attribute_values_database {
+            self.check_attribute_value(ObjectRef::Database, &value.name, &value.value);
+        }
+        for value in &dbc.attribute_values_node {
+            self.check_attribute_value(value.object(), &value.name, &value.value);
+        }
+        for value in &dbc.attribute_values_message {
+            self.check_attribute_value(value.object(), &value.name, &value.value);
+        }
+        for value in &dbc.attribute_values_signal {
+            self.check_attribute_value(value.object(), &value.name, &value.value);
+        }
+        for value in &dbc.attribute_values_env {
+            self.check_attribute_value(value.object(), &value.name, &value.value);
+        }
+        for value in &dbc.relation_attribute_values {
+            let object = value.details.object();
+            self.check_attribute_value(object, &value.name, value.details.value());
+        }
+    }
+
+    /// Check a default against every definition of its attribute, as it applies to all of them
+    fn check_attribute_default(
+        &mut self,
+        definitions: &[AttributeDefinition],
+        default: &AttributeDefault,
+        relation: bool,
+    ) {
+        let mut defined = false;
+        for def in definitions.iter().filter(|def| def.name() == default.name) {
+            defined = true;
+            let location = DiagnosticLocation::AttributeDefault {
+                name: default.name.to_string(),
+                object_type: Some(def.object_type()),
+            };
+            self.check_typed_value(location, def, &default.value);
+        }
+        if !defined {
+            let location = DiagnosticLocation::AttributeDefault {
+                name: default.name.to_string(),
+                object_type: None,
+            };
+            self.push_undefined(location, &default.name, relation);
+        }
+    }
+
+    /// Check a value against the definition of attribute `name` for the kind of `object`
+    fn check_attribute_value(&mut self, object: ObjectRef, name: &str, value: &AttributeValue) {
+        if let Some(def) = self.dbc.attribute_definition_for(&object, name) {
+            self.check_typed_value(object, def, value);
+        } else {
+            let relation = object.is_relation();
+            self.push_undefined(object, name, relation);
+        }
+    }
+
+    fn check_typed_value(
+        &mut self,
+        location: impl Into<DiagnosticLocation>,
+        def: &AttributeDefinition,
+        value: &AttributeValue,
+    ) {
+        if let Err(err) = def.value_type().typed_value(value) {
+            self.push(
+                DiagnosticCode::InvalidAttributeValue,
+                location,
+                format!("Invalid value of attribute {}: {err}", Quoted(def.name())),
+            );
+        }
+    }
+
+    fn push_undefined(
+        &mut self,
+        location: impl Into<DiagnosticLocation>,
+        name: &str,
+        relation: bool,
+    ) {
+        let keyword = if relation { "BA_DEF_REL_" } else { "BA_DEF_" };
+        self.push(
+            DiagnosticCode::UndefinedAttribute,
+            location,
+            format!("Attribute {} is not defined by {keyword}", Quoted(name)),
+        );
+    }
+
+    fn message(&self, id: MessageId) -> Option<&Message<'_>> {
+        self.dbc.messages.iter().find(|m| m.id == id)
+    }
+
+    fn check_node(&mut self, name: &str, context: &str) {
+        if !self.dbc.nodes.iter().any(|node| node.0 == name) {
+            self.push(
+                DiagnosticCode::UnknownNode,
+                ObjectRef::Node(name.to_string()),
+                format!("{context} refers to a node that is not listed in BU_"),
+            );
+        }
+    }
+
+    fn check_message(&mut self, id: MessageId, context: &str) {
+        if self.message(id).is_none() {
+            self.push(
+                DiagnosticCode::UnknownMessage,
+                ObjectRef::Message(id),
+                format!("{context} refers to an undefined message"),
+            );
+        }
+    }
+
+    fn check_signal(&mut self, message_id: MessageId, name: &str, context: &str) {
+        let Some(message) = self.message(message_id) else {
+            self.check_message(message_id, context);
+            return;
+        };
+        if !message.signals.iter().any(|s| s.name == name) {
+            self.push(
+                DiagnosticCode::UnknownSignal,
+                ObjectRef::Signal {
+                    message_id,
+                    signal_name: name.to_string(),
+                },
+                format!("{context} refers to an undefined signal"),
+            );
+        }
+    }
+
+    fn check_relation(&mut self, details: &AttributeValueForRelationType) {
+        match details {
+            AttributeValueForRelationType::NodeToSignal {
+                node_name,
+                message_id,
+                signal_name,
+                ..
+            } => {
+                self.check_node(node_name, "Relation attribute value");
+                self.check_signal(*message_id, signal_name, "Relation attribute value");
+            }
+            AttributeValueForRelationType::NodeToMessage {
+                node_name,
+                message_id,
+                ..
+            } => {
+                self.check_node(node_name, "Relation attribute value");
+                self.check_message(*message_id, "Relation attribute value");
+            }
+            AttributeValueForRelationType::NodeToEnvironmentVariable {
+                node_name,
+                variable_name,
+                ..
+            } => {
+                self.check_node(node_name, "Relation attribute value");
+                self.check_env(variable_name, "Relation attribute value");
+            }
+        }
+    }
+
+    fn check_signal_type_ref(&mut self, reference: &SignalTypeRef) {
+        self.check_signal(
+            reference.message_id,
+            &reference.signal_name,
+            "SIG_TYPE_REF_",
+        );
+        if !self
+            .dbc
+            .signal_types
+            .iter()
+            .any(|t| t.name == reference.signal_type_name)
+        {
+            self.push(
+                DiagnosticCode::UnknownSignalType,
+                ObjectRef::Signal {
+                    message_id: reference.message_id,
+                    signal_name: reference.signal_name.to_string(),
+                },
+                format!(
+                    "SIG_TYPE_REF_ refers to an undefined signal type {}",
+                    reference.signal_type_name
+                ),
+            );
+        }
+    }
+
+    fn check_env(&mut self, name: &str, context: &str) {
+        if !self
+            .dbc
+            .environment_variables
+            .iter()
+            .any(|env| env.name == name)
+        {
+            self.push(
+                DiagnosticCode::UnknownEnvironmentVariable,
+                ObjectRef::EnvironmentVariable(name.to_string()),
+                format!("{context} refers to an undefined environment variable"),
+            );
+        }
+    }
+}
+
+/// Whether two signals can never be active at the same time,
+/// i.e. they are selected by disjoint values of a common multiplexor
+fn mutually_exclusive(selectors: &[Option<MuxSelector>], a: usize, b: usize) -> bool {
+    let chain_b = multiplexor_chain(selectors, b);
+    multiplexor_chain(selectors, a)
+        .iter()
+        .any(|(mux_a, ranges_a)| {
+            chain_b
+                .iter()
+                .any(|(mux_b, ranges_b)| mux_a == mux_b && !ranges_overlap(ranges_a, ranges_b))
+        })
+}
+
+/// All multiplexors a signal depends on, innermost first
+fn multiplexor_chain(selectors: &[Option<MuxSelector>], idx: usize) -> Vec<(usize, &[(u64, u64)])> {
+    let mut chain = Vec::new();
+    let mut current = idx;
+    // the length limit guards against cyclic multiplexor definitions
+    while let Some(selector) = &selectors[current] {
+        if chain.len() >= selectors.len() {
+            break;
+        }
+        chain.push((selector.multiplexor, selector.ranges.as_slice()));
+        current = selector.multiplexor;
+    }
+    chain
+}
+
+fn ranges_overlap(a: &[(u64, u64)], b: &[(u64, u64)]) -> bool {
+    a.iter().any(|(a_min, a_max)| {
+        b.iter()
+            .any(|(b_min, b_max)| a_min <= b_max && b_min <= a_max)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(dbc: &str) -> Vec<(DiagnosticCode, String)> {
+        Dbc::try_from(dbc)
+            .unwrap()
+            .validate()
+            .into_iter()
+            .map(|d| (d.code, d.location.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn valid_database_test() {
+        let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 1 Msg: 2 ECU
+ SG_ Mux M : 0|4@1+ (1,0) [0|0] "" ECU
+ SG_ A m0 : 8|8@1+ (1,0) [0|0] "" ECU
+ SG_ B m1 : 8|8@1+ (1,0) [0|0] "" ECU
+
+CM_ SG_ 1 A "comment";
+"#;
+        assert_eq!(codes(dbc), []);
+    }
+
+    #[test]
+    fn signal_layout_test() {
+        let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 1 Msg: 2 ECU
+ SG_ A : 0|8@1+ (1,0) [0|0] "" ECU
+ SG_ B : 4|8@1+ (1,0) [0|0] "" ECU
+ SG_ C : 15|16@0+ (1,0) [0|0] "" ECU
+
+BO_ 1 Other: 8 ECU
+"#;
+        assert_eq!(
+            codes(dbc),
+            [
+                (
+                    DiagnosticCode::OverlappingSignals,
+                    "signal B of message 1".into()
+                ),
+                (
+                    DiagnosticCode::SignalOutOfBounds,
+                    "signal C of message 1".into()
+                ),
+                (
+                    DiagnosticCode::OverlappingSignals,
+                    "signal C of message 1".into()
+                ),
+                (DiagnosticCode::DuplicateMessageId, "message 1".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn extended_multiplex_overlap_test() {
+        let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 1 Msg: 3 ECU
+ SG_ Mux M : 0|8@1+ (1,0) [0|0] "" ECU
+ SG_ SubMux m0M : 8|8@1+ (1,0) [0|0] "" ECU
+ SG_ A m0 : 16|8@1+ (1,0) [0|0] "" ECU
+ SG_ B m0 : 16|8@1+ (1,0) [0|0] "" ECU
+ SG_ C m0 : 16|8@1+ (1,0) [0|0] "" ECU
+
+SG_MUL_VAL_ 1 SubMux Mux 0-0;
+SG_MUL_VAL_ 1 A SubMux 0-3;
+SG_MUL_VAL_ 1 B SubMux 4-7;
+SG_MUL_VAL_ 1 C SubMux 3-3;
+SG_MUL_VAL_ 1 C Unknown 1-1;
+"#;
+        assert_eq!(
+            codes(dbc),
+            [
+                (
+                    DiagnosticCode::OverlappingSignals,
+                    "signal C of message 1".into()
+                ),
+                (
+                    DiagnosticCode::UnknownMultiplexor,
+                    "signal C of message 1".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn dangling_references_test() {
+        let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 1 Msg: 1 Gateway
+ SG_ A : 0|8@1+ (1,0) [0|0] "" Dashboard
+
+CM_ SG_ 1 B "missing signal";
+CM_ BO_ 2 "missing message";
+BA_DEF_ SG_ "attr" INT 0 10;
+BA_ "attr" SG_ 1 C 1;
+"#;
+        let diagnostics = Dbc::try_from(dbc).unwrap().validate();
+        let codes: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.severity, d.code, d.location.to_string()))
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                (
+                    Severity::Warning,
+                    DiagnosticCode::UnknownNode,
+                    "node Gateway".into()
+                ),
+                (
+                    Severity::Warning,
+                    DiagnosticCode::UnknownNode,
+                    "node Dashboard".into()
+                ),
+                (
+                    Severity::Warning,
+                    DiagnosticCode::UnknownSignal,
+                    "signal B of message 1".into()
+                ),
+                (
+                    Severity::Warning,
+                    DiagnosticCode::UnknownMessage,
+                    "message 2".into()
+                ),
+                (
+                    Severity::Warning,
+                    DiagnosticCode::UnknownSignal,
+                    "signal C of message 1".into()
+                ),
+            ]
+        );
+        assert_eq!(
+            diagnostics[2].to_string(),
+            "warning[unknown-signal] signal B of message 1: Comment refers to an undefined signal"
+        );
+    }
+
+    #[test]
+    fn bit_timing_test() {
+        let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_: 500 : 12,300
+
+BU_: ECU
+"#;
+        assert_eq!(
+            codes(dbc),
+            [(DiagnosticCode::InvalidBitTiming, "database".into())]
+        );
+    }
+
+    #[test]
+    fn message_size_test() {
+        let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 1 Classic: 16 ECU
+BO_ 2 Fd: 64 ECU
+BO_ 3 FdInvalid: 10 ECU
+BO_ 2147483652 Pg: 100 ECU
+
+BA_DEF_ BO_ "VFrameFormat" ENUM "StandardCAN","ExtendedCAN","reserved","J1939PG","reserved","reserved","reserved","reserved","reserved","reserved","reserved","reserved","reserved","reserved","StandardCAN_FD","ExtendedCAN_FD";
+BA_DEF_DEF_ "VFrameFormat" "StandardCAN";
+BA_ "VFrameFormat" BO_ 2 14;
+BA_ "VFrameFormat" BO_ 3 14;
+BA_ "VFrameFormat" BO_ 2147483652 3;
+"#;
+        assert_eq!(
+            codes(dbc),
+            [
+                (DiagnosticCode::InvalidMessageSize, "message 1".into()),
+                (DiagnosticCode::InvalidMessageSize, "message 3".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn signal_type_ref_test() {
+        let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 1 Msg: 1 ECU
+ SG_ A : 0|8@1+ (1,0) [0|0] "" ECU
+
+SGTYPE_ Byte : 8@1+ (1,0) [0|255] "" 0, ;
+
+SIG_TYPE_REF_ 1 A : Byte;
+SIG_TYPE_REF_ 1 A : Word;
+SIG_TYPE_REF_ 1 B : Byte;
+"#;
+        assert_eq!(
+            codes(dbc),
+            [
+                (
+                    DiagnosticCode::UnknownSignalType,
+                    "signal A of message 1".into()
+                ),
+                (
+                    DiagnosticCode::UnknownSignal,
+                    "signal B of message 1".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_signal_name_test() {
+        let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 1 Msg: 2 ECU
+ SG_ A : 0|8@1+ (1,0) [0|0] "" ECU
+ SG_ A : 8|8@1+ (1,0) [0|0] "" ECU
+"#;
+        assert_eq!(
+            codes(dbc),
+            [(
+                DiagnosticCode::DuplicateSignalName,
+                "signal A of message 1".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn attribute_values_test() {
+        let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 1 Msg: 1 ECU
+ SG_ A : 0|8@1+ (1,0) [0|0] "" ECU
+
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 1000;
+BA_DEF_ BO_ "GenMsgSendType" ENUM "Cyclic","Spontaneous";
+BA_DEF_ SG_ "GenSigStartValue" FLOAT 0 0;
+BA_DEF_DEF_ "GenMsgCycleTime" 2000;
+BA_DEF_DEF_ "GenMsgSendType" "Cyclic";
+BA_DEF_DEF_ "Nowhere" 1;
+BA_ "GenMsgSendType" BO_ 1 2;
+BA_ "GenSigStartValue" SG_ 1 A "zero";
+BA_ "Undefined" BO_ 1 1;
+"#;
+        let diagnostics = Dbc::try_from(dbc).unwrap().validate();
+        let messages: Vec<_> = diagnostics.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                r#"error[invalid-attribute-value] default of attribute "GenMsgCycleTime" for BO_: Invalid value of attribute "GenMsgCycleTime": 2000 is outside the range [0|1000]"#,
+                r#"warning[undefined-attribute] default of attribute "Nowhere": Attribute "Nowhere" is not defined by BA_DEF_"#,
+                r#"error[invalid-attribute-value] message 1: Invalid value of attribute "GenMsgSendType": 2 is neither an index nor a label of the enum values"#,
+                r#"warning[undefined-attribute] message 1: Attribute "Undefined" is not defined by BA_DEF_"#,
+                r#"error[invalid-attribute-value] signal A of message 1: Invalid value of attribute "GenSigStartValue": "zero" is not a valid FLOAT value"#,
+            ]
+        );
+    }
+}
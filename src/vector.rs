@@ -0,0 +1,343 @@
+//!
+//! Typed access to the attributes defined by Vector tools, e.g. `GenMsgCycleTime` and `VFrameFormat`
+//!
+
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::time::Duration;
+
+use crate::ast::{Dbc, MessageId, NumericValue, ObjectRef};
+use crate::TypedAttributeValue;
+
+/// Transmission mode of a message (`GenMsgSendType`)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SendType {
+    Cyclic,
+    Spontaneous,
+    CyclicIfActive,
+    SpontaneousWithDelay,
+    CyclicAndSpontaneous,
+    CyclicAndSpontaneousWithDelay,
+    SpontaneousWithRepetition,
+    CyclicIfActiveAndSpontaneous,
+    CyclicIfActiveAndSpontaneousWithDelay,
+    IfActive,
+    /// `NoMsgSendType`
+    Unspecified,
+    /// Label not known to this crate, e.g. `not_used`
+    Other(String),
+}
+
+impl From<&str> for SendType {
+    fn from(label: &str) -> Self {
+        match label {
+            "Cyclic" => Self::Cyclic,
+            "Spontaneous" => Self::Spontaneous,
+            "CyclicIfActive" => Self::CyclicIfActive,
+            "SpontaneousWithDelay" => Self::SpontaneousWithDelay,
+            "CyclicAndSpontaneous" => Self::CyclicAndSpontaneous,
+            "CyclicAndSpontaneousWithDelay" => Self::CyclicAndSpontaneousWithDelay,
+            "SpontaneousWithRepetition" => Self::SpontaneousWithRepetition,
+            // Vector tools spell these labels without the second "e"
+            "CyclicIfActiveAndSpontanous" | "CyclicIfActiveAndSpontaneous" => {
+                Self::CyclicIfActiveAndSpontaneous
+            }
+            "CyclicIfActiveAndSpontanousWithDelay" | "CyclicIfActiveAndSpontaneousWithDelay" => {
+                Self::CyclicIfActiveAndSpontaneousWithDelay
+            }
+            "IfActive" => Self::IfActive,
+            "NoMsgSendType" => Self::Unspecified,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for SendType {
+    /// Write the label used by Vector tools
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cyclic => "Cyclic",
+            Self::Spontaneous => "Spontaneous",
+            Self::CyclicIfActive => "CyclicIfActive",
+            Self::SpontaneousWithDelay => "SpontaneousWithDelay",
+            Self::CyclicAndSpontaneous => "CyclicAndSpontaneous",
+            Self::CyclicAndSpontaneousWithDelay => "CyclicAndSpontaneousWithDelay",
+            Self::SpontaneousWithRepetition => "SpontaneousWithRepetition",
+            Self::CyclicIfActiveAndSpontaneous => "CyclicIfActiveAndSpontanous",
+            Self::CyclicIfActiveAndSpontaneousWithDelay => "CyclicIfActiveAndSpontanousWithDelay",
+            Self::IfActive => "IfActive",
+            Self::Unspecified => "NoMsgSendType",
+            Self::Other(label) => label,
+        })
+    }
+}
+
+/// Transmission mode of a signal (`GenSigSendType`)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SignalSendType {
+    Cyclic,
+    OnWrite,
+    OnWriteWithRepetition,
+    OnChange,
+    OnChangeWithRepetition,
+    IfActive,
+    IfActiveWithRepetition,
+    /// `NoSigSendType`
+    Unspecified,
+    /// Label not known to this crate
+    Other(String),
+}
+
+impl From<&str> for SignalSendType {
+    fn from(label: &str) -> Self {
+        match label {
+            "Cyclic" => Self::Cyclic,
+            "OnWrite" => Self::OnWrite,
+            "OnWriteWithRepetition" => Self::OnWriteWithRepetition,
+            "OnChange" => Self::OnChange,
+            "OnChangeWithRepetition" => Self::OnChangeWithRepetition,
+            "IfActive" => Self::IfActive,
+            "IfActiveWithRepetition" => Self::IfActiveWithRepetition,
+            "NoSigSendType" => Self::Unspecified,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for SignalSendType {
+    /// Write the label used by Vector tools
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cyclic => "Cyclic",
+            Self::OnWrite => "OnWrite",
+            Self::OnWriteWithRepetition => "OnWriteWithRepetition",
+            Self::OnChange => "OnChange",
+            Self::OnChangeWithRepetition => "OnChangeWithRepetition",
+            Self::IfActive => "IfActive",
+            Self::IfActiveWithRepetition => "IfActiveWithRepetition",
+            Self::Unspecified => "NoSigSendType",
+            Self::Other(label) => label,
+        })
+    }
+}
+
+/// Frame format of a message (`VFrameFormat`)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FrameFormat {
+    StandardCan,
+    ExtendedCan,
+    StandardCanFd,
+    ExtendedCanFd,
+    J1939Pg,
+    /// Label not known to this crate, e.g. `reserved`
+    Other(String),
+}
+
+impl FrameFormat {
+    /// Whether messages of this format are CAN FD frames
+    #[must_use]
+    pub fn is_fd(&self) -> bool {
+        matches!(self, Self::StandardCanFd | Self::ExtendedCanFd)
+    }
+
+    /// Whether messages of this format use 29-bit identifiers
+    #[must_use]
+    pub fn is_extended(&self) -> bool {
+        matches!(
+            self,
+            Self::ExtendedCan | Self::ExtendedCanFd | Self::J1939Pg
+        )
+    }
+}
+
+impl From<&str> for FrameFormat {
+    fn from(label: &str) -> Self {
+        match label {
+            "StandardCAN" => Self::StandardCan,
+            "ExtendedCAN" => Self::ExtendedCan,
+            "StandardCAN_FD" => Self::StandardCanFd,
+            "ExtendedCAN_FD" => Self::ExtendedCanFd,
+            "J1939PG" => Self::J1939Pg,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for FrameFormat {
+    /// Write the label used by Vector tools
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::StandardCan => "StandardCAN",
+            Self::ExtendedCan => "ExtendedCAN",
+            Self::StandardCanFd => "StandardCAN_FD",
+            Self::ExtendedCanFd => "ExtendedCAN_FD",
+            Self::J1939Pg => "J1939PG",
+            Self::Other(label) => label,
+        })
+    }
+}
+
+/// The well-known attributes are resolved like [`Dbc::resolved_typed_attribute`], falling back to their defaults.
+/// They are `None` if the attribute is neither assigned nor has a default,
+/// or if its value does not match the attribute's definition, which [`Dbc::validate`] reports.
+impl Dbc<'_> {
+    /// Cycle time of a message (`GenMsgCycleTime`, in ms)
+    #[must_use]
+    pub fn message_cycle_time(&self, message_id: MessageId) -> Option<Duration> {
+        let millis = self.unsigned(&ObjectRef::Message(message_id), "GenMsgCycleTime")?;
+        Some(Duration::from_millis(millis))
+    }
+
+    /// Minimum delay between two transmissions of a message (`GenMsgDelayTime`, in ms)
+    #[must_use]
+    pub fn message_delay_time(&self, message_id: MessageId) -> Option<Duration> {
+        let millis = self.unsigned(&ObjectRef::Message(message_id), "GenMsgDelayTime")?;
+        Some(Duration::from_millis(millis))
+    }
+
+    /// Transmission mode of a message (`GenMsgSendType`)
+    #[must_use]
+    pub fn message_send_type(&self, message_id: MessageId) -> Option<SendType> {
+        self.label(&ObjectRef::Message(message_id), "GenMsgSendType")
+            .map(SendType::from)
+    }
+
+    /// Frame format of a message (`VFrameFormat`)
+    #[must_use]
+    pub fn message_frame_format(&self, message_id: MessageId) -> Option<FrameFormat> {
+        self.label(&ObjectRef::Message(message_id), "VFrameFormat")
+            .map(FrameFormat::from)
+    }
+
+    /// Whether a message is sent as CAN FD frame.
+    ///
+    /// Without a `VFrameFormat` value, this falls back to the bus type of the database being `CAN FD`.
+    #[must_use]
+    pub fn message_is_fd(&self, message_id: MessageId) -> bool {
+        match self.message_frame_format(message_id) {
+            Some(format) => format.is_fd(),
+            None => self.bus_type() == Some("CAN FD"),
+        }
+    }
+
+    /// Whether the data phase of a CAN FD message uses the faster bit rate (`CANFD_BRS`)
+    #[must_use]
+    pub fn message_bit_rate_switch(&self, message_id: MessageId) -> Option<bool> {
+        match self.label(&ObjectRef::Message(message_id), "CANFD_BRS")? {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Whether the interaction layer handles a message (`GenMsgILSupport`)
+    #[must_use]
+    pub fn message_il_support(&self, message_id: MessageId) -> Option<bool> {
+        match self.label(&ObjectRef::Message(message_id), "GenMsgILSupport")? {
+            "Yes" => Some(true),
+            "No" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Raw start value of a signal (`GenSigStartValue`)
+    #[must_use]
+    pub fn signal_start_value(&self, message_id: MessageId, signal_name: &str) -> Option<f64> {
+        let object = ObjectRef::Signal {
+            message_id,
+            signal_name: signal_name.to_string(),
+        };
+        match self.typed(&object, "GenSigStartValue")? {
+            TypedAttributeValue::Int(v) => Some(NumericValue::Int(v).as_f64()),
+            TypedAttributeValue::Hex(v) => Some(NumericValue::Uint(v).as_f64()),
+            TypedAttributeValue::Float(v) => Some(v),
+            TypedAttributeValue::Enum { .. } | TypedAttributeValue::String(_) => None,
+        }
+    }
+
+    /// Transmission mode of a signal (`GenSigSendType`)
+    #[must_use]
+    pub fn signal_send_type(
+        &self,
+        message_id: MessageId,
+        signal_name: &str,
+    ) -> Option<SignalSendType> {
+        let object = ObjectRef::Signal {
+            message_id,
+            signal_name: signal_name.to_string(),
+        };
+        self.label(&object, "GenSigSendType")
+            .map(SignalSendType::from)
+    }
+
+    /// Network management address of a node (`NmStationAddress`)
+    #[must_use]
+    pub fn node_nm_station_address(&self, node_name: &str) -> Option<u64> {
+        self.unsigned(&ObjectRef::Node(node_name.to_string()), "NmStationAddress")
+    }
+
+    /// Bus type of the database (`BusType`), e.g. `CAN` or `CAN FD`
+    #[must_use]
+    pub fn bus_type(&self) -> Option<&str> {
+        self.string(&ObjectRef::Database, "BusType")
+    }
+
+    /// Name of the database (`DBName`)
+    #[must_use]
+    pub fn db_name(&self) -> Option<&str> {
+        self.string(&ObjectRef::Database, "DBName")
+    }
+
+    fn typed(&self, object: &ObjectRef, name: &str) -> Option<TypedAttributeValue<'_>> {
+        self.resolved_typed_attribute(object, name).ok().flatten()
+    }
+
+    fn unsigned(&self, object: &ObjectRef, name: &str) -> Option<u64> {
+        match self.typed(object, name)? {
+            TypedAttributeValue::Int(v) => u64::try_from(v).ok(),
+            TypedAttributeValue::Hex(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn label(&self, object: &ObjectRef, name: &str) -> Option<&str> {
+        match self.typed(object, name)? {
+            TypedAttributeValue::Enum { label, .. } => Some(label),
+            _ => None,
+        }
+    }
+
+    fn string(&self, object: &ObjectRef, name: &str) -> Option<&str> {
+        match self.typed(object, name)? {
+            TypedAttributeValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_test() {
+        for label in ["Cyclic", "CyclicIfActiveAndSpontanous", "not_used"] {
+            assert_eq!(SendType::from(label).to_string(), label);
+        }
+        assert_eq!(
+            SendType::from("CyclicIfActiveAndSpontaneous"),
+            SendType::CyclicIfActiveAndSpontaneous
+        );
+        assert_eq!(SignalSendType::from("OnChange"), SignalSendType::OnChange);
+        assert_eq!(
+            FrameFormat::from("ExtendedCAN_FD"),
+            FrameFormat::ExtendedCanFd
+        );
+        assert!(FrameFormat::ExtendedCanFd.is_fd() && FrameFormat::ExtendedCanFd.is_extended());
+        assert!(!FrameFormat::J1939Pg.is_fd() && FrameFormat::J1939Pg.is_extended());
+        assert_eq!(
+            FrameFormat::from("reserved"),
+            FrameFormat::Other("reserved".into())
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//!
+//! Writer helpers used to serialize the AST back into DBC text
+//!
+
+use alloc::borrow::Cow;
+use core::fmt;
+use core::fmt::Write as _;
+
+/// Placeholder node name used when a message or signal has no transmitter or receiver
+pub(crate) const VECTOR_PLACEHOLDER: &str = "Vector__XXX";
+
+/// A string written in double quotes, escaping `"` and `\`.
+///
+/// Line breaks are written as is. The grammar reads a backslash before the closing quote
+/// as an escaped quote, so a string ending in a backslash gets an unescaped `"` appended,
+/// which is dropped again when reading the string, see [`unescape`](crate::parser::unescape).
+pub(crate) struct Quoted<'a>(pub &'a str);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                c => f.write_char(c)?,
+            }
+        }
+        if self.0.ends_with('\\') {
+            f.write_char('"')?;
+        }
+        f.write_char('"')
+    }
+}
+
+/// Write a list of node names separated by `sep`, or the Vector placeholder if the list is empty
+pub(crate) fn write_node_names(
+    f: &mut fmt::Formatter<'_>,
+    nodes: &[Cow<'_, str>],
+    sep: &str,
+) -> fmt::Result {
+    if nodes.is_empty() {
+        return f.write_str(VECTOR_PLACEHOLDER);
+    }
+    for (idx, node) in nodes.iter().enumerate() {
+        if idx > 0 {
+            f.write_str(sep)?;
+        }
+        f.write_str(node)?;
+    }
+    Ok(())
+}
+
+/// Write all items, each followed by a newline, and an empty line after the section
+pub(crate) fn write_section<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    items: &[T],
+) -> fmt::Result {
+    if items.is_empty() {
+        return Ok(());
+    }
+    for item in items {
+        writeln!(f, "{item}")?;
+    }
+    writeln!(f)
+}
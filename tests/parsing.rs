@@ -1,4 +1,13 @@
-use can_dbc::{AttributeValue, Dbc, MessageId, SignalExtendedValueType, ValDescription};
+#![cfg(feature = "std")]
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use can_dbc::{
+    dlc_to_length, AttributeValue, BitTiming, CategoryObject, Comment, Dbc, DbcError,
+    DiagnosticLocation, FrameFormat, IntoStatic, MessageId, ObjectRef, ParseOptions, SendType,
+    SignalExtendedValueType, SignalSendType, TypedAttributeValue, ValDescription,
+};
 
 const SAMPLE_DBC: &str = r#"
 VERSION "0.1"
@@ -129,7 +138,7 @@ fn lookup_value_descriptions_for_signal() {
 
     let exp = vec![ValDescription {
         id: 255,
-        description: "NOP".to_string(),
+        description: "NOP".into(),
     }];
     assert_eq!(exp, val_descriptions);
 }
@@ -225,19 +234,49 @@ fn lookup_signal_attribute_none_when_missing() {
 fn lookup_attribute_default() {
     let dbc_content = Dbc::try_from(SAMPLE_DBC).unwrap();
     assert_eq!(
-        dbc_content.attribute_default("BusType"),
-        Some(&AttributeValue::String("AS".to_string()))
+        dbc_content.attribute_default(&ObjectRef::Database, "BusType"),
+        Some(&AttributeValue::String("AS".into()))
+    );
+    let message = ObjectRef::Message(MessageId::Standard(1840));
+    assert_eq!(
+        dbc_content.attribute_default(&message, "GenMsgCycleTime"),
+        Some(&AttributeValue::Uint(0))
     );
+    // without a definition the default applies to any object
     assert_eq!(
-        dbc_content.attribute_default("GenMsgCycleTime"),
+        dbc_content.attribute_default(&ObjectRef::Database, "GenMsgCycleTime"),
         Some(&AttributeValue::Uint(0))
     );
+
+    // the default only applies to the kind of object the attribute is defined for
+    let dbc_content = Dbc::try_from(
+        r#"
+VERSION ""
+
+BU_: ECU
+
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 10000;
+BA_DEF_DEF_ "GenMsgCycleTime" 0;
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        dbc_content.attribute_default(&message, "GenMsgCycleTime"),
+        Some(&AttributeValue::Uint(0))
+    );
+    assert_eq!(
+        dbc_content.attribute_default(&ObjectRef::Database, "GenMsgCycleTime"),
+        None
+    );
 }
 
 #[test]
 fn lookup_attribute_default_none_when_missing() {
     let dbc_content = Dbc::try_from(SAMPLE_DBC).unwrap();
-    assert_eq!(dbc_content.attribute_default("Nonexistent"), None);
+    assert_eq!(
+        dbc_content.attribute_default(&ObjectRef::Database, "Nonexistent"),
+        None
+    );
 }
 
 #[test]
@@ -292,3 +331,816 @@ fn resolved_signal_attribute_none_when_no_value_and_no_default() {
         dbc_content.resolved_signal_attribute(MessageId::Standard(1840), "Signal_3", "Nonexistent");
     assert_eq!(value, None);
 }
+
+#[test]
+fn write_and_parse_round_trip() {
+    let dbc_content = Dbc::try_from(SAMPLE_DBC).unwrap();
+    let written = dbc_content.to_string();
+    let reparsed = Dbc::try_from(written.as_str())
+        .unwrap_or_else(|e| panic!("Failed to parse written DBC: {e}\n{written}"));
+    assert_eq!(dbc_content, reparsed);
+}
+
+#[test]
+fn write_and_parse_round_trip_trailing_backslash() {
+    let mut dbc_content = Dbc::try_from(SAMPLE_DBC).unwrap();
+    let path = "C:\\path\\";
+    dbc_content.comments.push(Comment::Node {
+        name: "PC".into(),
+        comment: path.into(),
+    });
+    let written = dbc_content.to_string();
+    let reparsed = Dbc::try_from(written.as_str())
+        .unwrap_or_else(|e| panic!("Failed to parse written DBC: {e}\n{written}"));
+    assert_eq!(dbc_content, reparsed);
+    assert_eq!(
+        reparsed.comment_for(&ObjectRef::Node("PC".into())),
+        Some(path)
+    );
+}
+
+#[test]
+fn write_and_parse_round_trip_examples() {
+    for entry in std::fs::read_dir("examples").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "dbc") {
+            let data = std::fs::read_to_string(&path).unwrap();
+            let dbc_content = Dbc::try_from(data.as_str()).unwrap();
+            let written = dbc_content.to_string();
+            let reparsed = Dbc::try_from(written.as_str()).unwrap_or_else(|e| {
+                panic!(
+                    "Failed to parse {} after writing: {e}\n{written}",
+                    path.display()
+                )
+            });
+            assert_eq!(dbc_content, reparsed, "{}", path.display());
+        }
+    }
+}
+
+#[test]
+fn decode_message_payload() {
+    let dbc_content = Dbc::try_from(SAMPLE_DBC).unwrap();
+    let message = dbc_content
+        .messages
+        .iter()
+        .find(|m| m.id == MessageId::Standard(3040))
+        .unwrap();
+    let values = message.decode(&[0x31, 0x23, 0x45, 0, 0, 0, 0, 0]).unwrap();
+    let value = |name: &str| {
+        values
+            .iter()
+            .find(|v| v.signal.name == name)
+            .map(|v| v.physical)
+            .unwrap()
+    };
+    assert!((value("Switch") - 3.0).abs() < f64::EPSILON);
+    assert!((value("Signal_4") - 35.0).abs() < f64::EPSILON);
+    assert!((value("Signal_5") - 69.0).abs() < f64::EPSILON);
+    // big endian 12 bit signal starting at bit 3: low nibble of byte 0, followed by byte 1
+    assert!((value("Signal_2") - 291.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn encode_message_payload() {
+    let dbc_content = Dbc::try_from(SAMPLE_DBC).unwrap();
+    let message = dbc_content
+        .messages
+        .iter()
+        .find(|m| m.id == MessageId::Standard(3040))
+        .unwrap();
+    let values = std::collections::HashMap::from([("Switch", 1.0), ("Signal_2", 291.0)]);
+    let data = message.encode(values).unwrap();
+    assert_eq!(data, [0x11, 0x23, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn float_signal_payload() {
+    let dbc = Dbc::try_from(
+        r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 100 Floats: 16 ECU
+ SG_ Single : 0|32@1- (1,0) [0|0] "" Vector__XXX
+ SG_ Double : 39|64@0- (1,0) [0|0] "" Vector__XXX
+ SG_ Counter : 96|8@1+ (1,0) [0|0] "" Vector__XXX
+
+SIG_VALTYPE_ 100 Single : 1;
+SIG_VALTYPE_ 100 Double : 2;
+"#,
+    )
+    .unwrap();
+    let id = MessageId::Standard(100);
+
+    let values = [("Single", 1.5), ("Double", -0.1), ("Counter", 7.0)];
+    let data = dbc.encode_message(id, values).unwrap();
+    assert_eq!(data[..4], 1.5_f32.to_le_bytes());
+    assert_eq!(data[4..12], (-0.1_f64).to_be_bytes());
+    assert_eq!(data[12], 7);
+
+    let decoded = dbc.decode_message(id, &data).unwrap();
+    let physical: Vec<_> = decoded.iter().map(|s| s.physical).collect();
+    assert_eq!(physical, [1.5, -0.1, 7.0]);
+
+    assert!(dbc.decode_message(MessageId::Standard(1), &data).is_err());
+}
+
+#[test]
+fn decode_extended_multiplexed_message() {
+    let dbc = Dbc::try_from(
+        r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 200 Nested: 4 ECU
+ SG_ Mode M : 0|8@1+ (1,0) [0|0] "" Vector__XXX
+ SG_ SubMode m1M : 8|8@1+ (1,0) [0|0] "" Vector__XXX
+ SG_ Speed m0 : 16|16@1+ (0.1,0) [0|0] "km/h" Vector__XXX
+ SG_ Torque m0 : 16|16@1+ (1,0) [0|0] "Nm" Vector__XXX
+ SG_ Ranged m2 : 8|8@1+ (1,0) [0|0] "" Vector__XXX
+
+SG_MUL_VAL_ 200 SubMode Mode 1-1;
+SG_MUL_VAL_ 200 Speed SubMode 0-3, 10-10;
+SG_MUL_VAL_ 200 Torque SubMode 4-9;
+SG_MUL_VAL_ 200 Ranged Mode 2-5;
+"#,
+    )
+    .unwrap();
+    let id = MessageId::Standard(200);
+    let active = |data: &[u8]| {
+        let decoded = dbc.decode_multiplexed(id, data).unwrap();
+        let names: Vec<_> = decoded
+            .signals
+            .iter()
+            .map(|s| s.signal.name.clone())
+            .collect();
+        let branch: Vec<_> = decoded
+            .branch
+            .iter()
+            .map(|b| (b.multiplexor.name.clone(), b.value))
+            .collect();
+        (names, branch)
+    };
+
+    assert_eq!(
+        active(&[1, 10, 0x10, 0]),
+        (
+            vec!["Mode".into(), "SubMode".into(), "Speed".into()],
+            vec![("Mode".into(), 1), ("SubMode".into(), 10)]
+        )
+    );
+    assert_eq!(
+        active(&[1, 5, 0x10, 0]),
+        (
+            vec!["Mode".into(), "SubMode".into(), "Torque".into()],
+            vec![("Mode".into(), 1), ("SubMode".into(), 5)]
+        )
+    );
+    assert_eq!(
+        active(&[4, 5, 0x10, 0]),
+        (
+            vec!["Mode".into(), "Ranged".into()],
+            vec![("Mode".into(), 4)]
+        )
+    );
+    assert_eq!(
+        active(&[9, 5, 0x10, 0]),
+        (vec!["Mode".into()], vec![("Mode".into(), 9)])
+    );
+
+    let decoded = dbc.decode_multiplexed(id, &[1, 2, 0x10, 0]).unwrap();
+    let speed = decoded.signal("Speed").unwrap();
+    assert!((speed.physical - 1.6).abs() < 1e-9);
+}
+
+#[test]
+fn error_location() {
+    let dbc = "VERSION \"\"\n\nBU_: ECU\n\nBO_ 100 Msg: 99999999999999999999 ECU\n";
+    let err = Dbc::try_from(dbc).unwrap_err();
+    assert_eq!(
+        err.inner(),
+        &DbcError::InvalidUint("99999999999999999999".to_string())
+    );
+    let location = err.location().unwrap();
+    assert_eq!((location.line, location.column), (5, 14));
+    assert_eq!(location.snippet, "99999999999999999999");
+    assert_eq!(
+        err.to_string(),
+        "Invalid Uint value: '99999999999999999999' at line 5, column 14"
+    );
+    assert_eq!(
+        err.report("network.dbc"),
+        "error: Invalid Uint value: '99999999999999999999'
+ --> network.dbc:5:14
+  |
+5 | BO_ 100 Msg: 99999999999999999999 ECU
+  |              ^^^^^^^^^^^^^^^^^^^^
+"
+    );
+
+    // errors without a more specific location point at the statement
+    let dbc = "VERSION \"\"\n\nBU_: ECU\n\nCM_ BO_ 4294967296 \"out of range\";\n";
+    let err = Dbc::try_from(dbc).unwrap_err();
+    assert!(matches!(err.inner(), DbcError::MessageIdOutOfRange(_)));
+    let location = err.location().unwrap();
+    assert_eq!((location.line, location.column), (5, 1));
+}
+
+#[test]
+fn lenient_parsing() {
+    let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 100 Good: 8 ECU
+ SG_ Speed : 0|16@1+ (1,0) [0|0] "" ECU
+ SG_ Broken : 16|8@1+ (1,0) [0|0 "" ECU
+ SG_ Other : 24|8@1+ (1,0) [0|0] "" ECU
+
+CM_ BO_ 4294967296 "out of range";
+
+CM_ BO_ 100 "kept";
+"#;
+    assert!(Dbc::try_from(dbc).is_err());
+
+    let options = ParseOptions::default().lenient(true);
+    let (parsed, warnings) = Dbc::parse_with_options(dbc, &options).unwrap();
+    let signals: Vec<_> = parsed.messages[0]
+        .signals
+        .iter()
+        .map(|s| s.name.as_ref())
+        .collect();
+    assert_eq!(signals, ["Speed", "Other"]);
+    assert_eq!(
+        parsed.message_comment(MessageId::Standard(100)),
+        Some("kept")
+    );
+
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[0].location.line, 12);
+    assert!(matches!(warnings[0].reason, DbcError::Pest(_)));
+    assert_eq!(
+        warnings[1].location.snippet,
+        r#"CM_ BO_ 4294967296 "out of range";"#
+    );
+    assert!(matches!(
+        warnings[1].reason.inner(),
+        DbcError::MessageIdOutOfRange(_)
+    ));
+    assert_eq!(
+        warnings[1].to_string(),
+        "Skipped statement at line 15, column 1: Message ID out of range: 4294967296"
+    );
+
+    // strict parsing without errors yields no warnings
+    let (_, warnings) = Dbc::parse_with_options(SAMPLE_DBC, &ParseOptions::default()).unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn bit_timing_round_trip() {
+    let dbc = Dbc::try_from("VERSION \"\"\n\nNS_ :\n\nBS_: 500 : 12,34\n\nBU_: ECU\n").unwrap();
+    let bit_timing = dbc.bit_timing.as_deref().unwrap();
+    assert_eq!(
+        bit_timing,
+        [BitTiming {
+            baudrate: 500,
+            btr1: 12,
+            btr2: 34,
+        }]
+    );
+    let written = dbc.to_string();
+    assert!(written.contains("BS_: 500 : 12,34\n"));
+    assert_eq!(Dbc::try_from(written.as_str()).unwrap(), dbc);
+}
+
+const SIGNAL_TYPE_DBC: &str = r#"
+VERSION ""
+
+NS_ :
+    SGTYPE_
+    SIG_TYPE_REF_
+
+BS_:
+
+BU_: ECU
+
+VAL_TABLE_ Temperatures 0 "Cold" 255 "Hot" ;
+
+BO_ 100 Engine: 8 ECU
+ SG_ Engine_Temp : 0|8@1+ (1,0) [0|0] "" ECU
+
+SGTYPE_ Temperature : 12@0- (0.5,-40) [-40|87.5] "degC" 0, Temperatures;
+
+SIG_TYPE_REF_ 100 Engine_Temp : Temperature;
+"#;
+
+#[test]
+fn signal_types() {
+    let mut dbc = Dbc::try_from(SIGNAL_TYPE_DBC).unwrap();
+    assert_eq!(dbc.signal_types.len(), 1);
+    assert_eq!(dbc.signal_type_refs.len(), 1);
+    let id = MessageId::Standard(100);
+    let signal_type = dbc.signal_type_for_signal(id, "Engine_Temp").unwrap();
+    assert_eq!(signal_type.name, "Temperature");
+
+    let written = dbc.to_string();
+    assert!(written
+        .contains("SGTYPE_ Temperature : 12@0- (0.5,-40) [-40|87.5] \"degC\" 0, Temperatures;\n"));
+    assert!(written.contains("SIG_TYPE_REF_ 100 Engine_Temp : Temperature;\n"));
+    assert_eq!(Dbc::try_from(written.as_str()).unwrap(), dbc);
+
+    dbc.resolve_signal_types();
+    let signal = dbc.signal_by_name(id, "Engine_Temp").unwrap();
+    assert_eq!(signal.size, 12);
+    assert_eq!((signal.factor, signal.offset), (0.5, -40.0));
+    assert_eq!(signal.unit, "degC");
+    let descriptions = dbc
+        .value_descriptions_for_signal(id, "Engine_Temp")
+        .unwrap();
+    assert_eq!(descriptions.len(), 2);
+    assert!(dbc.validate().is_empty());
+}
+
+#[test]
+fn invalid_signal_type() {
+    let input = SIGNAL_TYPE_DBC.replace("12@0-", "12@");
+    let err = Dbc::try_from(input.as_str()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid SGTYPE_ statement, expected byte order and value type, e.g. 1+ at line 17, column 1"
+    );
+
+    let (dbc, warnings) =
+        Dbc::parse_with_options(&input, &ParseOptions::default().lenient(true)).unwrap();
+    assert!(dbc.signal_types.is_empty());
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(dbc.messages.len(), 1);
+}
+
+#[test]
+fn missing_statement_terminator() {
+    let input = "VERSION \"\"\n\nBU_: ECU\n\nCAT_ BU_ ECU 1\nCM_ BU_ ECU \"kept\";\n";
+    let err = Dbc::try_from(input).unwrap_err();
+    assert_eq!(err.location().unwrap().snippet, "CAT_ BU_ ECU 1");
+
+    let (dbc, warnings) =
+        Dbc::parse_with_options(input, &ParseOptions::default().lenient(true)).unwrap();
+    assert!(dbc.categories.is_empty());
+    assert_eq!(
+        dbc.comment_for(&ObjectRef::Node("ECU".into())),
+        Some("kept")
+    );
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].location.line, 5);
+}
+
+#[test]
+fn relation_attributes() {
+    let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: Gateway ECU
+
+BO_ 100 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (1,0) [0|0] "" Gateway
+
+EV_ Ignition: 0 [0|1] "" 0 1 DUMMY_NODE_VECTOR0 Gateway;
+
+BA_DEF_REL_ BU_EV_REL_ "EvAttr" INT 0 100;
+BA_DEF_REL_ BU_SG_REL_ "SgAttr" INT 0 100;
+BA_DEF_DEF_REL_ "EvAttr" 7;
+BA_DEF_DEF_REL_ "SgAttr" 3;
+BA_REL_ "EvAttr" BU_EV_REL_ Gateway EV_ Ignition 42;
+BA_REL_ "SgAttr" BU_SG_REL_ Gateway SG_ 100 Speed 9;
+"#;
+    let dbc = Dbc::try_from(dbc).unwrap();
+    let id = MessageId::Standard(100);
+    assert_eq!(
+        dbc.node_environment_variable_attribute("Gateway", "Ignition", "EvAttr"),
+        Some(&AttributeValue::Uint(42))
+    );
+    assert_eq!(
+        dbc.node_environment_variable_attribute("ECU", "Ignition", "EvAttr"),
+        None
+    );
+    assert_eq!(
+        dbc.resolved_node_environment_variable_attribute("ECU", "Ignition", "EvAttr"),
+        Some(&AttributeValue::Uint(7))
+    );
+    assert_eq!(
+        dbc.node_signal_attribute("Gateway", id, "Speed", "SgAttr"),
+        Some(&AttributeValue::Uint(9))
+    );
+    assert_eq!(
+        dbc.resolved_node_signal_attribute("ECU", id, "Speed", "SgAttr"),
+        Some(&AttributeValue::Uint(3))
+    );
+    // the default only applies to the kind of relation the attribute is defined for
+    assert_eq!(
+        dbc.resolved_node_message_attribute("Gateway", id, "SgAttr"),
+        None
+    );
+    assert!(dbc.validate().is_empty());
+
+    let written = dbc.to_string();
+    assert!(written.contains("BA_REL_ \"EvAttr\" BU_EV_REL_ Gateway EV_ Ignition 42;\n"));
+    assert_eq!(Dbc::try_from(written.as_str()).unwrap(), dbc);
+}
+
+#[test]
+fn obsolete_categories_and_filters() {
+    let dbc = r#"
+VERSION ""
+
+NS_ :
+    CAT_DEF_
+    CAT_
+    FILTER
+
+BS_:
+
+BU_: Gateway
+
+BO_ 100 Engine: 8 Gateway
+ SG_ Speed : 0|16@1+ (1,0) [0|0] "" Gateway
+
+CAT_DEF_ 1 Powertrain 0;
+CAT_ BU_ Gateway 1;
+CAT_ BO_ 100 1;
+FILTER 0 "Engine" BO_ 100;
+"#;
+    let dbc = Dbc::try_from(dbc).unwrap();
+    assert_eq!(dbc.category_definitions.len(), 1);
+    assert_eq!(dbc.category_definitions[0].name, "Powertrain");
+    assert_eq!(dbc.categories.len(), 2);
+    assert_eq!(dbc.filter.len(), 1);
+
+    let written = dbc.to_string();
+    assert!(written.contains("CAT_DEF_ 1 Powertrain 0;\n\nCAT_ BU_ Gateway 1;\nCAT_ BO_ 100 1;\n"));
+    assert!(written.contains("FILTER 0 \"Engine\" BO_ 100;\n"));
+    assert_eq!(Dbc::try_from(written.as_str()).unwrap(), dbc);
+}
+
+#[test]
+fn borrowed_strings() {
+    let buffer = SAMPLE_DBC.to_string();
+    let dbc = Dbc::try_from(buffer.as_str()).unwrap();
+    let message = &dbc.messages[0];
+    assert!(matches!(message.name, Cow::Borrowed(_)));
+    assert!(matches!(message.signals[0].name, Cow::Borrowed(_)));
+
+    // an owned copy outlives the input buffer
+    let written = dbc.to_string();
+    let owned: Dbc<'static> = dbc.into_static();
+    drop(buffer);
+    assert!(matches!(owned.messages[0].name, Cow::Owned(_)));
+    assert_eq!(owned.to_string(), written);
+}
+
+#[test]
+fn borrowed_strings_after_rewriting_input() {
+    // the extracted CAT_ statement and the skipped line contain multi-byte characters
+    let buffer = r#"
+VERSION ""
+
+BU_: ECU
+
+CAT_DEF_ 1 Größe 0;
+CAT_ BU_ ECU 1;
+this line is invalid: Öl
+
+BO_ 100 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (1,0) [0|0] "" ECU
+
+CM_ BO_ 100 "Motor \"Öl\"";
+"#;
+    let (dbc, warnings) =
+        Dbc::parse_with_options(buffer, &ParseOptions::default().lenient(true)).unwrap();
+    assert_eq!(warnings.len(), 1);
+    let message = &dbc.messages[0];
+    assert!(matches!(message.name, Cow::Borrowed("Engine")));
+    assert!(matches!(message.signals[0].name, Cow::Borrowed("Speed")));
+    assert!(matches!(
+        dbc.categories[0].object,
+        CategoryObject::Node(Cow::Borrowed("ECU"))
+    ));
+    // unescaped strings can't be borrowed
+    assert_eq!(
+        dbc.comment_for(&ObjectRef::Message(MessageId::Standard(100))),
+        Some("Motor \"Öl\"")
+    );
+}
+
+#[test]
+fn attribute_defined_for_several_kinds() {
+    let dbc = Dbc::try_from(
+        r#"
+VERSION ""
+
+BU_: ECU
+
+BO_ 100 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (1,0) [0|0] "" ECU
+
+BA_DEF_ SG_ "Mode" STRING;
+BA_DEF_ BO_ "Mode" INT 0 10;
+BA_DEF_ EV_ "Level" INT 0 10;
+BA_DEF_ BO_ "Level" STRING;
+BA_DEF_DEF_ "Level" "high";
+BA_ "Mode" BO_ 100 5;
+BA_ "Mode" SG_ 100 Speed "fast";
+"#,
+    )
+    .unwrap();
+    let message = ObjectRef::Message(MessageId::Standard(100));
+    let signal = ObjectRef::Signal {
+        message_id: MessageId::Standard(100),
+        signal_name: "Speed".into(),
+    };
+    assert_eq!(
+        dbc.attribute_definition_for(&message, "Mode"),
+        Some(&dbc.attribute_definitions[1])
+    );
+    assert_eq!(
+        dbc.attribute_definition_for(&signal, "Mode"),
+        Some(&dbc.attribute_definitions[0])
+    );
+    assert_eq!(
+        dbc.attribute_definition_for(&ObjectRef::Database, "Mode"),
+        None
+    );
+
+    assert_eq!(
+        dbc.resolved_typed_attribute(&message, "Mode"),
+        Ok(Some(TypedAttributeValue::Int(5)))
+    );
+    assert_eq!(
+        dbc.resolved_typed_attribute(&signal, "Mode"),
+        Ok(Some(TypedAttributeValue::String("fast")))
+    );
+
+    // each value is checked against the definition for its kind, the default against all definitions
+    let diagnostics = dbc.validate();
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(
+        diagnostics[0].location,
+        DiagnosticLocation::AttributeDefault {
+            name: "Level".into(),
+            object_type: Some("EV_"),
+        }
+    );
+    assert!(diagnostics[0].message.contains("Level"));
+
+    let index = dbc.index();
+    let level = AttributeValue::String("high".into());
+    assert_eq!(
+        index.resolved_message_attribute(MessageId::Standard(100), "Level"),
+        Some(&level)
+    );
+    assert_eq!(
+        index.attribute_default(&message, "Level"),
+        dbc.attribute_default(&message, "Level")
+    );
+    assert_eq!(index.attribute_default(&signal, "Level"), None);
+}
+
+#[test]
+fn typed_attributes() {
+    let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU
+
+BO_ 100 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (1,0) [0|0] "" ECU
+
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 10000;
+BA_DEF_ BO_ "GenMsgSendType" ENUM "Cyclic","Spontaneous","IfActive";
+BA_DEF_ BO_ "GenMsgStartDelayTime" HEX 0 65535;
+BA_DEF_ SG_ "GenSigStartValue" FLOAT -1000 1000;
+BA_DEF_DEF_ "GenMsgCycleTime" 0;
+BA_DEF_DEF_ "GenMsgSendType" "Cyclic";
+BA_DEF_DEF_ "GenSigStartValue" 0;
+BA_ "GenMsgCycleTime" BO_ 100 50;
+BA_ "GenMsgSendType" BO_ 100 2;
+BA_ "GenMsgStartDelayTime" BO_ 100 20000;
+"#;
+    let dbc = Dbc::try_from(dbc).unwrap();
+    let id = MessageId::Standard(100);
+    assert_eq!(
+        dbc.typed_message_attribute(id, "GenMsgCycleTime"),
+        Ok(Some(TypedAttributeValue::Int(50)))
+    );
+    assert_eq!(
+        dbc.typed_message_attribute(id, "GenMsgSendType"),
+        Ok(Some(TypedAttributeValue::Enum {
+            index: 2,
+            label: "IfActive"
+        }))
+    );
+    assert_eq!(
+        dbc.typed_message_attribute(id, "GenMsgStartDelayTime"),
+        Ok(Some(TypedAttributeValue::Hex(20000)))
+    );
+    assert_eq!(
+        dbc.typed_signal_attribute(id, "Speed", "GenSigStartValue"),
+        Ok(Some(TypedAttributeValue::Float(0.0)))
+    );
+    assert_eq!(dbc.typed_message_attribute(id, "Missing"), Ok(None));
+    assert!(dbc.validate().is_empty());
+}
+
+#[test]
+fn resolved_attributes() {
+    let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: Gateway ECU
+
+BO_ 100 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (1,0) [0|0] "" Gateway
+
+EV_ Ignition: 0 [0|1] "" 0 1 DUMMY_NODE_VECTOR0 Gateway;
+
+BA_DEF_  "DBName" STRING;
+BA_DEF_ BU_ "NodeLayerModules" STRING;
+BA_DEF_ EV_ "EvAttr" INT 0 10;
+BA_DEF_REL_ BU_BO_REL_ "MsgRelAttr" INT 0 10;
+BA_DEF_DEF_ "DBName" "";
+BA_DEF_DEF_ "NodeLayerModules" "CANoeILNVector.dll";
+BA_DEF_DEF_ "EvAttr" 1;
+BA_DEF_DEF_REL_ "MsgRelAttr" 4;
+BA_ "DBName" "Powertrain";
+BA_ "NodeLayerModules" BU_ Gateway "Gateway.dll";
+BA_REL_ "MsgRelAttr" BU_BO_REL_ Gateway 100 5;
+"#;
+    let dbc = Dbc::try_from(dbc).unwrap();
+    let id = MessageId::Standard(100);
+    let gateway = ObjectRef::Node("Gateway".into());
+    let ecu = ObjectRef::Node("ECU".into());
+    let string = |s: &'static str| Some(AttributeValue::String(s.into()));
+    assert_eq!(
+        dbc.resolved_attribute(&ObjectRef::Database, "DBName")
+            .cloned(),
+        string("Powertrain")
+    );
+    assert_eq!(
+        dbc.resolved_attribute(&gateway, "NodeLayerModules")
+            .cloned(),
+        string("Gateway.dll")
+    );
+    assert_eq!(dbc.attribute(&ecu, "NodeLayerModules"), None);
+    assert_eq!(
+        dbc.resolved_attribute(&ecu, "NodeLayerModules").cloned(),
+        string("CANoeILNVector.dll")
+    );
+    // defaults only apply to the kind of object the attribute is defined for
+    assert_eq!(
+        dbc.resolved_attribute(&ObjectRef::Message(id), "NodeLayerModules"),
+        None
+    );
+    assert_eq!(
+        dbc.resolved_typed_attribute(&ObjectRef::EnvironmentVariable("Ignition".into()), "EvAttr"),
+        Ok(Some(TypedAttributeValue::Int(1)))
+    );
+
+    let relation = |node_name: &str| ObjectRef::NodeToMessage {
+        node_name: node_name.into(),
+        message_id: id,
+    };
+    assert_eq!(
+        dbc.resolved_attribute(&relation("Gateway"), "MsgRelAttr"),
+        Some(&AttributeValue::Uint(5))
+    );
+    assert_eq!(
+        dbc.resolved_typed_attribute(&relation("ECU"), "MsgRelAttr"),
+        Ok(Some(TypedAttributeValue::Int(4)))
+    );
+    assert_eq!(
+        dbc.resolved_attribute(&ObjectRef::Message(id), "MsgRelAttr"),
+        None
+    );
+    assert!(dbc.validate().is_empty());
+}
+
+#[test]
+fn vector_attributes() {
+    let dbc = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU Gateway
+
+BO_ 100 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (1,0) [0|0] "" Gateway
+
+BO_ 2566914046 Diagnostics: 64 Gateway
+ SG_ Status : 0|8@1+ (1,0) [0|0] "" ECU
+
+BA_DEF_  "BusType" STRING;
+BA_DEF_  "DBName" STRING;
+BA_DEF_ BU_ "NmStationAddress" HEX 0 255;
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 65535;
+BA_DEF_ BO_ "GenMsgDelayTime" INT 0 65535;
+BA_DEF_ BO_ "GenMsgSendType" ENUM "Cyclic","not_used","not_used","not_used","not_used","Cyclic","not_used","IfActive","NoMsgSendType";
+BA_DEF_ BO_ "GenMsgILSupport" ENUM "No","Yes";
+BA_DEF_ BO_ "VFrameFormat" ENUM "StandardCAN","ExtendedCAN","reserved","J1939PG","reserved","reserved","reserved","reserved","reserved","reserved","reserved","reserved","reserved","reserved","StandardCAN_FD","ExtendedCAN_FD";
+BA_DEF_ BO_ "CANFD_BRS" ENUM "0","1";
+BA_DEF_ SG_ "GenSigStartValue" FLOAT 0 100000;
+BA_DEF_ SG_ "GenSigSendType" ENUM "Cyclic","OnWrite","OnWriteWithRepetition","OnChange","OnChangeWithRepetition","IfActive","IfActiveWithRepetition","NoSigSendType";
+BA_DEF_DEF_ "BusType" "CAN FD";
+BA_DEF_DEF_ "DBName" "";
+BA_DEF_DEF_ "NmStationAddress" 0;
+BA_DEF_DEF_ "GenMsgCycleTime" 0;
+BA_DEF_DEF_ "GenMsgDelayTime" 0;
+BA_DEF_DEF_ "GenMsgSendType" "NoMsgSendType";
+BA_DEF_DEF_ "GenMsgILSupport" "Yes";
+BA_DEF_DEF_ "VFrameFormat" "StandardCAN";
+BA_DEF_DEF_ "CANFD_BRS" "1";
+BA_DEF_DEF_ "GenSigStartValue" 0;
+BA_DEF_DEF_ "GenSigSendType" "Cyclic";
+BA_ "DBName" "Powertrain";
+BA_ "NmStationAddress" BU_ Gateway 42;
+BA_ "GenMsgCycleTime" BO_ 100 10;
+BA_ "GenMsgSendType" BO_ 100 0;
+BA_ "GenMsgSendType" BO_ 2566914046 7;
+BA_ "GenMsgILSupport" BO_ 2566914046 0;
+BA_ "VFrameFormat" BO_ 2566914046 15;
+BA_ "GenSigStartValue" SG_ 100 Speed 1500;
+BA_ "GenSigSendType" SG_ 100 Speed 3;
+"#;
+    let dbc = Dbc::try_from(dbc).unwrap();
+    let engine = MessageId::Standard(100);
+    let diagnostics = MessageId::Extended(0x18FF_FFFE);
+    assert_eq!(dbc.bus_type(), Some("CAN FD"));
+    assert_eq!(dbc.db_name(), Some("Powertrain"));
+    assert_eq!(dbc.node_nm_station_address("Gateway"), Some(42));
+    assert_eq!(dbc.node_nm_station_address("ECU"), Some(0));
+
+    assert_eq!(
+        dbc.message_cycle_time(engine),
+        Some(Duration::from_millis(10))
+    );
+    assert_eq!(dbc.message_delay_time(engine), Some(Duration::ZERO));
+    assert_eq!(dbc.message_send_type(engine), Some(SendType::Cyclic));
+    assert_eq!(dbc.message_send_type(diagnostics), Some(SendType::IfActive));
+    assert_eq!(dbc.message_il_support(engine), Some(true));
+    assert_eq!(dbc.message_il_support(diagnostics), Some(false));
+    assert_eq!(
+        dbc.message_frame_format(engine),
+        Some(FrameFormat::StandardCan)
+    );
+    assert_eq!(
+        dbc.message_frame_format(diagnostics),
+        Some(FrameFormat::ExtendedCanFd)
+    );
+
+    assert!(!dbc.message_is_fd(engine));
+    assert!(dbc.message_is_fd(diagnostics));
+    assert_eq!(dbc.message_bit_rate_switch(diagnostics), Some(true));
+    assert_eq!(dbc.messages[1].dlc(), Some(15));
+    assert_eq!(dlc_to_length(15), Some(64));
+
+    assert_eq!(dbc.signal_start_value(engine, "Speed"), Some(1500.0));
+    assert_eq!(
+        dbc.signal_send_type(engine, "Speed"),
+        Some(SignalSendType::OnChange)
+    );
+    assert_eq!(
+        dbc.signal_send_type(diagnostics, "Status"),
+        Some(SignalSendType::Cyclic)
+    );
+    assert!(dbc.validate().is_empty());
+}
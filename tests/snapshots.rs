@@ -159,6 +159,14 @@ fn parse_one_file([path]: [&Path; 1]) {
     let result = Dbc::try_from(buffer.as_ref());
     let is_err = result.is_err();
 
+    if let Ok(dbc) = &result {
+        // Writing the parsed file back to text must not lose any information
+        let written = dbc.to_string();
+        let reparsed = Dbc::try_from(written.as_str())
+            .unwrap_or_else(|e| panic!("Failed to parse written {}.dbc: {e:#?}", test.file_name));
+        assert_eq!(dbc, &reparsed, "Round trip of {}.dbc", test.file_name);
+    }
+
     if let Some(snapshot_path) = test.snapshot_path(is_err) {
         with_settings! {
             {